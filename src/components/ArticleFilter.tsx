@@ -23,6 +23,7 @@ interface FilterResult {
   page: number;
   limit: number;
   total_pages: number;
+  warnings?: string[];
 }
 
 // WASM模块接口
@@ -808,6 +809,11 @@ const ArticleFilter: React.FC<ArticleFilterProps> = ({ searchParams = {} }) => {
         throw new Error("筛选结果格式错误");
       }
       
+      // 开发时输出被忽略或纠正的筛选参数
+      if (import.meta.env.DEV && result.warnings?.length) {
+        console.warn("筛选参数警告:", result.warnings);
+      }
+      
       // 确保有一个文章数组
       let articles: Article[] = [];
       
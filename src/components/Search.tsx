@@ -7,9 +7,14 @@ interface SearchResult {
   page: number;
   page_size: number;
   total_pages: number;
-  time_ms: number;
   query: string;
   suggestions: SearchSuggestion[];
+  diagnostics?: SearchDiagnostics | null;
+}
+
+// 诊断信息，关闭计时后为空
+interface SearchDiagnostics {
+  time_ms: number;
 }
 
 interface SearchResultItem {
@@ -1317,7 +1322,7 @@ const Search: React.FC<SearchProps> = ({
       return null;
     }
 
-    const { total, time_ms } = searchResults;
+    const { total, diagnostics } = searchResults;
 
     // 检查是否已加载所有结果
     const hasLoadedAllResults = allItems.length >= total;
@@ -1330,7 +1335,8 @@ const Search: React.FC<SearchProps> = ({
         <div className="p-4">
           <div className="flex justify-between items-center mb-3 pb-2 border-b border-gray-200 dark:border-gray-700">
             <div className="text-sm text-gray-600 dark:text-gray-400">
-              找到 {total} 条结果 ({time_ms / 1000} 秒)
+              找到 {total} 条结果
+              {diagnostics && ` (${diagnostics.time_ms / 1000} 秒)`}
             </div>
           </div>
 
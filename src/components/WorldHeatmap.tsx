@@ -28,8 +28,12 @@ const loadControlsAndRenderers = () => Promise.all([
 // WASM模块接口
 interface GeoWasmModule {
   GeoProcessor: new () => {
-    process_geojson: (worldData: string, chinaData: string, visitedPlaces: string, scale: number) => void;
+    // precision: 0 = 双精度（默认），1 = 单精度
+    process_geojson: (worldData: string, chinaData: string, visitedPlaces: string, scale: number, precision?: number, tolerance?: number) => void;
     get_boundary_lines: () => any[];
+    get_boundary_lines_lod: (level: number) => any[];
+    get_boundary_positions: (level?: number) => { positions: Float32Array; lines: { region_name: string; is_visited: boolean; offset: number; count: number }[] };
+    get_visited_stats: () => { country_count: number; visited_country_count: number; province_count: number; visited_province_count: number; region_count: number; visited_region_count: number; visited_percentage: number; visited: { name: string; center: { x: number; y: number; z: number }; lat: number; lon: number }[] };
     find_nearest_country: (x: number, y: number, z: number, radius: number) => string | null;
   };
   default?: () => Promise<any>;
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 替代查询
+ */
+export type AlternativeQuery = { 
+/**
+ * 查询文本
+ */
+query: string, 
+/**
+ * 按该查询搜索的结果数
+ */
+total: number, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 生效的筛选条件
+ */
+export type AppliedFilter = { 
+/**
+ * 筛选字段：scope（搜索范围）、visibility（最高可见级别）、
+ * page_type_boost（页面类型分数系数）、session（复用会话候选文章）、
+ * tag（标签筛选）、category（分类筛选）
+ */
+field: string, 
+/**
+ * 筛选值
+ */
+values: Array<string>, };
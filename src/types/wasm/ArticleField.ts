@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 文章字段 - 用于筛选参数的 `fields` 字段选择
+ */
+export type ArticleField = "id" | "title" | "summary" | "date" | "tags" | "url" | "featured" | "series" | "author" | "word_count" | "reading_minutes";
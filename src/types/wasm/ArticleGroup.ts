@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ListedArticle } from "./ListedArticle";
+
+/**
+ * 时间线分组 - 同一年或同一月发布的文章
+ */
+export type ArticleGroup = { 
+/**
+ * ISO格式的分组键，按年分组时为 YYYY，按月分组时为 YYYY-MM（UTC），与归档中的键相同
+ */
+key: string, 
+/**
+ * 按当前界面语言格式化的标签（如 "2024年3月"、"March 2024"）
+ */
+label: string, 
+/**
+ * 该分组中符合筛选条件的文章总数（包括其他页的文章）
+ */
+count: number, 
+/**
+ * 当前页中属于该分组的文章，保持排序后的顺序
+ */
+articles: Array<ListedArticle>, };
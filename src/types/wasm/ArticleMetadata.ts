@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 简化的文章元数据 - 只包含展示所需信息
+ */
+export type ArticleMetadata = { 
+/**
+ * 文章唯一标识符
+ */
+id: string, 
+/**
+ * 文章标题
+ */
+title: string, 
+/**
+ * 文章摘要
+ */
+summary: string, 
+/**
+ * 发布日期
+ */
+date: string, 
+/**
+ * 文章标签列表
+ */
+tags: Array<string>, 
+/**
+ * 文章URL路径
+ */
+url: string, };
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 分组中的文章
+ */
+export type ClusterItem = { 
+/**
+ * 文章ID
+ */
+id: string, 
+/**
+ * 文章标题
+ */
+title: string, 
+/**
+ * 文章URL
+ */
+url: string, 
+/**
+ * 结果所属的索引名称
+ */
+source: string, 
+/**
+ * 匹配所在的顶级章节ID，与 `heading_tree` 中节点的 `id` 相同
+ */
+heading_id: string, };
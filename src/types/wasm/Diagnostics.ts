@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 搜索诊断信息
+ */
+export type Diagnostics = { 
+/**
+ * 搜索耗时(毫秒)
+ */
+time_ms: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 错误码 - 序列化为 snake_case 字符串，各版本之间保持不变
+ */
+export type FilterErrorCode = "not_initialized" | "index_not_loaded" | "parse_error" | "lock_poisoned" | "invalid_params" | "not_found" | "serialize_error";
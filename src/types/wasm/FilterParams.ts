@@ -0,0 +1,91 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArticleField } from "./ArticleField";
+
+/**
+ * 筛选参数 - 客户端传递的筛选条件
+ */
+export type FilterParams = { 
+/**
+ * 标签筛选条件 (可选)
+ */
+tags?: Array<string>, 
+/**
+ * 标签匹配方式: "any"（带有任一标签，默认）或 "all"（带有全部标签）
+ */
+tag_mode?: string, 
+/**
+ * 排除的标签 - 带有其中任一标签的文章不出现在结果中 (可选)
+ */
+exclude_tags?: Array<string>, 
+/**
+ * 排序方式: "newest", "oldest", "title_asc", "title_desc", "reading_time_asc", "reading_time_desc" (可选)
+ */
+sort?: string, 
+/**
+ * 次要排序方式 (可选)，取值与 `sort` 相同，按 `sort` 排序相同的文章再按该方式排序；
+ * 仍然相同时按文章ID排序，保证每次请求的顺序一致
+ */
+sort_secondary?: string, 
+/**
+ * 按标题排序时的排序规则 (可选): "locale"（默认，按界面语言选择，中文按拼音，英文按Unicode排序规则）、
+ * "pinyin"、"unicode" 或 "binary"（按字节比较）
+ */
+collation?: string, 
+/**
+ * 分页 - 当前页码 (可选, 默认为1)
+ */
+page?: number, 
+/**
+ * 分页 - 每页条数 (可选, 默认为12，不能超过 `MAX_PAGE_LIMIT`)
+ */
+limit?: number, 
+/**
+ * 日期筛选: "all" 或 "startDate,endDate" 格式的日期范围
+ */
+date?: string, 
+/**
+ * 年份筛选，与归档中的年份对应
+ */
+year?: number, 
+/**
+ * 月份筛选: YYYY-MM 格式的月份键，与归档中月份的 `key` 对应
+ */
+month?: string, 
+/**
+ * 关键词筛选 - 标题、摘要或标签中包含该文本（不区分大小写）的文章 (可选)
+ */
+q?: string, 
+/**
+ * 按发布时间分组: "year" 或 "month" (可选)，指定时当前页的文章按年或月放入 `groups`
+ */
+group_by?: string, 
+/**
+ * 结果中包含的文章字段 (可选，默认为全部字段)，如 `["id", "title", "url", "date"]`，
+ * 文章ID总是包含在内。列表页不需要摘要和标签时可以减少序列化和传输的数据量
+ */
+fields?: Array<ArticleField>, 
+/**
+ * 分页游标 (可选)，为上一次结果中的 `next_cursor`，指定时从游标之后的文章开始并忽略页码。
+ * 排序方式和次要排序方式必须与生成游标时相同
+ */
+cursor?: string, 
+/**
+ * 只返回置顶文章 (可选，默认为false)
+ */
+featured_only?: boolean, 
+/**
+ * 置顶文章排在其他文章前面 (可选)，默认为true；指定 `group_by` 或 `series` 时默认为false，保持时间线的顺序
+ */
+featured_first?: boolean, 
+/**
+ * 系列筛选 - 只返回该系列中的文章 (可选)，未指定 `sort` 时按发布时间从早到晚排列，即系列的阅读顺序
+ */
+series?: string, 
+/**
+ * 作者筛选 - 只返回其中任一作者的文章 (可选)
+ */
+authors?: Array<string>, 
+/**
+ * 阅读时间上限 - 只返回估算阅读时间不超过该分钟数的文章 (可选)
+ */
+max_reading_minutes?: number, };
@@ -0,0 +1,40 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArticleGroup } from "./ArticleGroup";
+import type { ListedArticle } from "./ListedArticle";
+
+/**
+ * 筛选结果 - 返回给客户端的筛选结果
+ */
+export type FilterResult = { 
+/**
+ * 筛选后的文章列表，按分组返回时为空
+ */
+articles: Array<ListedArticle>, 
+/**
+ * 筛选结果总数
+ */
+total: number, 
+/**
+ * 当前页码
+ */
+page: number, 
+/**
+ * 每页条数
+ */
+limit: number, 
+/**
+ * 总页数
+ */
+total_pages: number, 
+/**
+ * 参数警告 - 被忽略或替换为默认值的筛选参数，参数都有效时为空
+ */
+warnings: Array<string>, 
+/**
+ * 按年或月分组的当前页文章，按排序后首次出现的顺序排列，未指定 `group_by` 时为空
+ */
+groups: Array<ArticleGroup> | null, 
+/**
+ * 下一页的游标，作为下一次请求的 `cursor` 传入；没有更多文章时为空
+ */
+next_cursor: string | null, };
@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 带有匹配内容的标题节点
+ */
+export type HeadingNode = { 
+/**
+ * 标题ID
+ */
+id: string, 
+/**
+ * 标题文本
+ */
+text: string, 
+/**
+ * 标题级别
+ */
+level: number, 
+/**
+ * 该标题下匹配的内容
+ */
+content: string | null, 
+/**
+ * 匹配的关键词列表
+ */
+matched_terms: Array<string> | null, 
+/**
+ * 子标题列表
+ */
+children: Array<HeadingNode>, };
@@ -0,0 +1,52 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 筛选结果中的文章 - 只包含筛选参数 `fields` 选择的字段，未选择的字段不出现在结果中
+ *
+ * 文章ID总是包含在内；未指定 `fields` 时包含全部字段，与 `ArticleMetadata` 相同。
+ */
+export type ListedArticle = { 
+/**
+ * 文章唯一标识符
+ */
+id: string, 
+/**
+ * 文章标题
+ */
+title?: string, 
+/**
+ * 文章摘要
+ */
+summary?: string, 
+/**
+ * 发布日期
+ */
+date?: string, 
+/**
+ * 文章标签列表
+ */
+tags?: Array<string>, 
+/**
+ * 文章URL路径
+ */
+url?: string, 
+/**
+ * 是否为置顶文章
+ */
+featured?: boolean, 
+/**
+ * 所属系列名称，文章不属于任何系列时不出现在结果中
+ */
+series?: string, 
+/**
+ * 文章作者，未注明作者时不出现在结果中
+ */
+author?: string, 
+/**
+ * 正文词数
+ */
+word_count?: number, 
+/**
+ * 估算的阅读时间（分钟）
+ */
+reading_minutes?: number, };
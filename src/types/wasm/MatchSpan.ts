@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 匹配区间 - 文本中命中查询词的位置
+ *
+ * 偏移量以UTF-16编码单元计算，与JavaScript字符串下标一致，
+ * 并且相对于未插入高亮标记的原始文本。
+ */
+export type MatchSpan = { 
+/**
+ * 起始偏移
+ */
+start: number, 
+/**
+ * 结束偏移（不包含）
+ */
+end: number, 
+/**
+ * 匹配到的原文
+ */
+text: string, };
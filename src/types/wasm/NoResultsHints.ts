@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AlternativeQuery } from "./AlternativeQuery";
+import type { QueryOperator } from "./QueryOperator";
+
+/**
+ * 无结果提示 - 界面可以据此显示"试试: wasm-bindgen, webassembly"，而不是固定的文案
+ */
+export type NoResultsHints = { 
+/**
+ * 与查询最接近的索引词汇，每个词汇单独搜索都有结果
+ */
+terms: Array<string>, 
+/**
+ * 支持的查询用法
+ */
+operators: Array<QueryOperator>, 
+/**
+ * 替代查询 - 纠正拼写或去掉部分单词后的完整查询，按结果数从多到少排列
+ */
+did_you_mean: Array<AlternativeQuery>, };
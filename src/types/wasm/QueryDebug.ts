@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AppliedFilter } from "./AppliedFilter";
+import type { QueryRewrite } from "./QueryRewrite";
+
+/**
+ * 查询处理信息 - 说明查询如何被规范化、切分和改写，以及哪些筛选条件生效
+ *
+ * 界面可以据此显示“rust wasm 的结果（范围: blog）”之类的标题。
+ */
+export type QueryDebug = { 
+/**
+ * 原始查询
+ */
+original: string, 
+/**
+ * 规范化后的查询（去除首尾空白并转为小写）
+ */
+normalized: string, 
+/**
+ * 参与匹配和高亮的词条，第一个为完整查询
+ */
+tokens: Array<string>, 
+/**
+ * 对查询做的改写
+ */
+rewrites: Array<QueryRewrite>, 
+/**
+ * 生效的筛选条件
+ */
+filters: Array<AppliedFilter>, };
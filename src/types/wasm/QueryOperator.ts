@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 查询用法说明
+ */
+export type QueryOperator = { 
+/**
+ * 示例语法
+ */
+syntax: string, 
+/**
+ * 说明
+ */
+description: string, };
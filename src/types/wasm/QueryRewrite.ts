@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RewriteKind } from "./RewriteKind";
+
+/**
+ * 查询改写
+ */
+export type QueryRewrite = { 
+/**
+ * 改写类型
+ */
+kind: RewriteKind, 
+/**
+ * 改写前的文本
+ */
+from: string, 
+/**
+ * 改写后的文本
+ */
+to: Array<string>, };
@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ClusterItem } from "./ClusterItem";
+
+/**
+ * 结果分组 - 所有结果中匹配出现在同名顶级章节（如“部署”）下的文章
+ *
+ * 一篇文章可以属于多个分组。分组覆盖全部结果而不只是当前页。
+ */
+export type ResultCluster = { 
+/**
+ * 分组名称 - 章节标题，同名（不区分大小写）的章节合为一组，按排名最高的文章中的写法显示
+ */
+label: string, 
+/**
+ * 分组中的文章，按结果顺序排列
+ */
+items: Array<ClusterItem>, };
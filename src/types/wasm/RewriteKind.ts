@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 查询改写类型
+ */
+export type RewriteKind = "normalize" | "split" | "loose_match";
@@ -0,0 +1,68 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ResultDetail } from "./ResultDetail";
+import type { SummaryMode } from "./SummaryMode";
+
+/**
+ * 搜索请求结构
+ *
+ * 二进制协议（`search_articles_bin`）按字段定义顺序编码，新增字段只能追加在末尾。
+ * 除查询外的字段都有默认值，TypeScript类型中为可选字段。
+ */
+export type SearchRequest = { 
+/**
+ * 搜索查询
+ */
+query: string, 
+/**
+ * 搜索类型 (normal或autocomplete)
+ */
+search_type?: string, 
+/**
+ * 当前页码
+ */
+page?: number, 
+/**
+ * 每页条数
+ */
+page_size?: number, 
+/**
+ * 摘要来源
+ */
+summary_mode?: SummaryMode, 
+/**
+ * 搜索范围 - 要搜索的已加载索引名称，为空时搜索所有索引
+ */
+scope?: Array<string>, 
+/**
+ * 请求所属的代数，与当前代数不一致时请求被视为已取消
+ */
+epoch?: number, 
+/**
+ * 覆盖索引中的页面类型分数系数
+ */
+page_type_boosts?: { [key in string]?: number }, 
+/**
+ * 结果详细程度
+ */
+result_detail?: ResultDetail, 
+/**
+ * 边输入边搜索的会话ID，同一输入框的连续查询使用相同的ID以复用上一次的候选文章
+ */
+session_id?: string, 
+/**
+ * 请求方使用的分词器标识，指定时必须与索引构建时使用的分词器一致
+ */
+tokenizer?: string, 
+/**
+ * 按匹配的顶级章节对结果分组，结果中返回 `clusters`
+ */
+cluster_results?: boolean, 
+/**
+ * 标签筛选 - 只返回带有全部指定标签的文章，与查询中的 `tag:标签` 合并
+ */
+tags?: Array<string>, 
+/**
+ * 分类筛选 - 只返回属于该分类（文章ID中的目录，包括下级目录）的文章，
+ * 与查询中的 `category:分类` 合并
+ */
+category?: string, };
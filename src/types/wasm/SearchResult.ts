@@ -0,0 +1,62 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Diagnostics } from "./Diagnostics";
+import type { NoResultsHints } from "./NoResultsHints";
+import type { QueryDebug } from "./QueryDebug";
+import type { ResultCluster } from "./ResultCluster";
+import type { SearchResultItem } from "./SearchResultItem";
+import type { SearchSuggestion } from "./SearchSuggestion";
+
+/**
+ * 搜索结果
+ *
+ * 二进制协议（`search_articles_bin`）按字段定义顺序编码。
+ */
+export type SearchResult = { 
+/**
+ * 搜索结果条目
+ */
+items: Array<SearchResultItem>, 
+/**
+ * 结果总数
+ */
+total: number, 
+/**
+ * 当前页码
+ */
+page: number, 
+/**
+ * 每页条数
+ */
+page_size: number, 
+/**
+ * 总页数
+ */
+total_pages: number, 
+/**
+ * 搜索查询
+ */
+query: string, 
+/**
+ * 搜索建议
+ */
+suggestions: Array<SearchSuggestion>, 
+/**
+ * 搜索是否被取消
+ */
+cancelled: boolean, 
+/**
+ * 查询的处理过程，供调试和界面显示实际生效的查询与筛选条件
+ */
+query_debug: QueryDebug, 
+/**
+ * 无结果提示 - 查询没有结果时提供，有结果时为空
+ */
+hints: NoResultsHints | null, 
+/**
+ * 诊断信息（耗时等每次搜索都会变化的数据），关闭计时后为空，结果中的其他字段保持确定
+ */
+diagnostics: Diagnostics | null, 
+/**
+ * 按顶级章节分组的结果 - 请求 `cluster_results` 时提供
+ */
+clusters: Array<ResultCluster> | null, };
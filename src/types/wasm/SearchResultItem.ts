@@ -0,0 +1,58 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HeadingNode } from "./HeadingNode";
+import type { MatchSpan } from "./MatchSpan";
+
+/**
+ * 搜索结果条目
+ */
+export type SearchResultItem = { 
+/**
+ * 文章ID
+ */
+id: string, 
+/**
+ * 文章标题
+ */
+title: string, 
+/**
+ * 文章摘要（查询词已高亮）
+ */
+summary: string, 
+/**
+ * 摘要中的匹配区间
+ */
+summary_matches: Array<MatchSpan>, 
+/**
+ * 文章URL
+ */
+url: string, 
+/**
+ * 原始匹配分数 - 各匹配规则的基础分数乘以各项系数，不同查询之间不可比较；
+ * 界面应改用 `relevance`，该字段在过渡期后移除
+ */
+score: number, 
+/**
+ * 结构化的标题和内容层级
+ */
+heading_tree: HeadingNode | null, 
+/**
+ * 页面类型
+ */
+page_type: string, 
+/**
+ * 文章语言，用于界面显示语言标记
+ */
+lang: string, 
+/**
+ * 结果所属的索引名称
+ */
+source: string, 
+/**
+ * 文章中匹配的关键词，合并标题树各节点的匹配并去重，按匹配的章节数降序；
+ * 精简模式下为空
+ */
+matched_terms: Array<string>, 
+/**
+ * 相关度 - 原始分数换算到0到100的值，分数越高越接近100
+ */
+relevance: number, };
@@ -0,0 +1,28 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SuggestionType } from "./SuggestionType";
+import type { TermField } from "./TermField";
+
+/**
+ * 搜索建议结构（对外输出）
+ */
+export type SearchSuggestion = { 
+/**
+ * 建议文本
+ */
+text: string, 
+/**
+ * 建议类型
+ */
+suggestion_type: SuggestionType, 
+/**
+ * 用户已输入匹配部分
+ */
+matched_text: string, 
+/**
+ * 建议补全部分
+ */
+suggestion_text: string, 
+/**
+ * 建议的来源字段
+ */
+field: TermField, };
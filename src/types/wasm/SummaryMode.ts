@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 搜索结果摘要的来源
+ */
+export type SummaryMode = "stored" | "snippet";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 词条的来源字段，按标题、章节标题、正文的顺序优先
+ */
+export type TermField = "title" | "heading" | "content";
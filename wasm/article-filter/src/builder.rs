@@ -1,14 +1,21 @@
-use utils_common::models::ArticleMetadata;
-use utils_common::compression::to_compressed;
-use crate::models::FilterIndex;
-use chrono::Datelike;
+use utils_common::arena::ArticleTable;
+use utils_common::models::{ArticleMetadata, IndexMetadata, IndexType};
+use utils_common::compression::{to_compressed_with_format, SerializationFormat};
+use utils_common::text::count_words;
+use crate::models::{FilterIndex, FilterPreset, IndexedArticle};
+use crate::INDEX_VERSION;
+use chrono::{Datelike, Utc};
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::Write;
 
 /// 筛选索引构建器
+#[derive(Default)]
 pub struct FilterBuilder {
     articles: Vec<ArticleMetadata>,
+    presets: Vec<FilterPreset>,
+    /// 保存索引文件使用的序列化格式
+    format: SerializationFormat,
 }
 
 impl FilterBuilder {
@@ -16,14 +23,31 @@ impl FilterBuilder {
     pub fn new() -> Self {
         Self {
             articles: Vec::new(),
+            presets: Vec::new(),
+            format: SerializationFormat::default(),
         }
     }
 
-    /// 添加文章到索引构建器
-    pub fn add_article(&mut self, article: ArticleMetadata) {
+    /// 添加文章到索引构建器，没有词数的文章根据内容计算词数
+    pub fn add_article(&mut self, mut article: ArticleMetadata) {
+        if article.word_count == 0 {
+            article.word_count = count_words(&article.content);
+        }
         self.articles.push(article);
     }
 
+    /// 添加筛选预设到索引构建器
+    pub fn add_preset(&mut self, preset: FilterPreset) {
+        // 同名预设以后添加的为准
+        self.presets.retain(|p| p.name != preset.name);
+        self.presets.push(preset);
+    }
+
+    /// 设置保存索引文件使用的序列化格式，默认为bincode
+    pub fn set_format(&mut self, format: SerializationFormat) {
+        self.format = format;
+    }
+
     /// 构建筛选索引
     pub fn build_filter_index(&self) -> Result<FilterIndex, String> {
         if self.articles.is_empty() {
@@ -37,12 +61,36 @@ impl FilterBuilder {
         let mut tag_index: HashMap<String, HashSet<usize>> = HashMap::new();
         let mut year_index: HashMap<i32, HashSet<usize>> = HashMap::new();
         let mut month_index: HashMap<String, HashSet<usize>> = HashMap::new();
+        let mut series_index: HashMap<String, HashSet<usize>> = HashMap::new();
+        let mut author_index: HashMap<String, HashSet<usize>> = HashMap::new();
+        let mut articles: Vec<IndexedArticle> = Vec::with_capacity(self.articles.len());
 
         // 填充索引
         for (i, article) in self.articles.iter().enumerate() {
+            // 排序和筛选键，文章表中的位置与文章位置相同
+            articles.push(IndexedArticle {
+                id: article.id.clone(),
+                title: article.title.clone(),
+                date: article.date,
+                visibility: article.visibility,
+                featured: article.featured,
+                word_count: article.word_count,
+                record: i as u32,
+            });
+
             // 标签索引
             for tag in &article.tags {
-                tag_index.entry(tag.clone()).or_insert_with(HashSet::new).insert(i);
+                tag_index.entry(tag.clone()).or_default().insert(i);
+            }
+
+            // 系列索引
+            if let Some(series) = &article.series {
+                series_index.entry(series.clone()).or_default().insert(i);
+            }
+
+            // 作者索引
+            if let Some(author) = &article.author {
+                author_index.entry(author.clone()).or_default().insert(i);
             }
 
             // 日期索引
@@ -50,21 +98,35 @@ impl FilterBuilder {
             let year = date.year();
             
             // 按年索引
-            year_index.entry(year).or_insert_with(HashSet::new).insert(i);
+            year_index.entry(year).or_default().insert(i);
             
             // 按年月索引 (格式：yyyy-mm)
             let month_key = format!("{}-{:02}", year, date.month());
-            month_index.entry(month_key).or_insert_with(HashSet::new).insert(i);
+            month_index.entry(month_key).or_default().insert(i);
         }
 
-        println!("索引构建完成，标签数量: {}, 年份数量: {}, 月份数量: {}", 
-                 tag_index.len(), year_index.len(), month_index.len());
+        println!("索引构建完成，标签数量: {}, 年份数量: {}, 月份数量: {}, 系列数量: {}, 作者数量: {}", 
+                 tag_index.len(), year_index.len(), month_index.len(), series_index.len(), author_index.len());
+
+        // 文章表只保存展示字段，正文、标题结构和媒体说明不写入筛选索引
+        let displayed: Vec<ArticleMetadata> = self.articles.iter()
+            .map(|article| ArticleMetadata {
+                content: String::new(),
+                headings: Vec::new(),
+                media_text: String::new(),
+                ..article.clone()
+            })
+            .collect();
 
         Ok(FilterIndex {
-            articles: self.articles.clone(),
+            articles,
+            records: ArticleTable::from_articles(&displayed),
             tag_index,
             year_index,
             month_index,
+            series_index,
+            author_index,
+            presets: self.presets.clone(),
         })
     }
 
@@ -95,10 +157,18 @@ impl FilterBuilder {
             }
         };
         
-        // 使用版本号3.0
-        let version = [3, 0];
+        // 头部写入索引元数据
+        let version = INDEX_VERSION;
+        let metadata = IndexMetadata {
+            article_count: filter_index.articles.len(),
+            tag_count: filter_index.tag_index.len(),
+            created_at: Utc::now(),
+            version: format!("{}.{}", version[0], version[1]),
+            index_type: IndexType::Filter,
+            token_count: 0,
+        };
         
-        let compressed_data = match to_compressed(&filter_index, version) {
+        let compressed_data = match to_compressed_with_format(&filter_index, version, Some(&metadata), self.format) {
             Ok(data) => {
                 println!("数据压缩成功，压缩后大小: {} 字节", data.len());
                 data
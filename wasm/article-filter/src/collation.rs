@@ -0,0 +1,119 @@
+// 标题排序规则 - 按标题排序时的字符串比较方式
+// WASM环境下使用宿主提供的 `Intl.Collator`（浏览器和Node.js都内置了ICU排序规则，中文按拼音排序），
+// 不需要把排序表编译进WASM；原生环境（测试和构建工具）下回退到忽略大小写和拉丁字母重音的比较，
+// 汉字按码位排序。
+// 排序规则认为相同的标题再按字节比较，保证比较结果是全序的，与游标的比较一致。
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use crate::Locale;
+
+/// 排序规则
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Collation {
+    /// 按字节（Unicode码位）比较
+    #[default]
+    Binary,
+    /// Unicode排序规则，忽略大小写和重音的差异优先
+    Unicode,
+    /// 中文拼音排序规则
+    Pinyin,
+}
+
+impl Collation {
+    /// 解析排序规则参数，`locale` 按界面语言选择（中文按拼音，其他按Unicode排序规则），
+    /// 未知的排序规则返回None
+    pub(crate) fn parse(value: &str, locale: Locale) -> Option<Self> {
+        match value {
+            "locale" => Some(Self::for_locale(locale)),
+            "binary" => Some(Collation::Binary),
+            "unicode" => Some(Collation::Unicode),
+            "pinyin" => Some(Collation::Pinyin),
+            _ => None,
+        }
+    }
+
+    /// 界面语言对应的排序规则
+    pub(crate) fn for_locale(locale: Locale) -> Self {
+        match locale {
+            Locale::Zh => Collation::Pinyin,
+            Locale::En => Collation::Unicode,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Collation::Binary => "binary",
+            Collation::Unicode => "unicode",
+            Collation::Pinyin => "pinyin",
+        }
+    }
+}
+
+/// 按排序规则比较字符串
+pub(crate) struct Collator {
+    collation: Collation,
+    #[cfg(target_arch = "wasm32")]
+    compare: Option<js_sys::Function>,
+}
+
+impl Collator {
+    pub(crate) fn new(collation: Collation) -> Self {
+        Collator {
+            collation,
+            #[cfg(target_arch = "wasm32")]
+            compare: intl_compare(collation),
+        }
+    }
+
+    pub(crate) fn compare(&self, a: &str, b: &str) -> Ordering {
+        let ordering = match self.collation {
+            Collation::Binary => Ordering::Equal,
+            #[cfg(target_arch = "wasm32")]
+            _ => match &self.compare {
+                Some(compare) => compare
+                    .call2(&wasm_bindgen::JsValue::NULL, &a.into(), &b.into())
+                    .ok()
+                    .and_then(|result| result.as_f64())
+                    .map_or(Ordering::Equal, |result| result.partial_cmp(&0.0).unwrap_or(Ordering::Equal)),
+                None => folded(a).cmp(folded(b)),
+            },
+            #[cfg(not(target_arch = "wasm32"))]
+            _ => folded(a).cmp(folded(b)),
+        };
+        ordering.then_with(|| a.cmp(b))
+    }
+}
+
+/// 创建 `Intl.Collator` 并取出绑定的比较函数，宿主不支持 `Intl` 时返回None
+#[cfg(target_arch = "wasm32")]
+fn intl_compare(collation: Collation) -> Option<js_sys::Function> {
+    use wasm_bindgen::JsValue;
+
+    let locale = match collation {
+        Collation::Binary => return None,
+        Collation::Unicode => "und",
+        Collation::Pinyin => "zh-u-co-pinyin",
+    };
+    let intl = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("Intl")).ok()?;
+    if intl.is_undefined() {
+        return None;
+    }
+    let locales = js_sys::Array::of1(&JsValue::from_str(locale));
+    Some(js_sys::Intl::Collator::new(&locales, &js_sys::Object::new()).compare())
+}
+
+/// 拉丁字母补充和扩展A区（U+00C0 - U+017F）字符去掉重音后的小写字母，0表示不转换（乘号和除号）
+const LATIN_FOLDED: &[u8; 192] = b"aaaaaaaceeeeiiiidnooooo\0ouuuuytsaaaaaaaceeeeiiiidnooooo\0ouuuuytyaaaaaaccccccccddddeeeeeeeeeegggggggghhhhiiiiiiiiiiiijjkkkllllllllllnnnnnnnnnoooooooorrrrrrssssssssttttttuuuuuuuuuuuuwwyyyzzzzzzs";
+
+/// 忽略大小写和拉丁字母重音后的字符序列
+fn folded(value: &str) -> impl Iterator<Item = char> + '_ {
+    value.chars().flat_map(|c| {
+        let base = match c as u32 {
+            code @ 0xC0..=0x17F => Some(LATIN_FOLDED[(code - 0xC0) as usize]).filter(|&base| base != 0),
+            _ => None,
+        };
+        base.map_or(c, char::from).to_lowercase()
+    })
+}
@@ -0,0 +1,159 @@
+// 游标分页 - 游标记录上一页最后一篇文章的排序键和ID，下一页从排在它之后的文章开始。
+// 与页码分页不同，两次请求之间发布或删除文章不会导致结果重复或遗漏，适合无限滚动。
+// 游标对客户端是不透明的字符串（JSON的十六进制编码），只能原样传回。
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use crate::collation::{Collation, Collator};
+use crate::models::IndexedArticle;
+use crate::{FilterError, ListOrder, SortOrder};
+
+/// 分页游标 - 上一页最后一篇文章的排序键
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct Cursor {
+    /// 生成游标时的排序方式
+    sort: SortOrder,
+    /// 生成游标时的次要排序方式
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    secondary: Option<SortOrder>,
+    /// 生成游标时标题的排序规则，不按标题排序时为按字节比较
+    #[serde(default, skip_serializing_if = "is_binary")]
+    collation: Collation,
+    /// 生成游标时置顶文章是否排在前面
+    #[serde(default)]
+    featured_first: bool,
+    /// 文章是否为置顶文章，置顶文章排在前面时使用
+    #[serde(default)]
+    featured: bool,
+    /// 发布日期，按发布时间排序（包括次要排序）时使用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<DateTime<Utc>>,
+    /// 标题，按标题排序（包括次要排序）时使用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    /// 词数，按阅读时间排序（包括次要排序）时使用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    word_count: Option<usize>,
+    /// 文章ID，排序键相同时按ID排序
+    id: String,
+}
+
+impl Cursor {
+    /// 指向文章之后的游标
+    pub(crate) fn after(article: &IndexedArticle, order: ListOrder) -> Self {
+        let ListOrder { sort, secondary, featured_first, collation } = order;
+        let uses = |key| SortKey::of(sort) == key || secondary.map(SortKey::of) == Some(key);
+        Cursor {
+            sort,
+            secondary,
+            collation,
+            featured_first,
+            featured: featured_first && article.featured,
+            date: uses(SortKey::Date).then_some(article.date),
+            title: uses(SortKey::Title).then(|| article.title.clone()),
+            word_count: uses(SortKey::WordCount).then_some(article.word_count),
+            id: article.id.clone(),
+        }
+    }
+
+    /// 编码为不透明的字符串
+    pub(crate) fn encode(&self) -> String {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        json.bytes().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// 解析 `encode` 生成的字符串，`order` 为当前请求的顺序，必须与生成游标时相同
+    pub(crate) fn decode(value: &str, order: ListOrder) -> Result<Self, FilterError> {
+        let invalid = || FilterError::InvalidParams(format!("无效的游标: {}", value));
+        if !value.len().is_multiple_of(2) || !value.is_ascii() {
+            return Err(invalid());
+        }
+        let bytes = (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+        let cursor: Cursor = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+        let has_key = |sort| match SortKey::of(sort) {
+            SortKey::Date => cursor.date.is_some(),
+            SortKey::Title => cursor.title.is_some(),
+            SortKey::WordCount => cursor.word_count.is_some(),
+        };
+        if !has_key(cursor.sort) || !cursor.secondary.is_none_or(has_key) {
+            return Err(invalid());
+        }
+        if cursor.sort != order.sort {
+            return Err(FilterError::InvalidParams(format!(
+                "游标按 {} 排序生成，与当前的排序方式 {} 不一致",
+                cursor.sort.as_str(),
+                order.sort.as_str()
+            )));
+        }
+        if cursor.secondary != order.secondary {
+            return Err(FilterError::InvalidParams("游标生成时的次要排序方式与当前请求不一致".to_string()));
+        }
+        if cursor.collation != order.collation {
+            return Err(FilterError::InvalidParams(format!(
+                "游标按 {} 排序规则生成，与当前的排序规则 {} 不一致",
+                cursor.collation.as_str(),
+                order.collation.as_str()
+            )));
+        }
+        if cursor.featured_first != order.featured_first {
+            return Err(FilterError::InvalidParams("游标生成时置顶文章的排列方式与当前请求不一致".to_string()));
+        }
+        Ok(cursor)
+    }
+
+    /// 文章排在游标之后时返回true，`collator` 为游标的排序规则
+    pub(crate) fn precedes(&self, article: &IndexedArticle, collator: &Collator) -> bool {
+        // 置顶文章在前时，非置顶文章排在置顶的游标之后
+        let featured = if self.featured_first {
+            self.featured.cmp(&article.featured)
+        } else {
+            Ordering::Equal
+        };
+        featured
+            .then_with(|| self.compare_key(self.sort, article, collator))
+            .then_with(|| self.secondary.map_or(Ordering::Equal, |sort| self.compare_key(sort, article, collator)))
+            .then_with(|| article.id.cmp(&self.id))
+            == Ordering::Greater
+    }
+
+    /// 按排序方式比较文章与游标的排序键
+    fn compare_key(&self, sort: SortOrder, article: &IndexedArticle, collator: &Collator) -> Ordering {
+        let ordering = match sort {
+            SortOrder::Newest => self.date.as_ref().map(|date| article.date.cmp(date).reverse()),
+            SortOrder::Oldest => self.date.as_ref().map(|date| article.date.cmp(date)),
+            SortOrder::TitleAsc => self.title.as_deref().map(|title| collator.compare(&article.title, title)),
+            SortOrder::TitleDesc => self.title.as_deref().map(|title| collator.compare(&article.title, title).reverse()),
+            SortOrder::ReadingTimeAsc => self.word_count.map(|count| article.word_count.cmp(&count)),
+            SortOrder::ReadingTimeDesc => self.word_count.map(|count| article.word_count.cmp(&count).reverse()),
+        };
+        ordering.unwrap_or(Ordering::Equal)
+    }
+}
+
+/// 排序方式使用的排序键
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    Date,
+    Title,
+    WordCount,
+}
+
+impl SortKey {
+    fn of(sort: SortOrder) -> Self {
+        match sort {
+            SortOrder::Newest | SortOrder::Oldest => SortKey::Date,
+            SortOrder::TitleAsc | SortOrder::TitleDesc => SortKey::Title,
+            SortOrder::ReadingTimeAsc | SortOrder::ReadingTimeDesc => SortKey::WordCount,
+        }
+    }
+}
+
+fn is_binary(collation: &Collation) -> bool {
+    *collation == Collation::Binary
+}
@@ -0,0 +1,102 @@
+// 筛选错误 - 带有稳定错误码的错误类型。错误信息是给人看的中文文本，可能随版本调整，
+// JS侧应按错误码区分错误（如索引尚未加载时等待加载完成，参数无效时重置筛选条件）。
+// 导出到JS时转换为 `Error` 对象，`message` 为错误信息，`code` 属性为错误码。
+
+use serde::Serialize;
+use std::fmt;
+use ts_rs::TS;
+use wasm_bindgen::JsValue;
+
+/// 错误码 - 序列化为 snake_case 字符串，各版本之间保持不变
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterErrorCode {
+    /// 尚未加载任何索引
+    NotInitialized,
+    /// 指定名称的索引未加载
+    IndexNotLoaded,
+    /// 索引数据或JSON参数无法解析
+    ParseError,
+    /// 全局状态的锁失效（持有锁的线程发生panic）
+    LockPoisoned,
+    /// 参数无效
+    InvalidParams,
+    /// 文章或筛选预设不存在
+    NotFound,
+    /// 结果无法序列化
+    SerializeError,
+}
+
+impl FilterErrorCode {
+    /// 错误码字符串，与序列化结果相同
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FilterErrorCode::NotInitialized => "not_initialized",
+            FilterErrorCode::IndexNotLoaded => "index_not_loaded",
+            FilterErrorCode::ParseError => "parse_error",
+            FilterErrorCode::LockPoisoned => "lock_poisoned",
+            FilterErrorCode::InvalidParams => "invalid_params",
+            FilterErrorCode::NotFound => "not_found",
+            FilterErrorCode::SerializeError => "serialize_error",
+        }
+    }
+}
+
+/// 筛选错误
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterError {
+    /// 尚未加载任何索引
+    NotInitialized,
+    /// 指定名称的索引未加载
+    IndexNotLoaded(String),
+    /// 索引数据或JSON参数无法解析
+    ParseError(String),
+    /// 全局状态的锁失效
+    LockPoisoned(&'static str),
+    /// 参数无效
+    InvalidParams(String),
+    /// 文章或筛选预设不存在
+    NotFound(String),
+    /// 结果无法序列化
+    SerializeError(String),
+}
+
+impl FilterError {
+    /// 错误码
+    pub fn code(&self) -> FilterErrorCode {
+        match self {
+            FilterError::NotInitialized => FilterErrorCode::NotInitialized,
+            FilterError::IndexNotLoaded(_) => FilterErrorCode::IndexNotLoaded,
+            FilterError::ParseError(_) => FilterErrorCode::ParseError,
+            FilterError::LockPoisoned(_) => FilterErrorCode::LockPoisoned,
+            FilterError::InvalidParams(_) => FilterErrorCode::InvalidParams,
+            FilterError::NotFound(_) => FilterErrorCode::NotFound,
+            FilterError::SerializeError(_) => FilterErrorCode::SerializeError,
+        }
+    }
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterError::NotInitialized => f.write_str("索引未初始化"),
+            FilterError::IndexNotLoaded(name) => write!(f, "索引未加载: {}", name),
+            FilterError::LockPoisoned(message) => f.write_str(message),
+            FilterError::ParseError(message)
+            | FilterError::InvalidParams(message)
+            | FilterError::NotFound(message)
+            | FilterError::SerializeError(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+impl From<FilterError> for JsValue {
+    fn from(error: FilterError) -> Self {
+        let js_error = js_sys::Error::new(&error.to_string());
+        js_error.set_name("FilterError");
+        let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("code"), &JsValue::from_str(error.code().as_str()));
+        js_error.into()
+    }
+}
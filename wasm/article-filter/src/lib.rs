@@ -1,20 +1,57 @@
 use wasm_bindgen::prelude::*;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use ts_rs::TS;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io;
 use once_cell::sync::OnceCell;
-use std::sync::Mutex;
-use serde_json;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
 use web_sys::console;
+use utils_common::arena::{ArticleRef, ArticleTable};
 use utils_common::compression as utils;
+use utils_common::models::{IndexMetadata, Visibility};
+use utils_common::text::reading_minutes;
+use crate::models::{FilterPreset, IndexedArticle};
 
 // 导出模块
 pub mod models;
 pub mod builder;
+mod collation;
+mod cursor;
+mod error;
 
-// 全局索引存储
-static INDEX: OnceCell<Mutex<Option<ArticleIndex>>> = OnceCell::new();
+use crate::collation::{Collation, Collator};
+use crate::cursor::Cursor;
+pub use crate::error::{FilterError, FilterErrorCode};
+
+/// 构建器写入的筛选索引版本，索引结构变化时递增主版本号
+pub const INDEX_VERSION: [u8; 2] = [10, 0];
+
+/// 支持的最大筛选索引版本
+pub const MAX_INDEX_VERSION: u8 = INDEX_VERSION[0];
+
+/// 支持的最小筛选索引版本 - 更早版本的索引结构不同，加载时返回要求重新生成索引的错误
+pub const MIN_INDEX_VERSION: u8 = 10;
+
+/// 每页条数上限 - 超过时返回参数无效错误，避免一次请求解码整个索引
+pub const MAX_PAGE_LIMIT: usize = 100;
+
+/// 默认索引名称 - `init`、`reload` 和不带索引名称的查询使用的索引
+pub const DEFAULT_INDEX: &str = "default";
+
+// 全局索引存储 - 按名称保存多个索引（如博客、笔记、项目等栏目各自的索引），
+// 读写锁只在取出或替换索引时持有，多个组件（归档、侧边栏、页脚等）同时查询时互不阻塞
+static INDEX: OnceCell<RwLock<BTreeMap<String, Arc<ArticleIndex>>>> = OnceCell::new();
+
+// 全局界面语言，影响归档标签等需要本地化的文本
+static LOCALE: OnceCell<RwLock<Locale>> = OnceCell::new();
+
+/// 相关文章的发布时间接近度尺度（天）- 相差该天数时接近度为0.5
+const RELATED_DATE_SCALE_DAYS: f64 = 30.0;
+
+// 随机文章的调用计数 - 未指定种子时与当前时间一起作为种子，同一毫秒内的多次调用也得到不同的结果
+static RANDOM_CALLS: AtomicU64 = AtomicU64::new(0);
 
 /// 初始化函数 - 设置错误处理
 #[wasm_bindgen(start)]
@@ -31,7 +68,7 @@ pub fn version() -> String {
 //===== Models 部分 =====
 
 /// 简化的文章元数据 - 只包含展示所需信息
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, TS)]
 pub struct ArticleMetadata {
     /// 文章唯一标识符
     pub id: String,
@@ -45,37 +82,204 @@ pub struct ArticleMetadata {
     pub tags: Vec<String>,
     /// 文章URL路径
     pub url: String,
+    /// 是否为置顶文章
+    #[serde(default)]
+    pub featured: bool,
+    /// 所属系列名称，不属于任何系列时为空
+    #[serde(default)]
+    pub series: Option<String>,
+    /// 文章作者，未注明作者时为空
+    #[serde(default)]
+    pub author: Option<String>,
+    /// 正文词数
+    #[serde(default)]
+    pub word_count: usize,
+    /// 估算的阅读时间（分钟）
+    #[serde(default)]
+    pub reading_minutes: u32,
+}
+
+/// 文章字段 - 用于筛选参数的 `fields` 字段选择
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ArticleField {
+    Id,
+    Title,
+    Summary,
+    Date,
+    Tags,
+    Url,
+    Featured,
+    Series,
+    Author,
+    WordCount,
+    ReadingMinutes,
+}
+
+/// 筛选结果中的文章 - 只包含筛选参数 `fields` 选择的字段，未选择的字段不出现在结果中
+///
+/// 文章ID总是包含在内；未指定 `fields` 时包含全部字段，与 `ArticleMetadata` 相同。
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(optional_fields)]
+pub struct ListedArticle {
+    /// 文章唯一标识符
+    pub id: String,
+    /// 文章标题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 文章摘要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// 发布日期
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<DateTime<Utc>>,
+    /// 文章标签列表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// 文章URL路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// 是否为置顶文章
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub featured: Option<bool>,
+    /// 所属系列名称，文章不属于任何系列时不出现在结果中
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<String>,
+    /// 文章作者，未注明作者时不出现在结果中
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// 正文词数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_count: Option<usize>,
+    /// 估算的阅读时间（分钟）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading_minutes: Option<u32>,
+}
+
+impl ListedArticle {
+    /// 文章表中的字段 - 选择其中任一字段时才需要读取文章表
+    const RECORD_FIELDS: [ArticleField; 5] = [
+        ArticleField::Summary,
+        ArticleField::Tags,
+        ArticleField::Url,
+        ArticleField::Series,
+        ArticleField::Author,
+    ];
+
+    /// 只保留选择的字段，`fields` 为None时保留全部字段；文章不在文章表中时返回None
+    fn project(index: &ArticleIndex, id: usize, fields: Option<&[ArticleField]>) -> Option<Self> {
+        let selected = |field: ArticleField| fields.is_none_or(|fields| fields.contains(&field));
+        let article = index.articles.get(id)?;
+        let record = if Self::RECORD_FIELDS.iter().any(|&field| selected(field)) {
+            Some(index.record(id)?)
+        } else {
+            None
+        };
+        let record_field = |field: ArticleField| record.filter(|_| selected(field));
+        Some(ListedArticle {
+            id: article.id.clone(),
+            title: selected(ArticleField::Title).then(|| article.title.clone()),
+            summary: record_field(ArticleField::Summary).map(|record| record.summary().to_string()),
+            date: selected(ArticleField::Date).then_some(article.date),
+            tags: record_field(ArticleField::Tags).map(|record| record.tags().map(str::to_string).collect()),
+            url: record_field(ArticleField::Url).map(|record| record.url().to_string()),
+            featured: selected(ArticleField::Featured).then_some(article.featured),
+            series: record_field(ArticleField::Series).and_then(|record| record.series()).map(str::to_string),
+            author: record_field(ArticleField::Author).and_then(|record| record.author()).map(str::to_string),
+            word_count: selected(ArticleField::WordCount).then_some(article.word_count),
+            reading_minutes: selected(ArticleField::ReadingMinutes).then(|| reading_minutes(article.word_count)),
+        })
+    }
 }
 
 /// 文章索引 - 存储所有文章和索引数据
+///
+/// 文章的排序和筛选键在加载时全部解码；摘要、标签等展示字段保留在紧凑文章表中，
+/// 通过 `record` 或 `article` 按需读取，每次查询只为当前页的文章分配字符串。
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ArticleIndex {
-    /// 所有文章的元数据列表
-    pub articles: Vec<ArticleMetadata>,
+    /// 所有文章的排序和筛选键
+    pub articles: Vec<IndexedArticle>,
+    /// 文章的展示字段
+    records: ArticleTable,
     /// 标签索引: 标签名 -> 文章ID列表
     pub tag_index: HashMap<String, Vec<usize>>,
+    /// 年份索引: 年份 -> 文章ID列表
+    pub year_index: HashMap<i32, Vec<usize>>,
+    /// 月份索引: 月份键（yyyy-mm）-> 文章ID列表
+    pub month_index: HashMap<String, Vec<usize>>,
+    /// 系列索引: 系列名称 -> 文章ID列表
+    pub series_index: HashMap<String, Vec<usize>>,
+    /// 作者索引: 作者 -> 文章ID列表
+    pub author_index: HashMap<String, Vec<usize>>,
+    /// 筛选预设列表
+    pub presets: Vec<FilterPreset>,
+    /// 索引文件头部的索引元数据，合并加载多个索引时为构建时间最早的索引的元数据
+    #[serde(skip)]
+    metadata: Option<IndexMetadata>,
+    /// 按Unicode排序规则排列的标题位置，首次按标题排序时计算
+    #[serde(skip)]
+    unicode_title_ranks: OnceCell<Vec<u32>>,
+    /// 按拼音排序规则排列的标题位置，首次按标题排序时计算
+    #[serde(skip)]
+    pinyin_title_ranks: OnceCell<Vec<u32>>,
 }
 
 /// 筛选参数 - 客户端传递的筛选条件
-#[derive(Deserialize, Debug, Default)]
+#[derive(Deserialize, Debug, Default, TS)]
+#[ts(optional_fields)]
 pub struct FilterParams {
     /// 标签筛选条件 (可选)
     pub tags: Option<Vec<String>>,
-    /// 排序方式: "newest", "oldest", "title_asc", "title_desc" (可选)
+    /// 标签匹配方式: "any"（带有任一标签，默认）或 "all"（带有全部标签）
+    pub tag_mode: Option<String>,
+    /// 排除的标签 - 带有其中任一标签的文章不出现在结果中 (可选)
+    pub exclude_tags: Option<Vec<String>>,
+    /// 排序方式: "newest", "oldest", "title_asc", "title_desc", "reading_time_asc", "reading_time_desc" (可选)
     pub sort: Option<String>,
+    /// 次要排序方式 (可选)，取值与 `sort` 相同，按 `sort` 排序相同的文章再按该方式排序；
+    /// 仍然相同时按文章ID排序，保证每次请求的顺序一致
+    pub sort_secondary: Option<String>,
+    /// 按标题排序时的排序规则 (可选): "locale"（默认，按界面语言选择，中文按拼音，英文按Unicode排序规则）、
+    /// "pinyin"、"unicode" 或 "binary"（按字节比较）
+    pub collation: Option<String>,
     /// 分页 - 当前页码 (可选, 默认为1)
     pub page: Option<usize>,
-    /// 分页 - 每页条数 (可选, 默认为12)
+    /// 分页 - 每页条数 (可选, 默认为12，不能超过 `MAX_PAGE_LIMIT`)
     pub limit: Option<usize>,
     /// 日期筛选: "all" 或 "startDate,endDate" 格式的日期范围
     pub date: Option<String>,
+    /// 年份筛选，与归档中的年份对应
+    pub year: Option<i32>,
+    /// 月份筛选: YYYY-MM 格式的月份键，与归档中月份的 `key` 对应
+    pub month: Option<String>,
+    /// 关键词筛选 - 标题、摘要或标签中包含该文本（不区分大小写）的文章 (可选)
+    pub q: Option<String>,
+    /// 按发布时间分组: "year" 或 "month" (可选)，指定时当前页的文章按年或月放入 `groups`
+    pub group_by: Option<String>,
+    /// 结果中包含的文章字段 (可选，默认为全部字段)，如 `["id", "title", "url", "date"]`，
+    /// 文章ID总是包含在内。列表页不需要摘要和标签时可以减少序列化和传输的数据量
+    pub fields: Option<Vec<ArticleField>>,
+    /// 分页游标 (可选)，为上一次结果中的 `next_cursor`，指定时从游标之后的文章开始并忽略页码。
+    /// 排序方式和次要排序方式必须与生成游标时相同
+    pub cursor: Option<String>,
+    /// 只返回置顶文章 (可选，默认为false)
+    pub featured_only: Option<bool>,
+    /// 置顶文章排在其他文章前面 (可选)，默认为true；指定 `group_by` 或 `series` 时默认为false，保持时间线的顺序
+    pub featured_first: Option<bool>,
+    /// 系列筛选 - 只返回该系列中的文章 (可选)，未指定 `sort` 时按发布时间从早到晚排列，即系列的阅读顺序
+    pub series: Option<String>,
+    /// 作者筛选 - 只返回其中任一作者的文章 (可选)
+    pub authors: Option<Vec<String>>,
+    /// 阅读时间上限 - 只返回估算阅读时间不超过该分钟数的文章 (可选)
+    pub max_reading_minutes: Option<u32>,
 }
 
 /// 筛选结果 - 返回给客户端的筛选结果
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, TS)]
 pub struct FilterResult {
-    /// 筛选后的文章列表
-    pub articles: Vec<ArticleMetadata>,
+    /// 筛选后的文章列表，按分组返回时为空
+    pub articles: Vec<ListedArticle>,
     /// 筛选结果总数
     pub total: usize,
     /// 当前页码
@@ -84,12 +288,505 @@ pub struct FilterResult {
     pub limit: usize,
     /// 总页数
     pub total_pages: usize,
+    /// 参数警告 - 被忽略或替换为默认值的筛选参数，参数都有效时为空
+    pub warnings: Vec<String>,
+    /// 按年或月分组的当前页文章，按排序后首次出现的顺序排列，未指定 `group_by` 时为空
+    pub groups: Option<Vec<ArticleGroup>>,
+    /// 下一页的游标，作为下一次请求的 `cursor` 传入；没有更多文章时为空
+    pub next_cursor: Option<String>,
+}
+
+/// 时间线分组 - 同一年或同一月发布的文章
+#[derive(Serialize, Debug, TS)]
+pub struct ArticleGroup {
+    /// ISO格式的分组键，按年分组时为 YYYY，按月分组时为 YYYY-MM（UTC），与归档中的键相同
+    pub key: String,
+    /// 按当前界面语言格式化的标签（如 "2024年3月"、"March 2024"）
+    pub label: String,
+    /// 该分组中符合筛选条件的文章总数（包括其他页的文章）
+    pub count: usize,
+    /// 当前页中属于该分组的文章，保持排序后的顺序
+    pub articles: Vec<ListedArticle>,
+}
+
+/// 时间线分组方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GroupBy {
+    Year,
+    Month,
+}
+
+impl GroupBy {
+    fn parse(value: &str) -> Result<Self, FilterError> {
+        match value {
+            "year" => Ok(GroupBy::Year),
+            "month" => Ok(GroupBy::Month),
+            _ => Err(FilterError::InvalidParams(format!("无效的分组方式: {}，应为 year 或 month", value))),
+        }
+    }
+
+    /// 文章所属分组的年份和月份，按年分组时月份为0
+    fn bucket(self, date: &DateTime<Utc>) -> (i32, u32) {
+        match self {
+            GroupBy::Year => (date.year(), 0),
+            GroupBy::Month => (date.year(), date.month()),
+        }
+    }
+}
+
+/// 标签关系图 - 标签及其共同出现次数，用于在归档页绘制标签关系图
+#[derive(Serialize, Debug)]
+pub struct TagGraph {
+    /// 标签节点，按文章数量降序，数量相同时按标签名排序
+    pub nodes: Vec<TagNode>,
+    /// 加权边 `[a, b, count]`：`a`、`b` 为 `nodes` 中的序号（a < b），`count` 为同时带有两个标签的文章数量，
+    /// 按数量降序排列
+    pub edges: Vec<(usize, usize, usize)>,
+}
+
+/// 标签及其文章数量 - 用于标签关系图的节点和标签云
+#[derive(Serialize, Debug)]
+pub struct TagNode {
+    /// 标签名
+    pub tag: String,
+    /// 带有该标签的文章数量
+    pub count: usize,
+}
+
+/// 文章系列 - 分为多篇发布的文章，用于系列列表和系列内的导航
+#[derive(Serialize, Debug)]
+pub struct ArticleSeries {
+    /// 系列名称
+    pub name: String,
+    /// 系列中的文章数量
+    pub count: usize,
+    /// 系列中的文章，按发布时间从早到晚排列（阅读顺序）
+    pub articles: Vec<ArticleMetadata>,
+}
+
+/// 作者及其文章数量 - 用于多作者博客的作者列表
+#[derive(Serialize, Debug)]
+pub struct AuthorCount {
+    /// 作者
+    pub name: String,
+    /// 该作者的文章数量
+    pub count: usize,
+}
+
+/// 相邻文章 - 文章页面的上一篇/下一篇导航
+#[derive(Serialize, Debug)]
+pub struct AdjacentArticles {
+    /// 上一篇：发布时间更早的文章，没有时为空
+    pub previous: Option<ArticleMetadata>,
+    /// 下一篇：发布时间更晚的文章，没有时为空
+    pub next: Option<ArticleMetadata>,
+}
+
+/// 相关文章 - 按共同标签和发布时间的接近程度计算
+#[derive(Serialize, Debug)]
+pub struct RelatedArticle {
+    /// 文章
+    pub article: ArticleMetadata,
+    /// 与原文章共同的标签
+    pub shared_tags: Vec<String>,
+    /// 相关度：共同标签数加上发布时间接近度（0到1，相差越近越接近1）
+    pub score: f64,
+}
+
+/// 归档日历中的一天
+#[derive(Serialize, Debug)]
+pub struct CalendarDay {
+    /// 日期，格式为 YYYY-MM-DD（UTC）
+    pub date: String,
+    /// 当天发布的文章数量
+    pub count: usize,
+    /// 当天发布的文章ID列表
+    pub ids: Vec<String>,
+}
+
+/// 归档日历 - 一年中每天的发布情况，用于绘制写作热力图
+#[derive(Serialize, Debug)]
+pub struct ArchiveCalendar {
+    /// 年份
+    pub year: i32,
+    /// 全年发布的文章总数
+    pub total: usize,
+    /// 全年每一天的发布情况，按日期升序，没有发布文章的日期数量为0
+    pub days: Vec<CalendarDay>,
+}
+
+/// 已加载索引的信息 - 用于显示索引的新旧程度，发现未更新的部署
+#[derive(Serialize, Debug)]
+pub struct IndexInfo {
+    /// 索引名称
+    pub name: String,
+    /// 列出的文章数量（按可见级别过滤后）
+    pub article_count: usize,
+    /// 列出的文章中的标签数量
+    pub tag_count: usize,
+    /// 索引构建时间，没有头部的旧索引为空；合并加载多个索引时为最早的构建时间
+    pub created_at: Option<DateTime<Utc>>,
+    /// 索引格式版本（如 "8.0"），没有头部的旧索引为空
+    pub version: Option<String>,
+    /// 当前过滤器支持的最大索引版本
+    pub max_version: u8,
+}
+
+/// 发布统计的时间粒度
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Granularity {
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    fn parse(value: &str) -> Result<Self, FilterError> {
+        match value {
+            "day" => Ok(Granularity::Day),
+            "week" => Ok(Granularity::Week),
+            "month" => Ok(Granularity::Month),
+            _ => Err(FilterError::InvalidParams(format!("无效的统计粒度: {}，应为 day、week 或 month", value))),
+        }
+    }
+
+    /// 日期所属时间段的第一天：按周统计时为所在周的周一
+    fn bucket_start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Granularity::Day => date,
+            Granularity::Week => date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64),
+            Granularity::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// 时间段的ISO格式键
+    fn key(self, start: NaiveDate) -> String {
+        match self {
+            Granularity::Day | Granularity::Week => start.format("%Y-%m-%d").to_string(),
+            Granularity::Month => start.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+/// 发布统计中的一个时间段
+#[derive(Serialize, Debug)]
+pub struct HistogramBucket {
+    /// ISO格式的时间段键（UTC）：按天为 YYYY-MM-DD，按周为该周周一的 YYYY-MM-DD，按月为 YYYY-MM
+    pub key: String,
+    /// 该时间段内发布的文章数量
+    pub count: usize,
+}
+
+/// 界面语言 - 决定归档年月标签的格式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// 中文："2024年3月"
+    #[default]
+    Zh,
+    /// 英文："March 2024"
+    En,
+}
+
+impl std::str::FromStr for Locale {
+    type Err = String;
+
+    /// 按语言代码的主语言部分识别，如 `zh-CN`、`en_US`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_lowercase();
+        match value.split(['-', '_']).next().unwrap_or_default() {
+            "zh" => Ok(Locale::Zh),
+            "en" => Ok(Locale::En),
+            _ => Err(format!("不支持的语言: {}", value)),
+        }
+    }
+}
+
+impl Locale {
+    /// 年份标签
+    fn year_label(self, year: i32) -> String {
+        match self {
+            Locale::Zh => format!("{}年", year),
+            Locale::En => year.to_string(),
+        }
+    }
+
+    /// 月份标签，`month` 为 1-12
+    fn month_label(self, year: i32, month: u32) -> String {
+        const MONTH_NAMES: [&str; 12] = [
+            "January", "February", "March", "April", "May", "June",
+            "July", "August", "September", "October", "November", "December",
+        ];
+        match self {
+            Locale::Zh => format!("{}年{}月", year, month),
+            Locale::En => format!("{} {}", MONTH_NAMES[(month - 1) as usize], year),
+        }
+    }
+}
+
+/// 归档中的一个月
+#[derive(Serialize, Debug)]
+pub struct ArchiveMonth {
+    /// ISO格式的月份键，格式为 YYYY-MM（UTC）
+    pub key: String,
+    /// 月份（1-12）
+    pub month: u32,
+    /// 当月发布的文章数量
+    pub count: usize,
+    /// 按当前语言格式化的标签（如 "2024年3月"、"March 2024"），未请求本地化时为空
+    pub label: Option<String>,
+}
+
+/// 归档中的一年
+#[derive(Serialize, Debug)]
+pub struct ArchiveYear {
+    /// ISO格式的年份键，格式为 YYYY
+    pub key: String,
+    /// 年份
+    pub year: i32,
+    /// 全年发布的文章数量
+    pub count: usize,
+    /// 按当前语言格式化的标签（如 "2024年"），未请求本地化时为空
+    pub label: Option<String>,
+    /// 有文章的月份，按时间降序
+    pub months: Vec<ArchiveMonth>,
+}
+
+/// 年月归档 - 每年和每月的文章数量，用于归档下拉框
+#[derive(Serialize, Debug)]
+pub struct Archive {
+    /// 标签使用的语言，未请求本地化时为空
+    pub locale: Option<Locale>,
+    /// 有文章的年份，按时间降序
+    pub years: Vec<ArchiveYear>,
+}
+
+/// 解析 YYYY-MM 格式的月份键，返回年份和月份（1-12）
+fn parse_month_key(key: &str) -> Option<(i32, u32)> {
+    let (year, month) = key.trim().split_once('-')?;
+    let year = year.parse().ok()?;
+    let month = month.parse().ok().filter(|month| (1..=12).contains(month))?;
+    Some((year, month))
+}
+
+/// SplitMix64 - 把种子打散为均匀分布的64位数，相邻的种子也得到不相关的结果
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// 排序方式
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SortOrder {
+    /// 最新发布的在前（默认）
+    #[default]
+    Newest,
+    /// 最早发布的在前
+    Oldest,
+    /// 按标题升序
+    TitleAsc,
+    /// 按标题降序
+    TitleDesc,
+    /// 按阅读时间（词数）升序，篇幅短的在前
+    ReadingTimeAsc,
+    /// 按阅读时间（词数）降序，篇幅长的在前
+    ReadingTimeDesc,
+}
+
+impl SortOrder {
+    /// 解析排序参数，未知的排序方式返回None
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "newest" => Some(SortOrder::Newest),
+            "oldest" => Some(SortOrder::Oldest),
+            "title_asc" => Some(SortOrder::TitleAsc),
+            "title_desc" => Some(SortOrder::TitleDesc),
+            "reading_time_asc" => Some(SortOrder::ReadingTimeAsc),
+            "reading_time_desc" => Some(SortOrder::ReadingTimeDesc),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            SortOrder::Newest => "newest",
+            SortOrder::Oldest => "oldest",
+            SortOrder::TitleAsc => "title_asc",
+            SortOrder::TitleDesc => "title_desc",
+            SortOrder::ReadingTimeAsc => "reading_time_asc",
+            SortOrder::ReadingTimeDesc => "reading_time_desc",
+        }
+    }
+
+    /// 筛选参数中的排序方式，未指定或未知时使用默认值；按系列筛选时默认按阅读顺序
+    fn from_params(params: &FilterParams) -> Self {
+        let default = if params.series.is_some() { SortOrder::Oldest } else { SortOrder::default() };
+        params.sort.as_deref().and_then(Self::parse).unwrap_or(default)
+    }
+
+    /// 按标题排序
+    pub(crate) fn by_title(self) -> bool {
+        matches!(self, SortOrder::TitleAsc | SortOrder::TitleDesc)
+    }
+
+    /// 按排序键比较两篇文章，排序键相同时返回Equal
+    fn compare_key(self, keys: &SortKeys, a: usize, b: usize) -> std::cmp::Ordering {
+        let (x, y) = (&keys.articles[a], &keys.articles[b]);
+        match self {
+            SortOrder::Newest => y.date.cmp(&x.date),
+            SortOrder::Oldest => x.date.cmp(&y.date),
+            SortOrder::TitleAsc => keys.compare_titles(a, b),
+            SortOrder::TitleDesc => keys.compare_titles(b, a),
+            SortOrder::ReadingTimeAsc => x.word_count.cmp(&y.word_count),
+            SortOrder::ReadingTimeDesc => y.word_count.cmp(&x.word_count),
+        }
+    }
+}
+
+/// 排序使用的键 - 文章的排序和筛选键，以及按排序规则排列的标题位置
+struct SortKeys<'a> {
+    articles: &'a [IndexedArticle],
+    /// 按字节比较标题时为None
+    title_ranks: Option<&'a [u32]>,
+}
+
+impl SortKeys<'_> {
+    fn compare_titles(&self, a: usize, b: usize) -> std::cmp::Ordering {
+        match self.title_ranks {
+            Some(ranks) => ranks[a].cmp(&ranks[b]),
+            None => self.articles[a].title.cmp(&self.articles[b].title),
+        }
+    }
+}
+
+/// 文章列表的顺序 - 排序方式和次要排序方式，以及置顶文章是否排在前面；都相同的文章按文章ID排序
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ListOrder {
+    pub(crate) sort: SortOrder,
+    pub(crate) secondary: Option<SortOrder>,
+    pub(crate) featured_first: bool,
+    /// 标题的排序规则，不按标题排序时总是按字节比较
+    pub(crate) collation: Collation,
+}
+
+impl ListOrder {
+    /// 按发布时间从早到晚的顺序
+    const OLDEST: ListOrder = ListOrder {
+        sort: SortOrder::Oldest,
+        secondary: None,
+        featured_first: false,
+        collation: Collation::Binary,
+    };
+
+    fn from_params(params: &FilterParams, locale: Locale) -> Self {
+        let sort = SortOrder::from_params(params);
+        let secondary = params.sort_secondary.as_deref().and_then(SortOrder::parse);
+        let collation = if sort.by_title() || secondary.is_some_and(SortOrder::by_title) {
+            params.collation.as_deref()
+                .and_then(|collation| Collation::parse(collation, locale))
+                .unwrap_or(Collation::for_locale(locale))
+        } else {
+            Collation::Binary
+        };
+        ListOrder {
+            sort,
+            secondary,
+            featured_first: params.featured_first.unwrap_or(params.group_by.is_none() && params.series.is_none()),
+            collation,
+        }
+    }
+
+    fn compare(self, keys: &SortKeys, a: usize, b: usize) -> std::cmp::Ordering {
+        let (x, y) = (&keys.articles[a], &keys.articles[b]);
+        let featured = if self.featured_first {
+            y.featured.cmp(&x.featured)
+        } else {
+            std::cmp::Ordering::Equal
+        };
+        featured
+            .then_with(|| self.sort.compare_key(keys, a, b))
+            .then_with(|| self.secondary.map_or(std::cmp::Ordering::Equal, |sort| sort.compare_key(keys, a, b)))
+            .then_with(|| x.id.cmp(&y.id))
+    }
+}
+
+/// 是否为已知的标签匹配方式（any 或 all）
+fn is_tag_mode(mode: &str) -> bool {
+    matches!(mode, "any" | "all")
+}
+
+/// 解析日期范围的一端（YYYY-MM-DD），开始日期取当天0点，结束日期取当天最后一秒（UTC）
+fn parse_date_bound(date: &str, is_end: bool) -> Option<chrono::DateTime<chrono::Utc>> {
+    let time = if is_end { "T23:59:59Z" } else { "T00:00:00Z" };
+    chrono::DateTime::parse_from_rfc3339(&format!("{}{}", date, time))
+        .ok()
+        .map(|date| date.with_timezone(&chrono::Utc))
 }
 
 impl ArticleIndex {
-    /// 从压缩的二进制数据恢复索引
+    /// 从压缩的二进制数据恢复索引，文章表的区间超出范围时返回错误
     pub fn from_compressed(data: &[u8]) -> Result<Self, io::Error> {
-        utils::from_compressed_with_max_version(data, 3)
+        let index: Self = utils::from_compressed_with_version_range(data, MIN_INDEX_VERSION, MAX_INDEX_VERSION)?;
+        index.records.validate()?;
+        if index.articles.iter().any(|article| article.record as usize >= index.records.len()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "文章在文章表中的位置超出范围"));
+        }
+        Ok(index)
+    }
+    
+    /// 按排序规则排列后每篇文章标题的位置，标题相同的文章位置相同；按字节比较时返回None
+    ///
+    /// 每个排序规则只在首次使用时排序一次，之后的排序只比较位置，不需要再调用排序规则。
+    fn title_ranks(&self, collation: Collation) -> Option<&[u32]> {
+        let ranks = match collation {
+            Collation::Binary => return None,
+            Collation::Unicode => &self.unicode_title_ranks,
+            Collation::Pinyin => &self.pinyin_title_ranks,
+        };
+        let ranks = ranks.get_or_init(|| {
+            let collator = Collator::new(collation);
+            let mut ids: Vec<usize> = (0..self.articles.len()).collect();
+            ids.sort_by(|&a, &b| collator.compare(&self.articles[a].title, &self.articles[b].title));
+            
+            let mut ranks = vec![0; self.articles.len()];
+            let mut rank = 0;
+            for (i, &id) in ids.iter().enumerate() {
+                if i > 0 && self.articles[id].title != self.articles[ids[i - 1]].title {
+                    rank += 1;
+                }
+                ranks[id] = rank;
+            }
+            ranks
+        });
+        Some(ranks)
+    }
+    
+    /// 文章在文章表中的展示字段，文章不存在或不在文章表中时返回None
+    pub fn record(&self, id: usize) -> Option<ArticleRef<'_>> {
+        let article = self.articles.get(id)?;
+        self.records.get(article.record as usize)
+    }
+    
+    /// 读取文章的展示字段并组合为完整的文章元数据
+    pub fn article(&self, id: usize) -> Option<ArticleMetadata> {
+        let article = self.articles.get(id)?;
+        let record = self.record(id)?;
+        Some(ArticleMetadata {
+            id: article.id.clone(),
+            title: article.title.clone(),
+            summary: record.summary().to_string(),
+            date: article.date,
+            tags: record.tags().map(str::to_string).collect(),
+            url: record.url().to_string(),
+            featured: article.featured,
+            series: record.series().map(str::to_string),
+            author: record.author().map(str::to_string),
+            word_count: article.word_count,
+            reading_minutes: reading_minutes(article.word_count),
+        })
     }
 }
 
@@ -97,102 +794,665 @@ impl ArticleIndex {
 pub struct ArticleFilter;
 
 impl ArticleFilter {
-    /// 加载索引数据
-    pub fn load_index(data: &[u8]) -> Result<(), String> {
-        // 将FilterIndex转换为ArticleIndex
-        let filter_index = match utils::from_compressed_with_max_version::<crate::models::FilterIndex>(data, 3) {
-            Ok(index) => {
-                index
-            },
-            Err(e) => {
+    /// 加载索引数据，只列出公开文章
+    pub fn load_index(data: &[u8]) -> Result<(), FilterError> {
+        Self::load_index_with_visibility(data, Visibility::Public)
+    }
+    
+    /// 加载索引数据，只保留在 `max_visibility` 级别下列出的文章
+    ///
+    /// 不列出的文章不会出现在标签、筛选和归档结果中，但页面本身仍可通过URL访问。
+    /// 已加载索引时替换原有的索引；新数据无法解析时保留原有的索引。
+    pub fn load_index_with_visibility(data: &[u8], max_visibility: Visibility) -> Result<(), FilterError> {
+        Self::load_named_index(DEFAULT_INDEX, data, max_visibility)
+    }
+    
+    /// 以指定名称加载索引数据，与其他名称的索引并存，使用 `filter_articles_in` 查询
+    ///
+    /// 同名索引已加载时替换原有的索引；新数据无法解析时保留原有的索引。
+    pub fn load_named_index(name: &str, data: &[u8], max_visibility: Visibility) -> Result<(), FilterError> {
+        let (filter_index, metadata) = Self::decode_filter_index(data)?;
+        Self::install_index(name, filter_index, metadata, max_visibility)
+    }
+    
+    /// 加载多个索引数据并合并为一个索引，只保留在 `max_visibility` 级别下列出的文章
+    ///
+    /// 适合每个栏目单独生成筛选索引的站点，合并后的归档页无需专门构建合并索引。
+    pub fn load_indexes_with_visibility(data: &[&[u8]], max_visibility: Visibility) -> Result<(), FilterError> {
+        if data.is_empty() {
+            return Err(FilterError::InvalidParams("没有提供索引数据".to_string()));
+        }
+        
+        let (filter_indexes, metadata): (Vec<_>, Vec<_>) = data.iter()
+            .map(|data| Self::decode_filter_index(data))
+            .collect::<Result<Vec<_>, FilterError>>()?
+            .into_iter()
+            .unzip();
+        let metadata = metadata.into_iter().flatten().min_by_key(|metadata| metadata.created_at);
+        
+        Self::install_index(DEFAULT_INDEX, crate::models::FilterIndex::merge(filter_indexes), metadata, max_visibility)
+    }
+    
+    /// 清除所有已加载的索引，之后的查询返回错误，直到再次加载索引
+    pub fn clear_index() -> Result<(), FilterError> {
+        if let Some(indexes) = INDEX.get() {
+            indexes.write().map_err(|_| FilterError::LockPoisoned("获取索引写锁失败"))?.clear();
+        }
+        Ok(())
+    }
+    
+    /// 移除指定名称的索引，返回该索引是否已加载
+    pub fn remove_index(name: &str) -> Result<bool, FilterError> {
+        match INDEX.get() {
+            Some(indexes) => Ok(indexes.write().map_err(|_| FilterError::LockPoisoned("获取索引写锁失败"))?.remove(name).is_some()),
+            None => Ok(false),
+        }
+    }
+    
+    /// 已加载的索引名称，按名称排序
+    pub fn index_names() -> Result<Vec<String>, FilterError> {
+        match INDEX.get() {
+            Some(indexes) => Ok(indexes.read().map_err(|_| FilterError::LockPoisoned("获取索引读锁失败"))?.keys().cloned().collect()),
+            None => Ok(Vec::new()),
+        }
+    }
+    
+    /// 获取指定名称的索引的信息
+    pub fn get_index_info(name: &str) -> Result<IndexInfo, FilterError> {
+        let index = Self::read_index(name)?;
+        let metadata = index.metadata.as_ref();
+        
+        Ok(IndexInfo {
+            name: name.to_string(),
+            article_count: index.articles.len(),
+            tag_count: index.tag_index.len(),
+            created_at: metadata.map(|metadata| metadata.created_at),
+            version: metadata.map(|metadata| metadata.version.clone()),
+            max_version: MAX_INDEX_VERSION,
+        })
+    }
+    
+    // 解压筛选索引
+    fn decode_filter_index(data: &[u8]) -> Result<(crate::models::FilterIndex, Option<IndexMetadata>), FilterError> {
+        let filter_index = utils::from_compressed_with_version_range::<crate::models::FilterIndex>(data, MIN_INDEX_VERSION, MAX_INDEX_VERSION)
+            .and_then(|filter_index| filter_index.validate().map(|_| filter_index))
+            .map_err(|e| {
                 console::log_1(&JsValue::from_str(&format!("索引解析失败: {}", e)));
-                return Err(format!("解析索引失败: {}", e));
+                FilterError::ParseError(format!("解析索引失败: {}", e))
+            })?;
+        // 索引数据已通过校验，头部无法读取时只是缺少元数据
+        let metadata = utils::read_index_metadata(data, MAX_INDEX_VERSION).ok().flatten();
+        Ok((filter_index, metadata))
+    }
+    
+    // 转换为ArticleIndex并以指定名称存储到全局变量，替换同名的索引
+    //
+    // 转换在获取写锁之前完成，替换期间其他读取只需等待一次插入；
+    // 正在使用旧索引的查询不受影响，结束后旧索引随之释放。
+    fn install_index(
+        name: &str,
+        filter_index: crate::models::FilterIndex,
+        metadata: Option<IndexMetadata>,
+        max_visibility: Visibility,
+    ) -> Result<(), FilterError> {
+        let mut article_index = Self::convert_filter_to_article_index(filter_index, max_visibility);
+        article_index.metadata = metadata;
+        INDEX.get_or_init(|| RwLock::new(BTreeMap::new()))
+            .write()
+            .map_err(|_| FilterError::LockPoisoned("获取索引写锁失败"))?
+            .insert(name.to_string(), Arc::new(article_index));
+        Ok(())
+    }
+    
+    // 取出指定名称的索引，读锁在返回前释放
+    fn read_index(name: &str) -> Result<Arc<ArticleIndex>, FilterError> {
+        let indexes = INDEX.get()
+            .ok_or(FilterError::NotInitialized)?
+            .read()
+            .map_err(|_| FilterError::LockPoisoned("获取索引读锁失败"))?;
+        indexes.get(name)
+            .cloned()
+            .ok_or_else(|| FilterError::IndexNotLoaded(name.to_string()))
+    }
+    
+    // 将FilterIndex转换为ArticleIndex
+    fn convert_filter_to_article_index(filter_index: crate::models::FilterIndex, max_visibility: Visibility) -> ArticleIndex {
+        // 筛选索引中的文章位置到保留后位置的映射
+        let mut positions: HashMap<usize, usize> = HashMap::new();
+        
+        // 跳过不列出的文章，文章表保持原样，不列出的文章的展示字段不会被读取
+        let articles: Vec<IndexedArticle> = filter_index.articles
+            .into_iter()
+            .enumerate()
+            .filter(|(_, article)| article.visibility.is_listed(max_visibility))
+            .map(|(old_id, article)| {
+                positions.insert(old_id, positions.len());
+                article
+            })
+            .collect();
+        
+        // 转换标签和日期索引，只保留仍有文章的条目
+        fn remap<K: std::hash::Hash + Eq>(
+            index: HashMap<K, HashSet<usize>>,
+            positions: &HashMap<usize, usize>,
+        ) -> HashMap<K, Vec<usize>> {
+            index.into_iter()
+                .filter_map(|(key, article_ids)| {
+                    let mut article_ids: Vec<usize> = article_ids.into_iter()
+                        .filter_map(|old_id| positions.get(&old_id).copied())
+                        .collect();
+                    article_ids.sort_unstable();
+                    (!article_ids.is_empty()).then_some((key, article_ids))
+                })
+                .collect()
+        }
+        
+        ArticleIndex {
+            articles,
+            records: filter_index.records,
+            tag_index: remap(filter_index.tag_index, &positions),
+            year_index: remap(filter_index.year_index, &positions),
+            month_index: remap(filter_index.month_index, &positions),
+            series_index: remap(filter_index.series_index, &positions),
+            author_index: remap(filter_index.author_index, &positions),
+            presets: filter_index.presets,
+            metadata: None,
+            unicode_title_ranks: OnceCell::new(),
+            pinyin_title_ranks: OnceCell::new(),
+        }
+    }
+    
+    /// 获取所有标签
+    pub fn get_all_tags() -> Result<Vec<String>, FilterError> {
+        Self::get_all_tags_in(DEFAULT_INDEX)
+    }
+    
+    /// 与 `get_all_tags` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_all_tags_in(name: &str) -> Result<Vec<String>, FilterError> {
+        // 获取索引
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        // 提取所有标签
+        let tags = index.tag_index.keys().cloned().collect();
+        Ok(tags)
+    }
+    
+    /// 获取所有标签及其文章数量，按数量降序，数量相同时按标签名排序
+    pub fn get_tags_with_counts() -> Result<Vec<TagNode>, FilterError> {
+        Self::get_tags_with_counts_in(DEFAULT_INDEX)
+    }
+    
+    /// 与 `get_tags_with_counts` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_tags_with_counts_in(name: &str) -> Result<Vec<TagNode>, FilterError> {
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        Ok(Self::tag_counts(index))
+    }
+    
+    // 统计各标签的文章数量并排序
+    fn tag_counts(index: &ArticleIndex) -> Vec<TagNode> {
+        let mut tags: Vec<TagNode> = index.tag_index.iter()
+            .map(|(tag, article_ids)| TagNode { tag: tag.clone(), count: article_ids.len() })
+            .collect();
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        tags
+    }
+    
+    /// 获取所有系列，最近有文章发布的系列在前，系列中的文章按阅读顺序排列
+    pub fn get_series() -> Result<Vec<ArticleSeries>, FilterError> {
+        Self::get_series_in(DEFAULT_INDEX)
+    }
+    
+    /// 与 `get_series` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_series_in(name: &str) -> Result<Vec<ArticleSeries>, FilterError> {
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        let mut series: Vec<ArticleSeries> = index.series_index.iter()
+            .map(|(name, article_ids)| {
+                let mut article_ids = article_ids.clone();
+                Self::apply_sorting(index, &mut article_ids, ListOrder::OLDEST);
+                let articles: Vec<ArticleMetadata> = article_ids.into_iter()
+                    .filter_map(|id| index.article(id))
+                    .collect();
+                ArticleSeries { name: name.clone(), count: articles.len(), articles }
+            })
+            .collect();
+        let latest = |series: &ArticleSeries| series.articles.last().map(|article| article.date);
+        series.sort_by(|a, b| latest(b).cmp(&latest(a)).then_with(|| a.name.cmp(&b.name)));
+        
+        Ok(series)
+    }
+    
+    /// 获取所有作者及其文章数量，按数量降序，数量相同时按作者排序
+    pub fn get_authors() -> Result<Vec<AuthorCount>, FilterError> {
+        Self::get_authors_in(DEFAULT_INDEX)
+    }
+    
+    /// 与 `get_authors` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_authors_in(name: &str) -> Result<Vec<AuthorCount>, FilterError> {
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        let mut authors: Vec<AuthorCount> = index.author_index.iter()
+            .map(|(name, article_ids)| AuthorCount { name: name.clone(), count: article_ids.len() })
+            .collect();
+        authors.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        
+        Ok(authors)
+    }
+    
+    /// 获取所有筛选预设
+    pub fn get_presets() -> Result<Vec<FilterPreset>, FilterError> {
+        Self::get_presets_in(DEFAULT_INDEX)
+    }
+    
+    /// 与 `get_presets` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_presets_in(name: &str) -> Result<Vec<FilterPreset>, FilterError> {
+        // 获取索引
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        Ok(index.presets.clone())
+    }
+    
+    /// 按预设筛选文章
+    pub fn filter_by_preset(name: &str, page: usize) -> Result<FilterResult, FilterError> {
+        Self::filter_by_preset_in(DEFAULT_INDEX, name, page)
+    }
+    
+    /// 与 `filter_by_preset` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn filter_by_preset_in(index_name: &str, name: &str, page: usize) -> Result<FilterResult, FilterError> {
+        // 查找预设并转换为筛选参数，释放锁后再执行筛选
+        let params = {
+            let index = Self::read_index(index_name)?;
+            let index = index.as_ref();
+            
+            let preset = index.presets.iter()
+                .find(|preset| preset.name == name)
+                .ok_or_else(|| FilterError::NotFound(format!("筛选预设不存在: {}", name)))?;
+            
+            FilterParams {
+                tags: Some(preset.rules.tags.clone()),
+                tag_mode: preset.rules.tag_mode.clone(),
+                sort: Some(preset.rules.sort_param().to_string()),
+                page: Some(page),
+                ..Default::default()
+            }
+        };
+        
+        Self::filter_articles_in(index_name, &params)
+    }
+    
+    /// 获取标签关系图，只保留共同出现次数不少于 `min_cooccurrence` 的边
+    pub fn get_tag_graph(min_cooccurrence: usize) -> Result<TagGraph, FilterError> {
+        Self::get_tag_graph_in(DEFAULT_INDEX, min_cooccurrence)
+    }
+    
+    /// 与 `get_tag_graph` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_tag_graph_in(name: &str, min_cooccurrence: usize) -> Result<TagGraph, FilterError> {
+        // 获取索引
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        let nodes = Self::tag_counts(index);
+        let positions: HashMap<&str, usize> = nodes.iter()
+            .enumerate()
+            .map(|(i, node)| (node.tag.as_str(), i))
+            .collect();
+        
+        // 从标签索引还原每篇文章的标签，不需要解码文章记录
+        let mut article_tags: Vec<Vec<usize>> = vec![Vec::new(); index.articles.len()];
+        for (tag, article_ids) in &index.tag_index {
+            let Some(&position) = positions.get(tag.as_str()) else {
+                continue;
+            };
+            for &id in article_ids {
+                article_tags[id].push(position);
+            }
+        }
+        
+        // 统计每篇文章中两两标签的共同出现次数
+        let mut cooccurrence: HashMap<(usize, usize), usize> = HashMap::new();
+        for mut tags in article_tags {
+            tags.sort_unstable();
+            tags.dedup();
+            
+            for (i, &a) in tags.iter().enumerate() {
+                for &b in &tags[i + 1..] {
+                    *cooccurrence.entry((a, b)).or_insert(0) += 1;
+                }
+            }
+        }
+        
+        let mut edges: Vec<(usize, usize, usize)> = cooccurrence.into_iter()
+            .filter(|(_, count)| *count >= min_cooccurrence.max(1))
+            .map(|((a, b), count)| (a, b, count))
+            .collect();
+        edges.sort_by(|x, y| y.2.cmp(&x.2).then_with(|| (x.0, x.1).cmp(&(y.0, y.1))));
+        
+        Ok(TagGraph { nodes, edges })
+    }
+    
+    /// 获取指定年份的归档日历
+    pub fn get_calendar(year: i32) -> Result<ArchiveCalendar, FilterError> {
+        Self::get_calendar_in(DEFAULT_INDEX, year)
+    }
+    
+    /// 与 `get_calendar` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_calendar_in(name: &str, year: i32) -> Result<ArchiveCalendar, FilterError> {
+        let first_day = NaiveDate::from_ymd_opt(year, 1, 1)
+            .ok_or_else(|| FilterError::InvalidParams(format!("无效的年份: {}", year)))?;
+        
+        // 获取索引
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        // 生成全年每一天
+        let mut days: Vec<CalendarDay> = first_day.iter_days()
+            .take_while(|day| day.year() == year)
+            .map(|day| CalendarDay {
+                date: day.format("%Y-%m-%d").to_string(),
+                count: 0,
+                ids: Vec::new(),
+            })
+            .collect();
+        
+        // 按发布日期归入对应的一天
+        let mut total = 0;
+        for article in &index.articles {
+            let date = article.date.date_naive();
+            if date.year() != year {
+                continue;
             }
-        };
+            
+            let day = &mut days[date.ordinal0() as usize];
+            day.count += 1;
+            day.ids.push(article.id.clone());
+            total += 1;
+        }
+        
+        Ok(ArchiveCalendar { year, total, days })
+    }
+    
+    /// 获取按天、周或月统计的发布数量，用于绘制发布活动热力图
+    ///
+    /// 只包含有文章发布的时间段，按时间升序排列。
+    pub fn get_post_histogram(granularity: &str) -> Result<Vec<HistogramBucket>, FilterError> {
+        Self::get_post_histogram_in(DEFAULT_INDEX, granularity)
+    }
+    
+    /// 与 `get_post_histogram` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_post_histogram_in(name: &str, granularity: &str) -> Result<Vec<HistogramBucket>, FilterError> {
+        let granularity = Granularity::parse(granularity)?;
         
-        // 转换为ArticleIndex
-        let article_index = Self::convert_filter_to_article_index(filter_index);
+        // 获取索引
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
         
-        // 存储到全局变量
-        INDEX.get_or_init(|| Mutex::new(Some(article_index)));
-        Ok(())
+        let mut counts: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for article in &index.articles {
+            let start = granularity.bucket_start(article.date.date_naive());
+            *counts.entry(start).or_insert(0) += 1;
+        }
+        
+        Ok(counts.into_iter()
+            .map(|(start, count)| HistogramBucket { key: granularity.key(start), count })
+            .collect())
     }
     
-    // 将FilterIndex转换为ArticleIndex
-    fn convert_filter_to_article_index(filter_index: crate::models::FilterIndex) -> ArticleIndex {
-        // 转换文章元数据
-        let articles: Vec<ArticleMetadata> = filter_index.articles
-            .into_iter()
-            .map(|article| {
-                // 只保留需要的字段
-                ArticleMetadata {
-                    id: article.id,
-                    title: article.title,
-                    summary: article.summary,
-                    date: article.date,
-                    tags: article.tags,
-                    url: article.url,
+    /// 获取文章按发布时间的上一篇和下一篇，指定 `within_tag` 时只在带有该标签的文章（如同一系列）中查找
+    ///
+    /// 发布时间相同的文章按文章ID排序。
+    pub fn get_adjacent(article_id: &str, within_tag: Option<&str>) -> Result<AdjacentArticles, FilterError> {
+        Self::get_adjacent_in(DEFAULT_INDEX, article_id, within_tag)
+    }
+    
+    /// 与 `get_adjacent` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_adjacent_in(name: &str, article_id: &str, within_tag: Option<&str>) -> Result<AdjacentArticles, FilterError> {
+        // 获取索引
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        let current = index.articles.iter()
+            .position(|article| article.id == article_id)
+            .ok_or_else(|| FilterError::NotFound(format!("文章不存在: {}", article_id)))?;
+        
+        // 候选文章（包括当前文章），按发布时间升序
+        let mut ids: Vec<usize> = match within_tag {
+            Some(tag) => index.tag_index.get(tag).cloned().unwrap_or_default(),
+            None => (0..index.articles.len()).collect(),
+        };
+        if !ids.contains(&current) {
+            ids.push(current);
+        }
+        ids.sort_by(|&a, &b| {
+            let (a, b) = (&index.articles[a], &index.articles[b]);
+            a.date.cmp(&b.date).then_with(|| a.id.cmp(&b.id))
+        });
+        
+        let position = ids.iter().position(|&id| id == current).unwrap_or_default();
+        let article_at = |position: Option<usize>| {
+            position.and_then(|position| ids.get(position)).and_then(|&id| index.article(id))
+        };
+        Ok(AdjacentArticles {
+            previous: article_at(position.checked_sub(1)),
+            next: article_at(Some(position + 1)),
+        })
+    }
+    
+    /// 获取相关文章 - 与文章至少有一个共同标签的其他文章，按相关度降序返回最多 `limit` 篇
+    ///
+    /// 相关度为共同标签数加上发布时间接近度，标签数相同时发布时间较近的文章排在前面。
+    /// 只使用筛选索引，不需要加载搜索索引。
+    pub fn get_related(article_id: &str, limit: usize) -> Result<Vec<RelatedArticle>, FilterError> {
+        Self::get_related_in(DEFAULT_INDEX, article_id, limit)
+    }
+    
+    /// 与 `get_related` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_related_in(name: &str, article_id: &str, limit: usize) -> Result<Vec<RelatedArticle>, FilterError> {
+        // 获取索引
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
+        
+        let current = index.articles.iter()
+            .position(|article| article.id == article_id)
+            .ok_or_else(|| FilterError::NotFound(format!("文章不存在: {}", article_id)))?;
+        let date = index.articles[current].date;
+        let tags: Vec<String> = index.record(current)
+            .map(|record| record.tags().map(str::to_string).collect())
+            .unwrap_or_default();
+        
+        // 候选文章及其共同标签
+        let mut shared: HashMap<usize, Vec<String>> = HashMap::new();
+        for tag in &tags {
+            for &id in index.tag_index.get(tag).into_iter().flatten() {
+                if id != current {
+                    shared.entry(id).or_default().push(tag.clone());
                 }
+            }
+        }
+        
+        // 按相关度排序后只解码保留的文章
+        let mut scored: Vec<(usize, Vec<String>, f64)> = shared.into_iter()
+            .map(|(id, shared_tags)| {
+                let days = (index.articles[id].date - date).num_days().unsigned_abs() as f64;
+                let proximity = 1.0 / (1.0 + days / RELATED_DATE_SCALE_DAYS);
+                let score = shared_tags.len() as f64 + proximity;
+                (id, shared_tags, score)
+            })
+            .collect();
+        scored.sort_by(|a, b| {
+            b.2.total_cmp(&a.2).then_with(|| index.articles[a.0].id.cmp(&index.articles[b.0].id))
+        });
+        scored.truncate(limit);
+        
+        let related = scored.into_iter()
+            .filter_map(|(id, shared_tags, score)| {
+                index.article(id).map(|article| RelatedArticle { article, shared_tags, score })
             })
             .collect();
+        Ok(related)
+    }
+    
+    /// 从符合筛选条件的文章中随机选取一篇，没有符合条件的文章时返回None
+    ///
+    /// 排序和分页参数不影响结果。指定 `seed` 时对同一索引和同一参数总是返回同一篇文章。
+    pub fn get_random(params: &FilterParams, seed: Option<u64>) -> Result<Option<ArticleMetadata>, FilterError> {
+        Self::get_random_in(DEFAULT_INDEX, params, seed)
+    }
+    
+    /// 与 `get_random` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_random_in(name: &str, params: &FilterParams, seed: Option<u64>) -> Result<Option<ArticleMetadata>, FilterError> {
+        // 获取索引
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
         
-        // 转换标签索引
-        let mut tag_index = HashMap::new();
-        for (tag, article_ids) in filter_index.tag_index {
-            tag_index.insert(tag, article_ids.into_iter().collect::<Vec<_>>());
+        // 候选文章按索引中的顺序排列，保证相同的种子选中相同的文章
+        let mut candidate_ids = Self::apply_filters(index, params)?;
+        if candidate_ids.is_empty() {
+            return Ok(None);
         }
+        candidate_ids.sort_unstable();
         
-        ArticleIndex {
-            articles,
-            tag_index,
+        let seed = seed.unwrap_or_else(|| {
+            let now = Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64;
+            now ^ splitmix64(RANDOM_CALLS.fetch_add(1, Ordering::Relaxed))
+        });
+        let position = (splitmix64(seed) % candidate_ids.len() as u64) as usize;
+        
+        Ok(index.article(candidate_ids[position]))
+    }
+    
+    /// 设置界面语言
+    pub fn set_locale(locale: Locale) -> Result<(), FilterError> {
+        let mut guard = LOCALE.get_or_init(|| RwLock::new(Locale::default()))
+            .write()
+            .map_err(|_| FilterError::LockPoisoned("获取语言设置锁失败"))?;
+        *guard = locale;
+        Ok(())
+    }
+    
+    /// 当前界面语言，未设置时为中文
+    pub fn locale() -> Result<Locale, FilterError> {
+        match LOCALE.get() {
+            Some(locale) => locale.read().map(|guard| *guard).map_err(|_| FilterError::LockPoisoned("获取语言设置锁失败")),
+            None => Ok(Locale::default()),
         }
     }
     
-    /// 获取所有标签
-    pub fn get_all_tags() -> Result<Vec<String>, String> {
+    /// 获取年月归档，`localized` 为true时按当前界面语言生成年月标签
+    pub fn get_archive(localized: bool) -> Result<Archive, FilterError> {
+        Self::get_archive_in(DEFAULT_INDEX, localized)
+    }
+    
+    /// 与 `get_archive` 相同，使用指定名称的索引（见 `load_named_index`）
+    pub fn get_archive_in(name: &str, localized: bool) -> Result<Archive, FilterError> {
+        let locale = if localized { Some(Self::locale()?) } else { None };
+        
         // 获取索引
-        let index_mutex = INDEX.get().ok_or("索引未初始化")?;
-        let index_guard = index_mutex.lock().map_err(|_| "获取索引锁失败")?;
-        let index = index_guard.as_ref().ok_or("索引为空")?;
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
         
-        // 提取所有标签
-        let tags = index.tag_index.keys().cloned().collect();
-        Ok(tags)
+        // 年份和月份索引按发布日期（UTC）建立
+        let mut month_counts: std::collections::BTreeMap<(i32, u32), usize> = std::collections::BTreeMap::new();
+        for (key, article_ids) in &index.month_index {
+            if let Some(month) = parse_month_key(key) {
+                *month_counts.entry(month).or_insert(0) += article_ids.len();
+            }
+        }
+        
+        let mut year_keys: Vec<i32> = index.year_index.keys().copied().collect();
+        year_keys.sort_unstable_by(|a, b| b.cmp(a));
+        let years = year_keys.into_iter()
+            .map(|year| ArchiveYear {
+                key: format!("{:04}", year),
+                year,
+                count: index.year_index[&year].len(),
+                label: locale.map(|locale| locale.year_label(year)),
+                months: month_counts.range((year, 1)..=(year, 12))
+                    .rev()
+                    .map(|(&(year, month), &count)| ArchiveMonth {
+                        key: format!("{:04}-{:02}", year, month),
+                        month,
+                        count,
+                        label: locale.map(|locale| locale.month_label(year, month)),
+                    })
+                    .collect(),
+            })
+            .collect();
+        
+        Ok(Archive { locale, years })
     }
     
     /// 筛选文章
-    pub fn filter_articles(params: &FilterParams) -> Result<FilterResult, String> {
+    pub fn filter_articles(params: &FilterParams) -> Result<FilterResult, FilterError> {
+        Self::filter_articles_in(DEFAULT_INDEX, params)
+    }
+    
+    /// 在指定名称的索引中筛选文章
+    pub fn filter_articles_in(name: &str, params: &FilterParams) -> Result<FilterResult, FilterError> {
         // 获取索引
-        let index_mutex = INDEX.get().ok_or("索引未初始化")?;
-        let index_guard = index_mutex.lock().map_err(|_| "获取索引锁失败")?;
-        let index = index_guard.as_ref().ok_or("索引为空")?;
+        let index = Self::read_index(name)?;
+        let index = index.as_ref();
         
-        // 筛选候选文章
-        let candidate_ids = Self::apply_filters(index, params)?;
+        // 检查会被忽略或替换为默认值的参数
+        if let Some(limit) = params.limit.filter(|&limit| limit > MAX_PAGE_LIMIT) {
+            return Err(FilterError::InvalidParams(format!("每页条数 {} 超过上限 {}", limit, MAX_PAGE_LIMIT)));
+        }
+        let mut warnings = Self::validate_params(index, params);
+        let group_by = params.group_by.as_deref().map(GroupBy::parse).transpose()?;
         
-        // 从ID获取文章元数据
-        let mut filtered_articles = candidate_ids
-            .into_iter()
-            .filter_map(|id| index.articles.get(id).cloned())
-            .collect::<Vec<_>>();
+        // 筛选候选文章
+        let mut filtered_ids = Self::apply_filters(index, params)?;
         
-        // 排序
-        Self::apply_sorting(&mut filtered_articles, params);
+        // 按排序和筛选键排序，不需要解码文章记录
+        let order = ListOrder::from_params(params, Self::locale()?);
+        Self::apply_sorting(index, &mut filtered_ids, order);
         
-        // 分页
-        let page = params.page.unwrap_or(1).max(1);
+        // 分页 - 指定游标时从游标之后的文章开始，否则按页码
         let limit = params.limit.unwrap_or(12).max(1);
-        let total = filtered_articles.len();
+        let total = filtered_ids.len();
         let total_pages = (total + limit - 1) / limit.max(1);
-        let page = page.min(total_pages.max(1));
-        
-        let start = (page - 1) * limit;
+        let (start, page) = match params.cursor.as_deref() {
+            Some(cursor) => {
+                let cursor = Cursor::decode(cursor, order)?;
+                let collator = Collator::new(order.collation);
+                let start = filtered_ids.partition_point(|&id| !cursor.precedes(&index.articles[id], &collator));
+                (start, (start / limit + 1).min(total_pages.max(1)))
+            }
+            None => {
+                let page = params.page.unwrap_or(1).max(1);
+                if page > total_pages.max(1) {
+                    warnings.push(format!("页码 {} 超出总页数 {}，已使用最后一页", page, total_pages.max(1)));
+                }
+                let page = page.min(total_pages.max(1));
+                ((page - 1) * limit, page)
+            }
+        };
         let end = (start + limit).min(total);
         
-        let paged_articles = if start < filtered_articles.len() {
-            filtered_articles[start..end].to_vec()
-        } else {
-            Vec::new()
+        // 后面还有文章时返回指向当前页最后一篇文章的游标
+        let next_cursor = (start < end && end < total)
+            .then(|| Cursor::after(&index.articles[filtered_ids[end - 1]], order).encode());
+        
+        let paged_ids = filtered_ids.get(start..end).unwrap_or_default();
+        
+        // 按年或月分组当前页的文章，只保留选择的字段，只解码当前页的文章记录
+        let fields = params.fields.as_deref();
+        let (paged_articles, groups) = match group_by {
+            Some(group_by) => {
+                let groups = Self::group_articles(index, &filtered_ids, paged_ids, group_by, fields)?;
+                (Vec::new(), Some(groups))
+            }
+            None => {
+                let articles = paged_ids.iter()
+                    .filter_map(|&id| ListedArticle::project(index, id, fields))
+                    .collect();
+                (articles, None)
+            }
         };
         
         // 构建结果
@@ -202,93 +1462,230 @@ impl ArticleFilter {
             page,
             limit,
             total_pages,
+            warnings,
+            groups,
+            next_cursor,
         })
     }
     
+    // 把当前页的文章按年或月分组并只保留选择的字段，`all_ids` 为排序后的全部筛选结果，用于统计每组的总数
+    fn group_articles(
+        index: &ArticleIndex,
+        all_ids: &[usize],
+        paged_ids: &[usize],
+        group_by: GroupBy,
+        fields: Option<&[ArticleField]>,
+    ) -> Result<Vec<ArticleGroup>, FilterError> {
+        let locale = Self::locale()?;
+        
+        let mut counts: HashMap<(i32, u32), usize> = HashMap::new();
+        for &id in all_ids {
+            *counts.entry(group_by.bucket(&index.articles[id].date)).or_insert(0) += 1;
+        }
+        
+        let mut groups: Vec<ArticleGroup> = Vec::new();
+        let mut positions: HashMap<(i32, u32), usize> = HashMap::new();
+        for &id in paged_ids {
+            let Some(article) = ListedArticle::project(index, id, fields) else {
+                continue;
+            };
+            let bucket = group_by.bucket(&index.articles[id].date);
+            let position = *positions.entry(bucket).or_insert_with(|| {
+                let (year, month) = bucket;
+                let (key, label) = match group_by {
+                    GroupBy::Year => (format!("{:04}", year), locale.year_label(year)),
+                    GroupBy::Month => (format!("{:04}-{:02}", year, month), locale.month_label(year, month)),
+                };
+                groups.push(ArticleGroup {
+                    key,
+                    label,
+                    count: counts.get(&bucket).copied().unwrap_or_default(),
+                    articles: Vec::new(),
+                });
+                groups.len() - 1
+            });
+            groups[position].articles.push(article);
+        }
+        
+        Ok(groups)
+    }
+    
+    // 检查筛选参数，返回会被忽略或替换为默认值的参数的警告
+    //
+    // 明显无效的参数（分组方式、月份、过大的每页条数）在筛选时直接返回错误，这里只处理
+    // 原本会被静默纠正的情况，方便在开发时发现集成问题。
+    fn validate_params(index: &ArticleIndex, params: &FilterParams) -> Vec<String> {
+        let mut warnings = Vec::new();
+        
+        if let Some(sort) = params.sort.as_deref() {
+            if SortOrder::parse(sort).is_none() {
+                warnings.push(format!("未知的排序方式 '{}'，已使用 '{}'", sort, SortOrder::from_params(params).as_str()));
+            }
+        }
+        if let Some(sort) = params.sort_secondary.as_deref() {
+            if SortOrder::parse(sort).is_none() {
+                warnings.push(format!("未知的次要排序方式 '{}'，已忽略", sort));
+            }
+        }
+        if let Some(collation) = params.collation.as_deref() {
+            if Collation::parse(collation, Locale::default()).is_none() {
+                warnings.push(format!("未知的排序规则 '{}'，已按界面语言选择", collation));
+            }
+        }
+        if let Some(mode) = params.tag_mode.as_deref().filter(|mode| !is_tag_mode(mode)) {
+            warnings.push(format!("未知的标签匹配方式 '{}'，已使用 any", mode));
+        }
+        if params.cursor.is_some() && params.page.is_some() {
+            warnings.push("指定游标时忽略页码".to_string());
+        } else if params.page == Some(0) {
+            warnings.push("页码 0 无效，页码从 1 开始，已使用 1".to_string());
+        }
+        if params.limit == Some(0) {
+            warnings.push("每页条数 0 无效，已使用 1".to_string());
+        }
+        
+        for tag in params.tags.iter().chain(&params.exclude_tags).flatten() {
+            if !index.tag_index.contains_key(tag) {
+                warnings.push(format!("标签 '{}' 不存在", tag));
+            }
+        }
+        if let Some(series) = params.series.as_deref() {
+            if !index.series_index.contains_key(series) {
+                warnings.push(format!("系列 '{}' 不存在", series));
+            }
+        }
+        for author in params.authors.iter().flatten() {
+            if !index.author_index.contains_key(author) {
+                warnings.push(format!("作者 '{}' 不存在", author));
+            }
+        }
+        
+        if let Some(date) = params.date.as_deref().filter(|date| *date != "all") {
+            let parts: Vec<&str> = date.split(',').collect();
+            if parts.len() > 2 {
+                warnings.push(format!("日期范围 '{}' 应为 \"startDate,endDate\" 格式，只使用前两个日期", date));
+            }
+            for (part, is_end) in parts.iter().zip([false, true]) {
+                if !part.is_empty() && parse_date_bound(part, is_end).is_none() {
+                    let bound = if is_end { "结束" } else { "开始" };
+                    warnings.push(format!("无法解析{}日期 '{}'，应为 YYYY-MM-DD 格式，已忽略", bound, part));
+                }
+            }
+        }
+        
+        warnings
+    }
+    
     // 应用筛选条件
-    fn apply_filters(index: &ArticleIndex, params: &FilterParams) -> Result<Vec<usize>, String> {
+    fn apply_filters(index: &ArticleIndex, params: &FilterParams) -> Result<Vec<usize>, FilterError> {
         // 初始化候选文章 ID 集合，默认包含所有文章
         let mut candidate_ids: HashSet<usize> = (0..index.articles.len()).collect();
 
         // 标签筛选
         if let Some(tags) = &params.tags {
             if !tags.is_empty() {
-                let tag_candidates = Self::filter_by_tags(index, tags);
+                // 未知的匹配方式按 any 处理（见 `validate_params`）
+                let tag_candidates = match params.tag_mode.as_deref() {
+                    Some("all") => Self::filter_by_all_tags(index, tags),
+                    _ => Self::filter_by_tags(index, tags),
+                };
                 
                 // 保留同时存在于两个集合中的元素
                 candidate_ids.retain(|id| tag_candidates.contains(id));
             }
         }
         
+        // 排除标签
+        if let Some(exclude_tags) = &params.exclude_tags {
+            if !exclude_tags.is_empty() {
+                let excluded = Self::filter_by_tags(index, exclude_tags);
+                candidate_ids.retain(|id| !excluded.contains(id));
+            }
+        }
+        
+        // 系列筛选
+        if let Some(series) = &params.series {
+            let series_ids = index.series_index.get(series).map(Vec::as_slice).unwrap_or_default();
+            candidate_ids.retain(|id| series_ids.binary_search(id).is_ok());
+        }
+        
+        // 作者筛选
+        if let Some(authors) = params.authors.as_deref().filter(|authors| !authors.is_empty()) {
+            candidate_ids.retain(|id| {
+                authors.iter().any(|author| {
+                    index.author_index.get(author).is_some_and(|author_ids| author_ids.binary_search(id).is_ok())
+                })
+            });
+        }
+        
+        // 阅读时间筛选
+        if let Some(max_minutes) = params.max_reading_minutes {
+            candidate_ids.retain(|&id| {
+                index.articles.get(id).is_some_and(|article| reading_minutes(article.word_count) <= max_minutes)
+            });
+        }
+        
+        // 置顶文章筛选
+        if params.featured_only == Some(true) {
+            candidate_ids.retain(|&id| index.articles.get(id).is_some_and(|article| article.featured));
+        }
+        
+        // 年份筛选
+        if let Some(year) = params.year {
+            let year_ids = index.year_index.get(&year).map(Vec::as_slice).unwrap_or_default();
+            candidate_ids.retain(|id| year_ids.binary_search(id).is_ok());
+        }
+        
+        // 月份筛选
+        if let Some(month) = &params.month {
+            let (year, month) = parse_month_key(month)
+                .ok_or_else(|| FilterError::InvalidParams(format!("无效的月份: {}，应为 YYYY-MM 格式", month)))?;
+            let month_ids = index.month_index.get(&format!("{}-{:02}", year, month))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            candidate_ids.retain(|id| month_ids.binary_search(id).is_ok());
+        }
+        
         // 日期筛选
         if let Some(date_param) = &params.date {
             if date_param != "all" {
                 // 解析日期范围（格式: "startDate,endDate"）
                 let date_parts: Vec<&str> = date_param.split(',').collect();
-                let start_date_str = date_parts.get(0).map(|s| *s).unwrap_or("");
-                let end_date_str = date_parts.get(1).map(|s| *s).unwrap_or("");
+                let start_date_str = date_parts.first().copied().unwrap_or("");
+                let end_date_str = date_parts.get(1).copied().unwrap_or("");
                 
                 let has_start_date = !start_date_str.is_empty();
                 let has_end_date = !end_date_str.is_empty();
                 
-                if has_start_date && has_end_date {
-                    // 两个日期都存在的情况
-                    // 尝试解析日期
-                    let start_date_fmt = format!("{}T00:00:00Z", start_date_str);
-                    let end_date_fmt = format!("{}T23:59:59Z", end_date_str);
-                    
-                    match (
-                        chrono::DateTime::parse_from_rfc3339(&start_date_fmt),
-                        chrono::DateTime::parse_from_rfc3339(&end_date_fmt)
-                    ) {
-                        (Ok(start), Ok(end)) => {
-                            let start_utc = start.with_timezone(&chrono::Utc);
-                            let end_utc = end.with_timezone(&chrono::Utc);
-                            
-                            candidate_ids.retain(|&id| {
-                                if let Some(article) = index.articles.get(id) {
-                                    article.date >= start_utc && article.date <= end_utc
-                                } else {
-                                    false
-                                }
-                            });
-                        },
-                        _ => {}
-                    }
-                } else if has_start_date {
-                    // 只有开始日期的情况
-                    let start_date_fmt = format!("{}T00:00:00Z", start_date_str);
-                    
-                    if let Ok(start) = chrono::DateTime::parse_from_rfc3339(&start_date_fmt) {
-                        let start_utc = start.with_timezone(&chrono::Utc);
-                        
-                        candidate_ids.retain(|&id| {
-                            if let Some(article) = index.articles.get(id) {
-                                article.date >= start_utc
-                            } else {
-                                false
-                            }
-                        });
-                    }
-                } else if has_end_date {
-                    // 只有结束日期的情况
-                    let end_date_fmt = format!("{}T23:59:59Z", end_date_str);
-                    
-                    if let Ok(end) = chrono::DateTime::parse_from_rfc3339(&end_date_fmt) {
-                        let end_utc = end.with_timezone(&chrono::Utc);
-                        
-                        candidate_ids.retain(|&id| {
-                            if let Some(article) = index.articles.get(id) {
-                                article.date <= end_utc
-                            } else {
-                                false
-                            }
-                        });
-                    }
+                // 无法解析的日期已在参数检查中给出警告，这里直接忽略
+                let start_date = has_start_date.then(|| parse_date_bound(start_date_str, false)).flatten();
+                let end_date = has_end_date.then(|| parse_date_bound(end_date_str, true)).flatten();
+                
+                if start_date.is_some() || end_date.is_some() {
+                    candidate_ids.retain(|&id| {
+                        index.articles.get(id).is_some_and(|article| {
+                            start_date.is_none_or(|start| article.date >= start)
+                                && end_date.is_none_or(|end| article.date <= end)
+                        })
+                    });
                 }
             }
         }
         
+        // 关键词筛选 - 摘要和标签需要读取文章表，放在其他筛选条件之后，只读取剩下的候选文章
+        if let Some(q) = params.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+            let q = q.to_lowercase();
+            candidate_ids.retain(|&id| {
+                index.articles.get(id).is_some_and(|article| {
+                    article.title.to_lowercase().contains(&q)
+                        || index.record(id).is_some_and(|record| {
+                            record.summary().to_lowercase().contains(&q)
+                                || record.tags().any(|tag| tag.to_lowercase().contains(&q))
+                        })
+                })
+            });
+        }
+        
         Ok(candidate_ids.into_iter().collect())
     }
     
@@ -307,54 +1704,291 @@ impl ArticleFilter {
         result
     }
     
-    // 应用排序
-    fn apply_sorting(articles: &mut [ArticleMetadata], params: &FilterParams) {
-        match params.sort.as_deref() {
-            Some("oldest") => {
-                articles.sort_by(|a, b| a.date.cmp(&b.date));
-            }
-            Some("title_asc") => {
-                articles.sort_by(|a, b| a.title.cmp(&b.title));
-            }
-            Some("title_desc") => {
-                articles.sort_by(|a, b| b.title.cmp(&a.title));
-            }
-            _ => {
-                // 默认按最新排序
-                articles.sort_by(|a, b| b.date.cmp(&a.date));
+    // 按标签筛选，只保留带有全部标签的文章
+    fn filter_by_all_tags(index: &ArticleIndex, tags: &[String]) -> HashSet<usize> {
+        let mut result: Option<HashSet<usize>> = None;
+        
+        for tag in tags {
+            let article_ids = index.tag_index.get(tag).map(Vec::as_slice).unwrap_or_default();
+            match &mut result {
+                Some(result) => result.retain(|id| article_ids.binary_search(id).is_ok()),
+                None => result = Some(article_ids.iter().copied().collect()),
             }
         }
+        
+        result.unwrap_or_default()
+    }
+    
+    // 应用排序
+    fn apply_sorting(index: &ArticleIndex, article_ids: &mut [usize], order: ListOrder) {
+        let keys = SortKeys {
+            articles: &index.articles,
+            title_ranks: index.title_ranks(order.collation),
+        };
+        article_ids.sort_by(|&a, &b| order.compare(&keys, a, b));
     }
 }
 
 /// 文章过滤器JS接口 - 提供给JavaScript使用的筛选API
+///
+/// 查询函数最后的可选参数 `index` 为 `init_named` 加载的索引名称，未指定时查询默认索引。
 #[wasm_bindgen]
 pub struct ArticleFilterJS;
 
 #[wasm_bindgen]
 impl ArticleFilterJS {
     /// 初始化过滤器并加载索引
+    ///
+    /// `max_visibility` 为最高可见级别（public 或 members），默认只列出公开文章。
     #[wasm_bindgen]
-    pub fn init(index_data: &[u8]) -> Result<(), JsValue> {
+    pub fn init(index_data: &[u8], max_visibility: Option<String>) -> Result<(), JsValue> {
         console_error_panic_hook::set_once();
         
-        let result = ArticleFilter::load_index(index_data)
+        let result = max_visibility.as_deref()
+            .map_or(Ok(Visibility::Public), str::parse)
+            .map_err(FilterError::InvalidParams)
+            .and_then(|max_visibility| ArticleFilter::load_index_with_visibility(index_data, max_visibility))
             .map_err(|e| {
                 console::log_1(&JsValue::from_str(&format!("初始化过滤器失败: {}", e)));
-                JsValue::from_str(&e)
+                JsValue::from(e)
             });
             
         result
     }
     
+    /// 重新加载索引（如站点重新构建后），替换已加载的索引
+    ///
+    /// 参数与 `init` 相同；新数据无法解析时保留原有的索引并返回错误。
+    #[wasm_bindgen]
+    pub fn reload(index_data: &[u8], max_visibility: Option<String>) -> Result<(), JsValue> {
+        max_visibility.as_deref()
+            .map_or(Ok(Visibility::Public), str::parse)
+            .map_err(FilterError::InvalidParams)
+            .and_then(|max_visibility| ArticleFilter::load_index_with_visibility(index_data, max_visibility))
+            .map_err(|e| {
+                console::log_1(&JsValue::from_str(&format!("重新加载索引失败: {}", e)));
+                JsValue::from(e)
+            })
+    }
+    
+    /// 清除已加载的索引并释放内存，之后的查询返回错误，直到再次调用 `init` 或 `reload`
+    ///
+    /// 指定 `name` 时只移除该名称的索引（见 `init_named`），否则清除所有索引。
+    #[wasm_bindgen]
+    pub fn clear(name: Option<String>) -> Result<(), JsValue> {
+        match name {
+            Some(name) => ArticleFilter::remove_index(&name).map(|_| ()),
+            None => ArticleFilter::clear_index(),
+        }
+        .map_err(JsValue::from)
+    }
+    
+    /// 以指定名称加载索引（如 "blog"、"notes"），与其他名称的索引并存，使用 `filter_articles_in` 查询
+    ///
+    /// 同名索引已加载时替换原有的索引。`max_visibility` 与 `init` 相同；
+    /// `init` 加载的索引名称为 "default"。
+    #[wasm_bindgen]
+    pub fn init_named(name: &str, index_data: &[u8], max_visibility: Option<String>) -> Result<(), JsValue> {
+        console_error_panic_hook::set_once();
+        
+        max_visibility.as_deref()
+            .map_or(Ok(Visibility::Public), str::parse)
+            .map_err(FilterError::InvalidParams)
+            .and_then(|max_visibility| ArticleFilter::load_named_index(name, index_data, max_visibility))
+            .map_err(|e| {
+                console::log_1(&JsValue::from_str(&format!("加载索引 {} 失败: {}", name, e)));
+                JsValue::from(e)
+            })
+    }
+    
+    /// 获取已加载的索引名称，按名称排序
+    #[wasm_bindgen]
+    pub fn get_index_names() -> Result<JsValue, JsValue> {
+        let names = ArticleFilter::index_names()?;
+        
+        serde_wasm_bindgen::to_value(&names)
+            .map_err(|e| FilterError::SerializeError(format!("序列化索引名称失败: {}", e)).into())
+    }
+    
+    /// 获取已加载索引的信息（`{ name, article_count, tag_count, created_at, version, max_version }`），
+    /// 未指定名称时为默认索引；没有头部的旧索引 `created_at` 和 `version` 为null
+    #[wasm_bindgen]
+    pub fn get_index_info(name: Option<String>) -> Result<JsValue, JsValue> {
+        let info = ArticleFilter::get_index_info(name.as_deref().unwrap_or(DEFAULT_INDEX))?;
+        
+        serde_wasm_bindgen::to_value(&info)
+            .map_err(|e| FilterError::SerializeError(format!("序列化索引信息失败: {}", e)).into())
+    }
+    
+    /// 初始化过滤器并加载多个索引，合并为一个索引查询
+    ///
+    /// `index_data` 为 `Uint8Array` 数组，每项是一个筛选索引文件的内容；
+    /// `max_visibility` 与 `init` 相同。
+    #[wasm_bindgen]
+    pub fn init_multi(index_data: js_sys::Array, max_visibility: Option<String>) -> Result<(), JsValue> {
+        console_error_panic_hook::set_once();
+        
+        let data: Vec<Vec<u8>> = index_data.iter()
+            .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec())
+            .collect();
+        let slices: Vec<&[u8]> = data.iter().map(Vec::as_slice).collect();
+        
+        max_visibility.as_deref()
+            .map_or(Ok(Visibility::Public), str::parse)
+            .map_err(FilterError::InvalidParams)
+            .and_then(|max_visibility| ArticleFilter::load_indexes_with_visibility(&slices, max_visibility))
+            .map_err(|e| {
+                console::log_1(&JsValue::from_str(&format!("初始化过滤器失败: {}", e)));
+                JsValue::from(e)
+            })
+    }
+    
+    /// 读取索引文件头部的索引元数据，不解压索引；没有头部的旧索引返回 `null`
+    #[wasm_bindgen]
+    pub fn get_index_metadata(index_data: &[u8]) -> Result<JsValue, JsValue> {
+        let metadata = utils::read_index_metadata(index_data, MAX_INDEX_VERSION)
+            .map_err(|e| FilterError::ParseError(format!("读取索引元数据失败: {}", e)))?;
+        
+        serde_wasm_bindgen::to_value(&metadata)
+            .map_err(|e| FilterError::SerializeError(format!("序列化索引元数据失败: {}", e)).into())
+    }
+    
     /// 获取所有标签
     #[wasm_bindgen]
-    pub fn get_all_tags() -> Result<JsValue, JsValue> {
-        let tags = ArticleFilter::get_all_tags()
-            .map_err(|e| JsValue::from_str(&e))?;
+    pub fn get_all_tags(index: Option<String>) -> Result<JsValue, JsValue> {
+        let tags = ArticleFilter::get_all_tags_in(index.as_deref().unwrap_or(DEFAULT_INDEX))?;
+        
+        serde_wasm_bindgen::to_value(&tags)
+            .map_err(|e| FilterError::SerializeError(format!("序列化标签失败: {}", e)).into())
+    }
+    
+    /// 获取所有标签及其文章数量（`{ tag, count }` 列表），按数量降序，用于绘制标签云
+    #[wasm_bindgen]
+    pub fn get_tags_with_counts(index: Option<String>) -> Result<JsValue, JsValue> {
+        let tags = ArticleFilter::get_tags_with_counts_in(index.as_deref().unwrap_or(DEFAULT_INDEX))?;
         
         serde_wasm_bindgen::to_value(&tags)
-            .map_err(|e| JsValue::from_str(&format!("序列化标签失败: {}", e)))
+            .map_err(|e| FilterError::SerializeError(format!("序列化标签失败: {}", e)).into())
+    }
+    
+    /// 获取所有系列（`[{ name, count, articles }]`），最近有文章发布的系列在前，
+    /// 系列中的文章按阅读顺序排列，用于系列列表和系列内的上一篇/下一篇导航
+    #[wasm_bindgen]
+    pub fn get_series(index: Option<String>) -> Result<JsValue, JsValue> {
+        let series = ArticleFilter::get_series_in(index.as_deref().unwrap_or(DEFAULT_INDEX))?;
+        
+        serde_wasm_bindgen::to_value(&series)
+            .map_err(|e| FilterError::SerializeError(format!("序列化系列失败: {}", e)).into())
+    }
+    
+    /// 获取所有作者及其文章数量（`{ name, count }` 列表），按数量降序
+    #[wasm_bindgen]
+    pub fn get_authors(index: Option<String>) -> Result<JsValue, JsValue> {
+        let authors = ArticleFilter::get_authors_in(index.as_deref().unwrap_or(DEFAULT_INDEX))?;
+        
+        serde_wasm_bindgen::to_value(&authors)
+            .map_err(|e| FilterError::SerializeError(format!("序列化作者失败: {}", e)).into())
+    }
+    
+    /// 获取所有筛选预设
+    #[wasm_bindgen]
+    pub fn get_presets(index: Option<String>) -> Result<JsValue, JsValue> {
+        let presets = ArticleFilter::get_presets_in(index.as_deref().unwrap_or(DEFAULT_INDEX))?;
+        
+        serde_wasm_bindgen::to_value(&presets)
+            .map_err(|e| FilterError::SerializeError(format!("序列化筛选预设失败: {}", e)).into())
+    }
+    
+    /// 按预设筛选文章
+    #[wasm_bindgen]
+    pub fn filter_by_preset(name: &str, page: usize, index: Option<String>) -> Result<JsValue, JsValue> {
+        let result = ArticleFilter::filter_by_preset_in(index.as_deref().unwrap_or(DEFAULT_INDEX), name, page)?;
+        
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| FilterError::SerializeError(format!("序列化结果失败: {}", e)).into())
+    }
+    
+    /// 获取标签关系图：节点为标签及文章数量，边为 `[a, b, count]` 形式的共同出现次数
+    #[wasm_bindgen]
+    pub fn get_tag_graph(min_cooccurrence: usize, index: Option<String>) -> Result<JsValue, JsValue> {
+        let graph = ArticleFilter::get_tag_graph_in(index.as_deref().unwrap_or(DEFAULT_INDEX), min_cooccurrence)?;
+        
+        serde_wasm_bindgen::to_value(&graph)
+            .map_err(|e| FilterError::SerializeError(format!("序列化标签关系图失败: {}", e)).into())
+    }
+    
+    /// 获取指定年份的归档日历（每天的发布数量和文章ID）
+    #[wasm_bindgen]
+    pub fn get_calendar(year: i32, index: Option<String>) -> Result<JsValue, JsValue> {
+        let calendar = ArticleFilter::get_calendar_in(index.as_deref().unwrap_or(DEFAULT_INDEX), year)?;
+        
+        serde_wasm_bindgen::to_value(&calendar)
+            .map_err(|e| FilterError::SerializeError(format!("序列化归档日历失败: {}", e)).into())
+    }
+    
+    /// 获取按 `granularity`（day、week 或 month）统计的发布数量（`[{ key, count }]`），按时间升序，
+    /// 只包含有文章发布的时间段
+    #[wasm_bindgen]
+    pub fn get_post_histogram(granularity: &str, index: Option<String>) -> Result<JsValue, JsValue> {
+        let histogram = ArticleFilter::get_post_histogram_in(index.as_deref().unwrap_or(DEFAULT_INDEX), granularity)?;
+        
+        serde_wasm_bindgen::to_value(&histogram)
+            .map_err(|e| FilterError::SerializeError(format!("序列化发布统计失败: {}", e)).into())
+    }
+    
+    /// 获取文章按发布时间的上一篇和下一篇（`{ previous, next }`，没有时为null），
+    /// 指定 `within_tag` 时只在带有该标签的文章（如同一系列）中查找
+    #[wasm_bindgen]
+    pub fn get_adjacent(article_id: &str, within_tag: Option<String>, index: Option<String>) -> Result<JsValue, JsValue> {
+        let adjacent = ArticleFilter::get_adjacent_in(index.as_deref().unwrap_or(DEFAULT_INDEX), article_id, within_tag.as_deref())?;
+        
+        serde_wasm_bindgen::to_value(&adjacent)
+            .map_err(|e| FilterError::SerializeError(format!("序列化相邻文章失败: {}", e)).into())
+    }
+    
+    /// 获取相关文章：与文章有共同标签的其他文章（`[{ article, shared_tags, score }]`），
+    /// 按共同标签数和发布时间的接近程度排序，最多返回 `limit` 篇
+    #[wasm_bindgen]
+    pub fn get_related(article_id: &str, limit: usize, index: Option<String>) -> Result<JsValue, JsValue> {
+        let related = ArticleFilter::get_related_in(index.as_deref().unwrap_or(DEFAULT_INDEX), article_id, limit)?;
+        
+        serde_wasm_bindgen::to_value(&related)
+            .map_err(|e| FilterError::SerializeError(format!("序列化相关文章失败: {}", e)).into())
+    }
+    
+    /// 从符合筛选条件（`FilterParams` 的JSON字符串）的文章中随机选取一篇，没有时返回null
+    ///
+    /// 排序和分页参数不影响结果。指定 `seed` 时对同一索引和同一参数总是返回同一篇文章。
+    #[wasm_bindgen]
+    pub fn get_random(params_json: &str, seed: Option<u32>, index: Option<String>) -> Result<JsValue, JsValue> {
+        let params: FilterParams = serde_json::from_str(params_json)
+            .map_err(|e| FilterError::ParseError(format!("解析参数失败: {}", e)))?;
+        
+        let article = ArticleFilter::get_random_in(index.as_deref().unwrap_or(DEFAULT_INDEX), &params, seed.map(u64::from))?;
+        
+        serde_wasm_bindgen::to_value(&article)
+            .map_err(|e| FilterError::SerializeError(format!("序列化随机文章失败: {}", e)).into())
+    }
+    
+    /// 设置界面语言（zh 或 en，也接受 zh-CN、en-US 等语言代码），影响归档标签
+    #[wasm_bindgen]
+    pub fn set_locale(locale: &str) -> Result<(), JsValue> {
+        locale.parse()
+            .map_err(FilterError::InvalidParams)
+            .and_then(ArticleFilter::set_locale)
+            .map_err(JsValue::from)
+    }
+    
+    /// 获取年月归档：每年和每月的文章数量及ISO键（YYYY、YYYY-MM），按时间降序
+    ///
+    /// `localized` 为true时附带按 `set_locale` 设置的语言格式化的标签，
+    /// 如 "2024年3月" 或 "March 2024"。
+    #[wasm_bindgen]
+    pub fn get_archive(localized: Option<bool>, index: Option<String>) -> Result<JsValue, JsValue> {
+        let archive = ArticleFilter::get_archive_in(index.as_deref().unwrap_or(DEFAULT_INDEX), localized.unwrap_or(false))?;
+        
+        serde_wasm_bindgen::to_value(&archive)
+            .map_err(|e| FilterError::SerializeError(format!("序列化归档失败: {}", e)).into())
     }
     
     /// 筛选文章
@@ -362,14 +1996,25 @@ impl ArticleFilterJS {
     pub fn filter_articles(params_json: &str) -> Result<JsValue, JsValue> {
         // 解析参数
         let params: FilterParams = serde_json::from_str(params_json)
-            .map_err(|e| JsValue::from_str(&format!("解析参数失败: {}", e)))?;
+            .map_err(|e| FilterError::ParseError(format!("解析参数失败: {}", e)))?;
         
         // 筛选文章
-        let result = ArticleFilter::filter_articles(&params)
-            .map_err(|e| JsValue::from_str(&e))?;
+        let result = ArticleFilter::filter_articles(&params)?;
         
         // 序列化结果
         serde_wasm_bindgen::to_value(&result)
-            .map_err(|e| JsValue::from_str(&format!("序列化结果失败: {}", e)))
+            .map_err(|e| FilterError::SerializeError(format!("序列化结果失败: {}", e)).into())
+    }
+    
+    /// 在 `init_named` 加载的指定名称的索引中筛选文章，参数与 `filter_articles` 相同
+    #[wasm_bindgen]
+    pub fn filter_articles_in(name: &str, params_json: &str) -> Result<JsValue, JsValue> {
+        let params: FilterParams = serde_json::from_str(params_json)
+            .map_err(|e| FilterError::ParseError(format!("解析参数失败: {}", e)))?;
+        
+        let result = ArticleFilter::filter_articles_in(name, &params)?;
+        
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| FilterError::SerializeError(format!("序列化结果失败: {}", e)).into())
     }
 }
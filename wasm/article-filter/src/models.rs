@@ -1,27 +1,144 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use utils_common::models::ArticleMetadata;
+use std::io;
+use utils_common::arena::ArticleTable;
+use utils_common::models::{ArticleMetadata, Visibility};
 
-/// 筛选索引 - 存储标签和日期索引
+/// 索引中的文章 - 排序和筛选使用的字段，加载索引时全部解码
+///
+/// 摘要、标签等只在展示时需要的字段存放在紧凑文章表中，加载索引时
+/// 不为每篇文章分配字符串，只在返回当前页的文章时读取。
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IndexedArticle {
+    /// 文章唯一标识符
+    pub id: String,
+    /// 文章标题
+    pub title: String,
+    /// 发布日期
+    pub date: DateTime<Utc>,
+    /// 文章可见性
+    pub visibility: Visibility,
+    /// 是否为置顶文章
+    pub featured: bool,
+    /// 正文词数
+    pub word_count: usize,
+    /// 文章在文章表中的位置
+    pub record: u32,
+}
+
+/// 筛选索引 - 存储文章的排序和筛选键、按需读取的文章表，以及标签和日期索引
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FilterIndex {
-    /// 所有文章的元数据列表
-    pub articles: Vec<ArticleMetadata>,
+    /// 所有文章的排序和筛选键
+    pub articles: Vec<IndexedArticle>,
+    /// 所有文章的展示字段，不包含正文、标题结构和媒体说明
+    pub records: ArticleTable,
     /// 标签到文章ID列表的映射
     pub tag_index: HashMap<String, HashSet<usize>>,
     /// 年份到文章ID列表的映射
     pub year_index: HashMap<i32, HashSet<usize>>,
     /// 月份到文章ID列表的映射（格式：yyyy-mm）
     pub month_index: HashMap<String, HashSet<usize>>,
+    /// 系列名称到文章ID列表的映射
+    pub series_index: HashMap<String, HashSet<usize>>,
+    /// 作者到文章ID列表的映射
+    pub author_index: HashMap<String, HashSet<usize>>,
+    /// 站点配置中定义的筛选预设
+    pub presets: Vec<FilterPreset>,
+}
+
+impl FilterIndex {
+    /// 检查文章表的各区间和每篇文章在文章表中的位置，加载索引时调用
+    pub fn validate(&self) -> Result<(), io::Error> {
+        self.records.validate()?;
+        if let Some(article) = self.articles.iter().find(|article| article.record as usize >= self.records.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("文章 {} 在文章表中的位置超出范围", article.id),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 合并多个筛选索引（如多栏目站点中每个栏目单独生成的索引）
+    ///
+    /// 后面索引中的文章位置依次顺延，标签、日期、系列和作者索引随之改写。ID相同的文章只保留
+    /// 第一次出现的一篇及其记录，后面的重复文章连同其记录和在各索引中的条目一起丢弃，
+    /// 保证索引条目与保留的文章记录一致；同名预设同样只保留第一个。
+    pub fn merge(indexes: Vec<FilterIndex>) -> FilterIndex {
+        let mut merged = FilterIndex {
+            articles: Vec::new(),
+            records: ArticleTable::default(),
+            tag_index: HashMap::new(),
+            year_index: HashMap::new(),
+            month_index: HashMap::new(),
+            series_index: HashMap::new(),
+            author_index: HashMap::new(),
+            presets: Vec::new(),
+        };
+        let mut kept_ids: HashSet<String> = HashSet::new();
+        // 保留的文章的展示字段，合并完成后重新生成文章表
+        let mut records: Vec<ArticleMetadata> = Vec::new();
+        
+        for index in indexes {
+            // 本索引中的文章位置到合并后位置的映射，重复的文章和不在文章表中的文章为None
+            // 只复制保留的文章的记录，文章在文章表中的位置改为合并后的位置
+            let positions: Vec<Option<usize>> = index.articles.into_iter()
+                .map(|mut article| {
+                    let record = index.records.get(article.record as usize)?;
+                    if !kept_ids.insert(article.id.clone()) {
+                        return None;
+                    }
+                    article.record = records.len() as u32;
+                    records.push(record.to_metadata());
+                    merged.articles.push(article);
+                    Some(merged.articles.len() - 1)
+                })
+                .collect();
+            let remap = |article_ids: HashSet<usize>| -> HashSet<usize> {
+                article_ids.into_iter()
+                    .filter_map(|article_id| positions.get(article_id).copied().flatten())
+                    .collect()
+            };
+            
+            for (tag, article_ids) in index.tag_index {
+                merged.tag_index.entry(tag).or_default().extend(remap(article_ids));
+            }
+            for (year, article_ids) in index.year_index {
+                merged.year_index.entry(year).or_default().extend(remap(article_ids));
+            }
+            for (month, article_ids) in index.month_index {
+                merged.month_index.entry(month).or_default().extend(remap(article_ids));
+            }
+            for (series, article_ids) in index.series_index {
+                merged.series_index.entry(series).or_default().extend(remap(article_ids));
+            }
+            for (author, article_ids) in index.author_index {
+                merged.author_index.entry(author).or_default().extend(remap(article_ids));
+            }
+            for preset in index.presets {
+                if !merged.presets.iter().any(|existing| existing.name == preset.name) {
+                    merged.presets.push(preset);
+                }
+            }
+        }
+        
+        merged.records = ArticleTable::from_articles(&records);
+        merged
+    }
 }
 
 /// 筛选规则 - 定义筛选条件
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
 pub struct FilterRules {
     /// 需要包含的标签列表
     pub tags: Vec<String>,
-    /// 排序方式: date_desc, date_asc, title_asc, title_desc
+    /// 排序方式: date_desc, date_asc, title_asc, title_desc，或筛选参数中的其他排序方式
     pub sort_by: String,
+    /// 标签匹配方式: any（带有任一标签，默认）或 all（带有全部标签）
+    pub tag_mode: Option<String>,
 }
 
 impl Default for FilterRules {
@@ -29,6 +146,42 @@ impl Default for FilterRules {
         Self {
             tags: Vec::new(),
             sort_by: "date_desc".to_string(),
+            tag_mode: None,
         }
     }
-} 
\ No newline at end of file
+} 
+
+impl FilterRules {
+    /// 转换为筛选参数使用的排序方式
+    pub fn sort_param(&self) -> &str {
+        match self.sort_by.as_str() {
+            "date_desc" => "newest",
+            "date_asc" => "oldest",
+            other => other,
+        }
+    }
+
+    /// 检查排序方式和标签匹配方式，未知的取值返回错误，供构建索引时检查站点配置
+    pub fn validate(&self) -> Result<(), String> {
+        if crate::SortOrder::parse(self.sort_param()).is_none() {
+            return Err(format!(
+                "无效的排序方式: {}，应为 date_desc、date_asc、title_asc、title_desc、reading_time_asc 或 reading_time_desc",
+                self.sort_by,
+            ));
+        }
+        if let Some(mode) = self.tag_mode.as_deref().filter(|mode| !crate::is_tag_mode(mode)) {
+            return Err(format!("无效的标签匹配方式: {}，应为 any 或 all", mode));
+        }
+        Ok(())
+    }
+}
+
+/// 筛选预设 - 由站点配置定义的命名筛选视图（如"教程" = 标签[tutorial, guide]，按最新排序）
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FilterPreset {
+    /// 预设名称
+    pub name: String,
+    /// 预设的筛选规则
+    #[serde(default)]
+    pub rules: FilterRules,
+}
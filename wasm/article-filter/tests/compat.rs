@@ -0,0 +1,210 @@
+//! 筛选索引格式兼容性测试 - 解码检入的筛选索引样本，确保修改模型或压缩格式后
+//! 已部署的索引文件仍能读取。
+//!
+//! 样本文件与 utils-common 的兼容性测试共用 `utils-common/tests/fixtures`。支持的格式版本为
+//! `MIN_INDEX_VERSION` 到 `MAX_INDEX_VERSION`，更早版本的索引加载时返回要求重新生成索引的
+//! 错误。修改索引结构时需要提升写入的格式版本，并设置环境变量 `UPDATE_GOLDEN=1` 运行测试
+//! 重新生成样本文件；新版本不保留旧版本的解码方式时，同时提升 `MIN_INDEX_VERSION`。
+
+use std::path::{Path, PathBuf};
+use article_filter::builder::FilterBuilder;
+use article_filter::{ArticleFilter, ArticleIndex, FilterParams, INDEX_VERSION, MAX_INDEX_VERSION, MIN_INDEX_VERSION};
+use utils_common::compression::{read_format, read_index_metadata, to_compressed_with_format, validate_compressed_data_with_max_version, SerializationFormat};
+use article_filter::models::FilterIndex;
+use serde_json::Value;
+use utils_common::models::{ArticleMetadata, IndexType};
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../utils-common/tests/fixtures")
+}
+
+fn fixture_articles() -> Vec<ArticleMetadata> {
+    let articles = std::fs::read_to_string(fixtures_dir().join("articles.json")).unwrap();
+    serde_json::from_str(&articles).unwrap()
+}
+
+fn fixture_path(version: u8) -> PathBuf {
+    fixtures_dir().join(format!("filter-v{}.bin", version))
+}
+
+/// 读取当前版本的筛选索引样本，设置 `UPDATE_GOLDEN` 时先用样本文章重新生成
+fn filter_fixture() -> Vec<u8> {
+    let path = fixture_path(MAX_INDEX_VERSION);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let mut builder = FilterBuilder::new();
+        for article in fixture_articles() {
+            builder.add_article(article);
+        }
+        builder.save_filter_index(path.to_str().unwrap()).unwrap();
+    }
+    std::fs::read(&path).unwrap_or_else(|e| panic!("无法读取样本文件 {}: {}", path.display(), e))
+}
+
+#[test]
+fn decodes_filter_index_v10() {
+    let data = filter_fixture();
+
+    assert_eq!(validate_compressed_data_with_max_version(&data, 10).unwrap(), [10, 0]);
+    let metadata = read_index_metadata(&data, 10).unwrap().expect("缺少索引元数据头部");
+    assert_eq!(metadata.index_type, IndexType::Filter);
+    assert_eq!(metadata.article_count, 3);
+
+    ArticleFilter::load_index(&data).unwrap();
+
+    let mut tags = ArticleFilter::get_all_tags().unwrap();
+    tags.sort();
+    assert_eq!(tags, ["docker", "rust", "wasm", "教程"]);
+
+    let params = FilterParams {
+        tags: Some(vec!["rust".to_string()]),
+        sort: Some("newest".to_string()),
+        ..FilterParams::default()
+    };
+    let result = ArticleFilter::filter_articles(&params).unwrap();
+    let ids: Vec<&str> = result.articles.iter().map(|article| article.id.as_str()).collect();
+    assert_eq!(ids, ["articles/wasm", "articles/rust"]);
+    assert_eq!(result.total, 2);
+    // 展示字段从文章表中读取
+    let wasm = &result.articles[0];
+    assert_eq!(wasm.url.as_deref(), Some("/articles/wasm"));
+    assert_eq!(wasm.summary.as_deref(), Some("使用 wasm-bindgen 构建浏览器模块。"));
+    assert_eq!(wasm.tags.as_deref(), Some(&["rust".to_string(), "wasm".to_string()][..]));
+    assert_eq!(wasm.author.as_deref(), Some("shuyu"));
+
+    let series = ArticleFilter::get_series().unwrap();
+    assert_eq!(series.len(), 1);
+    assert_eq!(series[0].name, "Rust 实践");
+    let ids: Vec<&str> = series[0].articles.iter().map(|article| article.id.as_str()).collect();
+    assert_eq!(ids, ["articles/rust", "articles/wasm"]);
+
+    // 按系列筛选时默认按阅读顺序排列
+    let params = FilterParams {
+        series: Some("Rust 实践".to_string()),
+        ..FilterParams::default()
+    };
+    let result = ArticleFilter::filter_articles(&params).unwrap();
+    let ids: Vec<&str> = result.articles.iter().map(|article| article.id.as_str()).collect();
+    assert_eq!(ids, ["articles/rust", "articles/wasm"]);
+
+    let authors: Vec<(String, usize)> = ArticleFilter::get_authors().unwrap()
+        .into_iter()
+        .map(|author| (author.name, author.count))
+        .collect();
+    assert_eq!(authors, [("shuyu".to_string(), 2), ("lin".to_string(), 1)]);
+
+    let params = FilterParams {
+        authors: Some(vec!["lin".to_string()]),
+        ..FilterParams::default()
+    };
+    let result = ArticleFilter::filter_articles(&params).unwrap();
+    let ids: Vec<&str> = result.articles.iter().map(|article| article.id.as_str()).collect();
+    assert_eq!(ids, ["articles/docker"]);
+
+    // 词数由筛选索引构建器根据内容计算
+    let params = FilterParams {
+        sort: Some("reading_time_asc".to_string()),
+        featured_first: Some(false),
+        ..FilterParams::default()
+    };
+    let result = ArticleFilter::filter_articles(&params).unwrap();
+    let ids: Vec<&str> = result.articles.iter().map(|article| article.id.as_str()).collect();
+    assert_eq!(ids, ["articles/docker", "articles/rust", "articles/wasm"]);
+}
+
+#[test]
+fn rejects_versions_below_minimum() {
+    let mut data = filter_fixture();
+    // 版本号紧跟在魔数之后
+    data[utils_common::compression::MAGIC_BYTES.len()] = MIN_INDEX_VERSION - 1;
+
+    let error = ArticleIndex::from_compressed(&data).unwrap_err();
+    assert!(error.to_string().contains("请重新生成索引"), "{}", error);
+}
+
+#[test]
+fn rejects_articles_outside_article_table() {
+    let mut builder = FilterBuilder::new();
+    for article in fixture_articles() {
+        builder.add_article(article);
+    }
+    let mut index = builder.build_filter_index().unwrap();
+    index.validate().unwrap();
+
+    index.articles[0].record = index.records.len() as u32;
+    assert!(index.validate().is_err());
+    let data = to_compressed_with_format(&index, INDEX_VERSION, None::<&()>, SerializationFormat::default()).unwrap();
+    assert!(ArticleIndex::from_compressed(&data).is_err());
+}
+
+#[test]
+fn fixtures_cover_supported_versions() {
+    filter_fixture();
+    let mut versions: Vec<u8> = std::fs::read_dir(fixtures_dir()).unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix("filter-v")?.strip_suffix(".bin")?.parse().ok()
+        })
+        .collect();
+    versions.sort_unstable();
+    let supported: Vec<u8> = (MIN_INDEX_VERSION..=MAX_INDEX_VERSION).collect();
+    assert_eq!(versions, supported, "样本版本与支持的版本范围不一致，新增缺少的样本或删除过旧的样本");
+
+    for version in supported {
+        let data = std::fs::read(fixture_path(version)).unwrap();
+        ArticleIndex::from_compressed(&data).unwrap_or_else(|e| panic!("无法解码 v{} 样本: {}", version, e));
+    }
+}
+
+/// 转换为与集合迭代顺序无关的 JSON 值，便于比较两个索引的内容
+fn normalized(index: &FilterIndex) -> Value {
+    fn sort_arrays(value: &mut Value) {
+        match value {
+            Value::Array(items) => {
+                items.iter_mut().for_each(sort_arrays);
+                items.sort_by_key(|item| item.to_string());
+            }
+            Value::Object(fields) => fields.values_mut().for_each(sort_arrays),
+            _ => {}
+        }
+    }
+    let mut value = serde_json::to_value(index).unwrap();
+    sort_arrays(&mut value);
+    value
+}
+
+#[test]
+fn current_layout_matches_current_version_fixture() {
+    let data = filter_fixture();
+    let decoded: FilterIndex = utils_common::compression::from_compressed_with_max_version(&data, MAX_INDEX_VERSION).unwrap();
+
+    let mut builder = FilterBuilder::new();
+    for article in fixture_articles() {
+        builder.add_article(article);
+    }
+    let built = builder.build_filter_index().unwrap();
+
+    // 修改索引结构后旧样本仍能解码时，解码结果会与新构建的索引不同
+    assert_eq!(
+        normalized(&decoded), normalized(&built),
+        "筛选索引结构已变化，请提升 INDEX_VERSION 并设置 UPDATE_GOLDEN=1 生成新版本的样本",
+    );
+}
+
+#[test]
+fn round_trips_through_messagepack() {
+    let mut builder = FilterBuilder::new();
+    for article in fixture_articles() {
+        builder.add_article(article);
+    }
+    let built = builder.build_filter_index().unwrap();
+    builder.set_format(SerializationFormat::MessagePack);
+    let path = std::env::temp_dir().join(format!("filter-msgpack-{}.bin", std::process::id()));
+    builder.save_filter_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(read_format(&data, MAX_INDEX_VERSION).unwrap(), SerializationFormat::MessagePack);
+    let decoded: FilterIndex = utils_common::compression::from_compressed_with_max_version(&data, MAX_INDEX_VERSION).unwrap();
+    assert_eq!(normalized(&decoded), normalized(&built));
+    ArticleIndex::from_compressed(&data).unwrap();
+}
@@ -0,0 +1,105 @@
+//! 并发读取压力测试 - 多个线程同时调用筛选接口（模拟归档页、侧边栏、页脚等组件同时查询），
+//! 检查全局索引的读取互不干扰，每次调用都得到与单线程相同的结果。
+//!
+//! 使用 `utils-common/tests/fixtures` 中的筛选索引样本。
+
+use std::path::Path;
+use std::sync::{Arc, Barrier};
+use std::thread;
+use article_filter::{ArticleFilter, FilterParams, Locale, MAX_INDEX_VERSION};
+
+/// 并发线程数
+const THREADS: usize = 8;
+
+/// 每个线程的调用轮数
+const ROUNDS: usize = 200;
+
+fn load_fixture() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("../utils-common/tests/fixtures/filter-v{}.bin", MAX_INDEX_VERSION));
+    let data = std::fs::read(&path).unwrap_or_else(|e| panic!("无法读取样本文件 {}: {}", path.display(), e));
+    ArticleFilter::load_index(&data).unwrap();
+}
+
+/// 一组代表性的筛选参数
+fn params() -> Vec<FilterParams> {
+    vec![
+        FilterParams::default(),
+        FilterParams {
+            tags: Some(vec!["rust".to_string()]),
+            sort: Some("oldest".to_string()),
+            ..FilterParams::default()
+        },
+        FilterParams {
+            year: Some(2024),
+            limit: Some(1),
+            page: Some(2),
+            ..FilterParams::default()
+        },
+        FilterParams {
+            month: Some("2023-11".to_string()),
+            ..FilterParams::default()
+        },
+        FilterParams {
+            date: Some("2024-01-01,2024-02-01".to_string()),
+            sort: Some("title_asc".to_string()),
+            ..FilterParams::default()
+        },
+    ]
+}
+
+/// 一轮只读调用的结果，序列化后便于比较
+fn snapshot() -> Vec<String> {
+    let mut results: Vec<String> = params().iter()
+        .map(|params| {
+            let result = ArticleFilter::filter_articles(params).unwrap();
+            let ids: Vec<&str> = result.articles.iter().map(|article| article.id.as_str()).collect();
+            format!("{:?} total={} page={}/{}", ids, result.total, result.page, result.total_pages)
+        })
+        .collect();
+
+    let mut tags = ArticleFilter::get_all_tags().unwrap();
+    tags.sort();
+    results.push(format!("{:?}", tags));
+    results.push(serde_json::to_string(&ArticleFilter::get_archive(false).unwrap()).unwrap());
+    results.push(format!("{:?}", ArticleFilter::get_tag_graph(1).unwrap()));
+    results.push(format!("{}", ArticleFilter::get_calendar(2024).unwrap().total));
+    results
+}
+
+#[test]
+fn concurrent_reads_return_consistent_results() {
+    load_fixture();
+    let expected = Arc::new(snapshot());
+    let localized = [Locale::Zh, Locale::En].map(|locale| {
+        ArticleFilter::set_locale(locale).unwrap();
+        serde_json::to_string(&ArticleFilter::get_archive(true).unwrap()).unwrap()
+    });
+    let localized = Arc::new(localized);
+
+    // 所有线程同时开始，读取线程之间交替切换界面语言
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread_index| {
+            let expected = Arc::clone(&expected);
+            let localized = Arc::clone(&localized);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                for round in 0..ROUNDS {
+                    assert_eq!(snapshot(), *expected, "线程 {} 第 {} 轮结果不一致", thread_index, round);
+
+                    if thread_index == 0 {
+                        let locale = if round % 2 == 0 { Locale::En } else { Locale::Zh };
+                        ArticleFilter::set_locale(locale).unwrap();
+                    }
+                    let archive = serde_json::to_string(&ArticleFilter::get_archive(true).unwrap()).unwrap();
+                    assert!(localized.contains(&archive), "线程 {} 第 {} 轮归档标签异常: {}", thread_index, round, archive);
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().expect("读取线程异常退出");
+    }
+}
@@ -0,0 +1,244 @@
+//! 筛选参数检查测试 - 过大的每页条数返回错误，超出范围的页码、未知的排序方式和排序规则
+//! 替换为默认值并在结果中附带警告；排序键相同的文章按次要排序方式和文章ID排序，顺序稳定；
+//! 标题按指定的排序规则排序。
+//!
+//! 使用 `utils-common/tests/fixtures` 中的筛选索引样本和样本文章。
+
+use std::path::{Path, PathBuf};
+use article_filter::builder::FilterBuilder;
+use article_filter::models::{FilterPreset, FilterRules};
+use article_filter::{ArticleFilter, FilterErrorCode, FilterParams, MAX_INDEX_VERSION, MAX_PAGE_LIMIT};
+use utils_common::models::{ArticleMetadata, Visibility};
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../utils-common/tests/fixtures")
+}
+
+/// 用修改后的样本文章构建筛选索引
+fn build_index(name: &str, edit: impl Fn(usize, &mut ArticleMetadata)) -> Vec<u8> {
+    let articles = std::fs::read_to_string(fixtures_dir().join("articles.json")).unwrap();
+    let articles: Vec<ArticleMetadata> = serde_json::from_str(&articles).unwrap();
+    let mut builder = FilterBuilder::new();
+    for (i, mut article) in articles.into_iter().enumerate() {
+        edit(i, &mut article);
+        builder.add_article(article);
+    }
+
+    let path = std::env::temp_dir().join(format!("article-filter-{}-{}.bin", name, std::process::id()));
+    builder.save_filter_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    data
+}
+
+/// 逐页取出所有文章ID，每页一篇，使用游标翻页
+fn ids_by_cursor(name: &str, sort: &str, sort_secondary: Option<&str>, collation: Option<&str>) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let result = ArticleFilter::filter_articles_in(name, &FilterParams {
+            sort: Some(sort.to_string()),
+            sort_secondary: sort_secondary.map(str::to_string),
+            collation: collation.map(str::to_string),
+            limit: Some(1),
+            cursor: cursor.take(),
+            ..Default::default()
+        }).unwrap();
+        ids.extend(result.articles.into_iter().map(|article| article.id));
+        match result.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => return ids,
+        }
+    }
+}
+
+#[test]
+fn validates_and_clamps_params() {
+    ArticleFilter::load_index(&std::fs::read(fixtures_dir().join(format!("filter-v{}.bin", MAX_INDEX_VERSION))).unwrap()).unwrap();
+
+    // 每页条数上限以内正常返回
+    let result = ArticleFilter::filter_articles(&FilterParams {
+        limit: Some(MAX_PAGE_LIMIT),
+        ..Default::default()
+    }).unwrap();
+    assert_eq!(result.total, 3);
+    assert!(result.warnings.is_empty());
+
+    // 超过上限时返回参数无效错误
+    let error = ArticleFilter::filter_articles(&FilterParams {
+        limit: Some(MAX_PAGE_LIMIT + 1),
+        ..Default::default()
+    }).unwrap_err();
+    assert_eq!(error.code(), FilterErrorCode::InvalidParams);
+
+    // 超出总页数的页码使用最后一页
+    let result = ArticleFilter::filter_articles(&FilterParams {
+        page: Some(5),
+        limit: Some(2),
+        ..Default::default()
+    }).unwrap();
+    assert_eq!((result.page, result.total_pages, result.articles.len()), (2, 2, 1));
+    assert_eq!(result.warnings.len(), 1);
+
+    // 未知的排序方式、排序规则和标签匹配方式使用默认值并返回警告
+    let normalized = [
+        FilterParams { sort: Some("popular".to_string()), ..Default::default() },
+        FilterParams { sort_secondary: Some("popular".to_string()), ..Default::default() },
+        FilterParams { collation: Some("ascii".to_string()), ..Default::default() },
+        FilterParams { tags: Some(vec!["rust".to_string()]), tag_mode: Some("none".to_string()), ..Default::default() },
+    ];
+    for params in normalized {
+        let result = ArticleFilter::filter_articles(&params).unwrap();
+        assert_eq!(result.warnings.len(), 1, "{:?}", params);
+    }
+
+    // 未知的分组方式返回参数无效错误
+    let error = ArticleFilter::filter_articles(&FilterParams {
+        group_by: Some("week".to_string()),
+        ..Default::default()
+    }).unwrap_err();
+    assert_eq!(error.code(), FilterErrorCode::InvalidParams);
+}
+
+// 未知的排序方式和排序规则按默认值排序，警告中给出使用的取值
+#[test]
+fn warns_about_unknown_sort_and_collation() {
+    let index = build_index("unknown-sort", |i, article| {
+        article.title = ["Zebra", "apple", "éclair"][i].to_string();
+        article.featured = false;
+    });
+    ArticleFilter::load_named_index("unknown-sort", &index, Visibility::Public).unwrap();
+    let ids = |params: &FilterParams| -> (Vec<String>, Vec<String>) {
+        let result = ArticleFilter::filter_articles_in("unknown-sort", params).unwrap();
+        (result.articles.into_iter().map(|article| article.id).collect(), result.warnings)
+    };
+
+    let (newest, warnings) = ids(&FilterParams { sort: Some("newest".to_string()), ..Default::default() });
+    assert!(warnings.is_empty());
+    let (unknown, warnings) = ids(&FilterParams { sort: Some("foo".to_string()), ..Default::default() });
+    assert_eq!(unknown, newest);
+    assert_eq!(warnings, ["未知的排序方式 'foo'，已使用 'newest'"]);
+
+    // 未知的次要排序方式被忽略
+    let (unknown, warnings) = ids(&FilterParams {
+        sort_secondary: Some("foo".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(unknown, newest);
+    assert_eq!(warnings, ["未知的次要排序方式 'foo'，已忽略"]);
+
+    // 未知的排序规则按界面语言选择，与不指定排序规则时的顺序相同
+    let (default, _) = ids(&FilterParams { sort: Some("title_asc".to_string()), ..Default::default() });
+    let (unknown, warnings) = ids(&FilterParams {
+        sort: Some("title_asc".to_string()),
+        collation: Some("foo".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(unknown, default);
+    assert_eq!(warnings, ["未知的排序规则 'foo'，已按界面语言选择"]);
+}
+
+// 使用单独名称的索引，不影响其他测试使用的默认索引
+#[test]
+fn orders_same_date_articles_stably() {
+    let index = build_index("same-date", |_, article| {
+        article.date = "2024-01-10T08:00:00Z".parse().unwrap();
+        article.featured = false;
+    });
+    ArticleFilter::load_named_index("same-date", &index, Visibility::Public).unwrap();
+
+    // 发布时间相同时按文章ID排序
+    assert_eq!(ids_by_cursor("same-date", "newest", None, None), ["articles/docker", "articles/rust", "articles/wasm"]);
+
+    // 指定次要排序方式时按标题降序，游标翻页得到相同的顺序
+    let result = ArticleFilter::filter_articles_in("same-date", &FilterParams {
+        sort: Some("newest".to_string()),
+        sort_secondary: Some("title_desc".to_string()),
+        ..Default::default()
+    }).unwrap();
+    let ids: Vec<String> = result.articles.into_iter().map(|article| article.id).collect();
+    assert_eq!(ids, ["articles/wasm", "articles/rust", "articles/docker"]);
+    assert_eq!(ids_by_cursor("same-date", "newest", Some("title_desc"), None), ids);
+}
+
+#[test]
+fn sorts_titles_by_collation() {
+    let index = build_index("collation", |i, article| {
+        article.title = ["Zebra", "apple", "éclair"][i].to_string();
+        article.featured = false;
+    });
+    ArticleFilter::load_named_index("collation", &index, Visibility::Public).unwrap();
+    let [zebra, apple, eclair] = ["articles/rust", "articles/wasm", "articles/docker"];
+
+    // 按字节比较时大写字母在前，带重音的字母在最后
+    assert_eq!(ids_by_cursor("collation", "title_asc", None, Some("binary")), [zebra, apple, eclair]);
+
+    // Unicode排序规则忽略大小写和重音，游标翻页使用相同的排序规则
+    let result = ArticleFilter::filter_articles_in("collation", &FilterParams {
+        sort: Some("title_asc".to_string()),
+        collation: Some("unicode".to_string()),
+        ..Default::default()
+    }).unwrap();
+    let ids: Vec<String> = result.articles.into_iter().map(|article| article.id).collect();
+    assert_eq!(ids, [apple, eclair, zebra]);
+    assert_eq!(ids_by_cursor("collation", "title_asc", None, Some("unicode")), ids);
+    assert_eq!(ids_by_cursor("collation", "title_desc", None, Some("unicode")), [zebra, eclair, apple]);
+
+    // 游标只能用于生成时的排序规则
+    let result = ArticleFilter::filter_articles_in("collation", &FilterParams {
+        sort: Some("title_asc".to_string()),
+        collation: Some("unicode".to_string()),
+        limit: Some(1),
+        ..Default::default()
+    }).unwrap();
+    let error = ArticleFilter::filter_articles_in("collation", &FilterParams {
+        sort: Some("title_asc".to_string()),
+        collation: Some("binary".to_string()),
+        cursor: result.next_cursor,
+        ..Default::default()
+    }).unwrap_err();
+    assert_eq!(error.code(), FilterErrorCode::InvalidParams);
+}
+
+#[test]
+fn validates_preset_rules() {
+    let rules = |sort_by: &str, tag_mode: Option<&str>| FilterRules {
+        sort_by: sort_by.to_string(),
+        tag_mode: tag_mode.map(str::to_string),
+        ..Default::default()
+    };
+
+    assert!(FilterRules::default().validate().is_ok());
+    assert!(rules("date_asc", Some("all")).validate().is_ok());
+    assert!(rules("reading_time_desc", Some("any")).validate().is_ok());
+    assert!(rules("date", None).validate().unwrap_err().contains("date"));
+    assert!(rules("date_desc", Some("every")).validate().unwrap_err().contains("every"));
+}
+
+#[test]
+fn preset_uses_tag_mode() {
+    let articles = std::fs::read_to_string(fixtures_dir().join("articles.json")).unwrap();
+    let articles: Vec<ArticleMetadata> = serde_json::from_str(&articles).unwrap();
+    let tags: Vec<String> = articles[0].tags.iter().take(2).cloned().collect();
+    let mut builder = FilterBuilder::new();
+    for article in articles {
+        builder.add_article(article);
+    }
+    for (name, tag_mode) in [("any", None), ("all", Some("all"))] {
+        builder.add_preset(FilterPreset {
+            name: name.to_string(),
+            rules: FilterRules { tags: tags.clone(), tag_mode: tag_mode.map(str::to_string), ..Default::default() },
+        });
+    }
+    let path = std::env::temp_dir().join(format!("article-filter-presets-{}.bin", std::process::id()));
+    builder.save_filter_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    ArticleFilter::load_named_index("presets", &data, Visibility::Public).unwrap();
+
+    // 带有全部标签的文章也带有任一标签
+    let any = ArticleFilter::filter_by_preset_in("presets", "any", 1).unwrap();
+    let all = ArticleFilter::filter_by_preset_in("presets", "all", 1).unwrap();
+    assert!(all.total >= 1);
+    assert!(any.total > all.total, "标签 {:?}: any {} 篇，all {} 篇", tags, any.total, all.total);
+}
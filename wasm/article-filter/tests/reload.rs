@@ -0,0 +1,134 @@
+//! 索引重新加载测试 - 站点重新构建后再次加载索引应替换已加载的索引，清除后查询返回错误；
+//! 不同名称的索引互不影响；合并多个索引时重复的文章只保留第一篇。
+//!
+//! 使用 `utils-common/tests/fixtures` 中的筛选索引样本和样本文章。
+
+use std::path::{Path, PathBuf};
+use article_filter::builder::FilterBuilder;
+use article_filter::models::FilterIndex;
+use article_filter::{ArticleFilter, AuthorCount, FilterError, FilterErrorCode, FilterParams, DEFAULT_INDEX, INDEX_VERSION, MAX_INDEX_VERSION};
+use utils_common::models::{ArticleMetadata, Visibility};
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../utils-common/tests/fixtures")
+}
+
+/// 用样本文章中的前 `count` 篇构建筛选索引
+fn build_index(count: usize) -> Vec<u8> {
+    let articles = std::fs::read_to_string(fixtures_dir().join("articles.json")).unwrap();
+    let articles: Vec<ArticleMetadata> = serde_json::from_str(&articles).unwrap();
+    let mut builder = FilterBuilder::new();
+    for article in articles.into_iter().take(count) {
+        builder.add_article(article);
+    }
+
+    let path = std::env::temp_dir().join(format!("article-filter-reload-{}-{}.bin", std::process::id(), count));
+    builder.save_filter_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    data
+}
+
+fn total() -> Result<usize, FilterError> {
+    ArticleFilter::filter_articles(&FilterParams::default()).map(|result| result.total)
+}
+
+fn total_in(name: &str) -> Result<usize, FilterError> {
+    ArticleFilter::filter_articles_in(name, &FilterParams::default()).map(|result| result.total)
+}
+
+// 索引是全局的，所有步骤放在同一个测试中依次执行
+#[test]
+fn reload_named_and_clear_indexes() {
+    let full = std::fs::read(fixtures_dir().join(format!("filter-v{}.bin", MAX_INDEX_VERSION))).unwrap();
+    ArticleFilter::load_index(&full).unwrap();
+    assert_eq!(total(), Ok(3));
+    let info = ArticleFilter::get_index_info(DEFAULT_INDEX).unwrap();
+    let version = format!("{}.{}", INDEX_VERSION[0], INDEX_VERSION[1]);
+    assert_eq!((info.article_count, info.version.as_deref()), (3, Some(version.as_str())));
+    assert_eq!(info.max_version, MAX_INDEX_VERSION);
+
+    // 再次加载替换已加载的索引
+    ArticleFilter::load_index(&build_index(2)).unwrap();
+    assert_eq!(total(), Ok(2));
+    let rebuilt = ArticleFilter::get_index_info(DEFAULT_INDEX).unwrap();
+    assert_eq!(rebuilt.article_count, 2);
+    assert!(rebuilt.created_at > info.created_at);
+
+    // 不同名称的索引并存，互不影响
+    ArticleFilter::load_named_index("notes", &build_index(1), Visibility::Public).unwrap();
+    ArticleFilter::load_named_index("blog", &full, Visibility::Public).unwrap();
+    assert_eq!(total_in("blog"), Ok(3));
+    assert_eq!(total_in("notes"), Ok(1));
+    assert_eq!(total(), Ok(2));
+    assert_eq!(total_in("projects"), Err(FilterError::IndexNotLoaded("projects".to_string())));
+    assert_eq!(ArticleFilter::index_names().unwrap(), ["blog", "default", "notes"]);
+
+    // 带 `_in` 的查询使用指定名称的索引，不带的使用默认索引
+    let sorted = |mut tags: Vec<String>| { tags.sort(); tags };
+    assert_eq!(sorted(ArticleFilter::get_all_tags_in("notes").unwrap()), ["rust", "教程"]);
+    assert_eq!(sorted(ArticleFilter::get_all_tags().unwrap()), ["rust", "wasm", "教程"]);
+    let authors = |authors: Vec<AuthorCount>| authors.into_iter().map(|author| author.name).collect::<Vec<_>>();
+    assert_eq!(authors(ArticleFilter::get_authors_in("blog").unwrap()), ["shuyu", "lin"]);
+    assert_eq!(authors(ArticleFilter::get_authors().unwrap()), ["shuyu"]);
+    assert!(ArticleFilter::get_related_in("notes", "articles/rust", 5).unwrap().is_empty());
+    assert_eq!(ArticleFilter::get_related("articles/rust", 5).unwrap().len(), 1);
+    let previous = ArticleFilter::get_adjacent_in("blog", "articles/rust", None).unwrap().previous;
+    assert_eq!(previous.map(|article| article.id).as_deref(), Some("articles/docker"));
+    assert!(ArticleFilter::get_adjacent("articles/rust", None).unwrap().previous.is_none());
+    assert_eq!(ArticleFilter::get_calendar_in("blog", 2023).unwrap().total, 1);
+    assert_eq!(ArticleFilter::get_calendar(2023).unwrap().total, 0);
+    assert_eq!(ArticleFilter::get_archive_in("blog", false).unwrap().years.len(), 2);
+    assert_eq!(
+        ArticleFilter::get_related_in("projects", "articles/rust", 5).unwrap_err(),
+        FilterError::IndexNotLoaded("projects".to_string()),
+    );
+
+    assert!(ArticleFilter::remove_index("notes").unwrap());
+    assert_eq!(total_in("notes").unwrap_err().code(), FilterErrorCode::IndexNotLoaded);
+    assert_eq!(total_in("blog"), Ok(3));
+
+    // 清除所有索引
+    ArticleFilter::clear_index().unwrap();
+    assert!(total().is_err());
+    assert!(total_in("blog").is_err());
+    assert!(ArticleFilter::get_all_tags().is_err());
+
+    ArticleFilter::load_index(&full).unwrap();
+    assert_eq!(total(), Ok(3));
+}
+
+/// 用样本文章中的第一篇构建筛选索引，标签替换为 `tags`
+fn build_single(tags: &[&str]) -> FilterIndex {
+    let articles = std::fs::read_to_string(fixtures_dir().join("articles.json")).unwrap();
+    let mut articles: Vec<ArticleMetadata> = serde_json::from_str(&articles).unwrap();
+    let mut article = articles.remove(0);
+    article.tags = tags.iter().map(|tag| tag.to_string()).collect();
+    let mut builder = FilterBuilder::new();
+    builder.add_article(article);
+    builder.build_filter_index().unwrap()
+}
+
+#[test]
+fn merge_keeps_first_copy_of_shared_article() {
+    let first = build_single(&["rust", "共同"]);
+    let second = build_single(&["wasm", "共同"]);
+
+    let merged = FilterIndex::merge(vec![first, second]);
+
+    // 只保留第一篇及其记录，第二篇的记录不复制
+    assert_eq!(merged.articles.len(), 1);
+    assert_eq!(merged.records.len(), 1);
+    merged.validate().unwrap();
+    let record = merged.records.get(merged.articles[0].record as usize).unwrap();
+    assert_eq!(record.tags().collect::<Vec<_>>(), ["rust", "共同"]);
+
+    // 标签索引与保留的记录一致，第二篇独有的标签不指向保留的文章
+    let tagged = |tag: &str| merged.tag_index.get(tag).is_some_and(|ids| ids.contains(&0));
+    assert!(tagged("rust"));
+    assert!(tagged("共同"));
+    assert!(!tagged("wasm"));
+    for article_ids in merged.year_index.values().chain(merged.month_index.values()) {
+        assert!(article_ids.iter().all(|&id| id < merged.articles.len()));
+    }
+}
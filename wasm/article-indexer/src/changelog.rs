@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use markup5ever_rcdom::{Handle, NodeData};
+use serde::{Deserialize, Serialize};
+use utils_common::text::normalize_heading_text;
+use crate::config::{ChangelogConfig, ContentFilterConfig};
+use crate::extract_text_from_node;
+
+/// 条目边界选择器 - 支持标签名（`h2`）、类名（`.entry`）及其组合（`li.entry`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntrySelector {
+    tag: Option<String>,
+    classes: Vec<String>,
+}
+
+impl EntrySelector {
+    /// 解析选择器
+    pub fn parse(selector: &str) -> Result<Self, String> {
+        let selector = selector.trim();
+        let mut parts = selector.split('.');
+        let tag = parts.next().unwrap_or_default().to_lowercase();
+        let classes: Vec<String> = parts.map(str::to_string).collect();
+
+        let valid_name = |name: &str| !name.is_empty()
+            && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if (tag.is_empty() && classes.is_empty())
+            || (!tag.is_empty() && !valid_name(&tag))
+            || !classes.iter().all(|class| valid_name(class))
+        {
+            return Err(format!("无效的条目选择器 '{}'，应为标签名、类名或两者组合（如 h2、.entry、li.entry）", selector));
+        }
+
+        Ok(Self {
+            tag: (!tag.is_empty()).then_some(tag),
+            classes,
+        })
+    }
+
+    /// 元素是否匹配选择器
+    fn matches(&self, handle: &Handle) -> bool {
+        let NodeData::Element { ref name, ref attrs, .. } = handle.data else {
+            return false;
+        };
+        if self.tag.as_deref().is_some_and(|tag| name.local.as_ref() != tag) {
+            return false;
+        }
+        let attrs = attrs.borrow();
+        let class_attr = attrs.iter()
+            .find(|attr| attr.name.local.as_ref() == "class")
+            .map(|attr| attr.value.to_string())
+            .unwrap_or_default();
+        self.classes.iter().all(|class| class_attr.split_whitespace().any(|value| value == class))
+    }
+}
+
+/// 按条目边界拆分的页面正文
+#[derive(Debug, Default)]
+pub struct PageEntries {
+    /// 第一个条目之前的正文（页面简介等），始终写入索引
+    pub preamble: Section,
+    /// 各条目，按文档顺序
+    pub entries: Vec<Section>,
+}
+
+/// 一段正文及其中的标题
+#[derive(Debug, Default)]
+pub struct Section {
+    pub text: String,
+    pub headings: Vec<String>,
+}
+
+/// 按条目边界拆分内容区域的正文，匹配选择器的元素开始一个新条目，直到下一个匹配的元素为止
+pub fn split_entries(root: &Handle, selector: &EntrySelector, filter: &ContentFilterConfig) -> PageEntries {
+    let mut sections = vec![Section::default()];
+    collect_sections(root, selector, filter, &mut sections);
+
+    for section in &mut sections {
+        section.text = section.text.split_whitespace().collect::<Vec<_>>().join(" ");
+    }
+    let mut sections = sections.into_iter();
+    PageEntries {
+        preamble: sections.next().unwrap_or_default(),
+        entries: sections.collect(),
+    }
+}
+
+// 递归收集正文，过滤规则与 `extract_text_from_node_filtered` 相同
+fn collect_sections(handle: &Handle, selector: &EntrySelector, filter: &ContentFilterConfig, sections: &mut Vec<Section>) {
+    match handle.data {
+        NodeData::Element { ref name, ref attrs, .. } => {
+            let tag_name = name.local.to_string();
+            if filter.skips(&tag_name, &attrs.borrow()) || tag_name == "figcaption" {
+                return;
+            }
+
+            if selector.matches(handle) {
+                sections.push(Section::default());
+            }
+
+            // 记录标题所在的条目，未写入索引的条目中的标题也不写入
+            let is_heading = tag_name.len() == 2
+                && tag_name.starts_with('h')
+                && tag_name[1..].parse::<u8>().is_ok_and(|level| (1..=6).contains(&level));
+            if is_heading {
+                let mut heading = String::new();
+                extract_text_from_node(handle, &mut heading);
+                let heading = normalize_heading_text(heading.trim());
+                if let Some(section) = sections.last_mut() {
+                    section.headings.push(heading);
+                }
+            }
+
+            for child in handle.children.borrow().iter() {
+                collect_sections(child, selector, filter, sections);
+            }
+        }
+        NodeData::Text { ref contents } => {
+            let content = contents.borrow();
+            if !content.trim().is_empty() {
+                if let Some(section) = sections.last_mut() {
+                    section.text.push_str(&content);
+                    section.text.push(' ');
+                }
+            }
+        }
+        _ => {
+            for child in handle.children.borrow().iter() {
+                collect_sections(child, selector, filter, sections);
+            }
+        }
+    }
+}
+
+/// 条目内容哈希（FNV-1a 64位）- 跨版本稳定，写入增量状态
+fn entry_hash(text: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// 条目哈希记录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EntryRecord {
+    /// 条目正文的哈希
+    pub hash: String,
+    /// 首次出现的时间
+    pub first_seen: DateTime<Utc>,
+}
+
+/// 更新日志页面的增量状态 - 记录各页面出现过的条目哈希，用于识别新增和修改的条目
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ChangelogState {
+    /// 页面ID到条目哈希历史的映射，历史按首次出现时间从新到旧排列
+    pub pages: BTreeMap<String, Vec<EntryRecord>>,
+}
+
+/// 一个更新日志页面在本次构建中的变化
+#[derive(Debug)]
+pub struct ChangelogUpdate {
+    pub id: String,
+    /// 页面中的条目总数
+    pub total: usize,
+    /// 本次新增或修改的条目数
+    pub new_entries: usize,
+    /// 写入索引的条目数
+    pub indexed: usize,
+}
+
+impl ChangelogState {
+    /// 加载状态文件，文件不存在时返回空状态
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("无法读取更新日志状态 {}: {}", path.display(), e))?;
+        serde_json::from_str(&data)
+            .map_err(|e| format!("解析更新日志状态失败 {}: {}", path.display(), e))
+    }
+
+    /// 保存状态文件
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let data = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("序列化更新日志状态失败: {}", e))?;
+        fs::write(path, data)
+            .map_err(|e| format!("无法写入更新日志状态 {}: {}", path.display(), e))
+    }
+
+    /// 记录页面当前的条目并选出写入索引的条目
+    ///
+    /// 条目按首次出现时间从新到旧排列，修改过的条目视为新条目；同一次构建中出现的条目
+    /// 按文档顺序排列（更新日志通常把最新的条目放在最前面）。返回写入索引的条目下标，
+    /// 按文档顺序排列。
+    pub fn select_entries(
+        &mut self,
+        id: &str,
+        entries: &[Section],
+        config: &ChangelogConfig,
+        now: DateTime<Utc>,
+    ) -> (Vec<usize>, ChangelogUpdate) {
+        let history = self.pages.entry(id.to_string()).or_default();
+        let hashes: Vec<String> = entries.iter().map(|entry| entry_hash(&entry.text)).collect();
+
+        // 新条目记录在历史的最前面
+        let known: HashSet<&str> = history.iter().map(|record| record.hash.as_str()).collect();
+        let mut fresh = Vec::new();
+        let mut seen = HashSet::new();
+        for hash in &hashes {
+            if !known.contains(hash.as_str()) && seen.insert(hash.as_str()) {
+                fresh.push(EntryRecord { hash: hash.clone(), first_seen: now });
+            }
+        }
+        let new_entries = fresh.len();
+        history.splice(0..0, fresh);
+
+        // 历史中的顺序即新旧顺序
+        let rank: BTreeMap<&str, usize> = history.iter()
+            .enumerate()
+            .map(|(rank, record)| (record.hash.as_str(), rank))
+            .collect();
+        let mut order: Vec<usize> = (0..entries.len()).collect();
+        order.sort_by_key(|&index| (rank[hashes[index].as_str()], index));
+        if config.max_entries > 0 {
+            order.truncate(config.max_entries);
+        }
+        order.sort_unstable();
+
+        // 只保留最近的历史，当前页面中的条目始终保留
+        let current: HashSet<&str> = hashes.iter().map(String::as_str).collect();
+        let mut kept = 0;
+        history.retain(|record| {
+            kept += 1;
+            kept <= config.history_limit || current.contains(record.hash.as_str())
+        });
+
+        let update = ChangelogUpdate {
+            id: id.to_string(),
+            total: entries.len(),
+            new_entries,
+            indexed: order.len(),
+        };
+        (order, update)
+    }
+}
+
+/// 本次构建中的更新日志页面 - 拆分条目、更新哈希历史并记录各页面的变化
+pub struct ChangelogPages<'a> {
+    config: &'a ChangelogConfig,
+    selector: EntrySelector,
+    state: ChangelogState,
+    now: DateTime<Utc>,
+    updates: Vec<ChangelogUpdate>,
+}
+
+impl<'a> ChangelogPages<'a> {
+    pub fn new(config: &'a ChangelogConfig, state: ChangelogState) -> Result<Self, String> {
+        Ok(Self {
+            config,
+            selector: EntrySelector::parse(&config.entry_selector)?,
+            state,
+            now: Utc::now(),
+            updates: Vec::new(),
+        })
+    }
+
+    /// 提取更新日志页面的正文，只包含页面简介和最新的条目；不是更新日志页面时返回None
+    ///
+    /// 同时返回写入索引的部分中的标题文本。
+    pub fn extract_content(&mut self, id: &str, root: &Handle, filter: &ContentFilterConfig) -> Option<(String, HashSet<String>)> {
+        if !self.config.matches(id) {
+            return None;
+        }
+
+        let page = split_entries(root, &self.selector, filter);
+        let (indices, update) = self.state.select_entries(id, &page.entries, self.config, self.now);
+        self.updates.push(update);
+
+        let sections = std::iter::once(&page.preamble).chain(indices.iter().map(|&index| &page.entries[index]));
+        let mut parts = Vec::new();
+        let mut headings = HashSet::new();
+        for section in sections {
+            if !section.text.is_empty() {
+                parts.push(section.text.as_str());
+            }
+            headings.extend(section.headings.iter().cloned());
+        }
+        Some((parts.join(" "), headings))
+    }
+
+    /// 各更新日志页面的变化，按页面ID排序
+    pub fn updates(&self) -> Vec<&ChangelogUpdate> {
+        let mut updates: Vec<&ChangelogUpdate> = self.updates.iter().collect();
+        updates.sort_by(|a, b| a.id.cmp(&b.id));
+        updates
+    }
+
+    /// 更新后的哈希历史
+    pub fn state(&self) -> &ChangelogState {
+        &self.state
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::fs;
+use serde::Deserialize;
+use article_filter::models::FilterPreset;
+use search_wasm::builder::SearchBuilderConfig;
+use utils_common::compression::SerializationFormat;
+use html5ever::Attribute;
+use crate::changelog::EntrySelector;
+use crate::is_non_content_element;
+
+/// 索引器配置 - 从 `--config` 指定的JSON文件加载
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct IndexerConfig {
+    /// 写入筛选索引的筛选预设
+    pub presets: Vec<FilterPreset>,
+    /// 写入搜索索引的页面类型分数系数（如 `{"page": 0.5}`），覆盖内置默认值
+    pub page_type_boosts: HashMap<String, f64>,
+    /// 外部分词器，为空时使用内置的关键词提取
+    pub tokenizer: Option<TokenizerConfig>,
+    /// 将文章正文拆分到单独的内容文件 `search_content.bin`，搜索索引只记录正文区间
+    pub split_content: bool,
+    /// 索引文件的序列化格式: "bincode"（默认）或 "msgpack"（自描述，便于查看索引内容和在其他语言中读取）
+    pub format: SerializationFormat,
+    /// 搜索索引构建配置（最小词长、常用词数量、字段系数、停用词等），写入搜索索引
+    pub search: SearchBuilderConfig,
+    /// 各页面类型的正文长度下限（如 `{"article": {"min_length": 200}}`），未列出的类型使用默认下限
+    pub content_thresholds: HashMap<String, ContentThreshold>,
+    /// 经常更新的页面（如 now、更新日志）只索引最新的若干条目
+    pub changelog: ChangelogConfig,
+    /// 按类名或ID排除或保留的正文元素，补充内置的非内容区域判断
+    pub content_filter: ContentFilterConfig,
+    /// 足迹地图的地理索引，配置后在输出目录生成 `geo_index.bin`
+    pub geo: Option<GeoIndexConfig>,
+    /// 命令行 `--min-words` 指定的最少词数，覆盖各页面类型配置的词数下限
+    #[serde(skip)]
+    pub min_words: Option<usize>,
+}
+
+/// 地理索引配置 - 文件路径相对于运行索引器的目录
+#[derive(Deserialize, Debug, Clone)]
+pub struct GeoIndexConfig {
+    /// 世界地图GeoJSON文件
+    pub world: String,
+    /// 中国地图GeoJSON文件
+    pub china: String,
+    /// 已访问地点（如 "日本"、"中国-北京"）
+    #[serde(default)]
+    pub visited: Vec<String>,
+}
+
+/// 正文长度下限 - 正文低于下限的页面可能不是有效内容页面（或正文提取失败），不写入索引
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct ContentThreshold {
+    /// 最少长度（字节，去除首尾空白后）
+    pub min_length: usize,
+    /// 最少词数，每个汉字计为一个词
+    pub min_words: usize,
+}
+
+impl Default for ContentThreshold {
+    fn default() -> Self {
+        Self { min_length: 30, min_words: 0 }
+    }
+}
+
+/// 更新日志页面配置 - 这类页面不断追加条目，整页写入索引会让搜索结果中出现全部历史内容
+///
+/// 页面正文按条目边界拆分，新增或修改的条目按内容哈希识别，哈希历史记录在输出目录的
+/// `changelog_state.json` 中，增量更新时读取。
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ChangelogConfig {
+    /// 页面ID（如 `now`、`changelog`），以 `/` 结尾时匹配该目录下的所有页面
+    pub pages: Vec<String>,
+    /// 条目边界选择器，匹配的元素开始一个新条目：标签名（`h2`）、类名（`.entry`）或两者组合（`li.entry`）
+    pub entry_selector: String,
+    /// 写入索引的最新条目数量，为0时写入全部条目
+    pub max_entries: usize,
+    /// 每个页面保留的条目哈希历史数量，页面中现有的条目始终保留
+    pub history_limit: usize,
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            pages: Vec::new(),
+            entry_selector: "h2".to_string(),
+            max_entries: 5,
+            history_limit: 200,
+        }
+    }
+}
+
+impl ChangelogConfig {
+    /// 页面是否为更新日志页面
+    pub fn matches(&self, id: &str) -> bool {
+        self.pages.iter().any(|page| match page.strip_suffix('/') {
+            Some(dir) => id.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/')),
+            None => page == id,
+        })
+    }
+}
+
+/// 正文过滤配置 - 在内置的非内容区域判断（导航、目录、评论等）之外，按类名或ID精确排除或保留元素
+///
+/// 每项为类名（`.post-meta`）或ID（`#author-box`），类名与元素的任一类名完全相同时匹配。
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ContentFilterConfig {
+    /// 排除的元素 - 元素及其子元素不写入正文、摘要、媒体说明和标题
+    pub exclude: Vec<String>,
+    /// 保留的元素 - 即使被内置规则判断为非内容区域也提取，子元素仍按规则过滤；
+    /// 同时匹配排除和保留时排除
+    pub include: Vec<String>,
+}
+
+impl ContentFilterConfig {
+    /// 检查各项的格式
+    pub fn validate(&self) -> Result<(), String> {
+        for item in self.exclude.iter().chain(&self.include) {
+            let name = item.strip_prefix('.').or_else(|| item.strip_prefix('#')).unwrap_or_default();
+            if name.is_empty() || name.chars().any(char::is_whitespace) {
+                return Err(format!("无效的正文过滤项 '{}'，应为类名（如 .post-meta）或ID（如 #author-box）", item));
+            }
+        }
+        Ok(())
+    }
+
+    /// 元素是否被配置排除
+    pub fn excludes(&self, attrs: &[Attribute]) -> bool {
+        Self::matches_any(&self.exclude, attrs)
+    }
+
+    /// 元素是否不写入正文：配置排除的元素，或者未配置保留的内置非内容区域
+    pub fn skips(&self, tag_name: &str, attrs: &[Attribute]) -> bool {
+        if self.excludes(attrs) {
+            return true;
+        }
+        is_non_content_element(tag_name, attrs) && !Self::matches_any(&self.include, attrs)
+    }
+
+    fn matches_any(items: &[String], attrs: &[Attribute]) -> bool {
+        if items.is_empty() {
+            return false;
+        }
+        let attr = |name: &str| attrs.iter()
+            .find(|attr| attr.name.local.as_ref() == name)
+            .map(|attr| attr.value.as_ref());
+        let classes = attr("class").unwrap_or_default();
+        let id = attr("id").unwrap_or_default();
+        items.iter().any(|item| match (item.strip_prefix('.'), item.strip_prefix('#')) {
+            (Some(class), _) => classes.split_whitespace().any(|value| value == class),
+            (_, Some(item_id)) => id == item_id,
+            _ => false,
+        })
+    }
+}
+
+/// 外部分词器配置 - 构建搜索索引时调用外部程序分词
+///
+/// WASI模块可以通过 `wasmtime` 等运行时作为命令调用，
+/// 如 `{"command": "wasmtime", "args": ["run", "analyzer.wasm"]}`。
+#[derive(Deserialize, Debug, Clone)]
+pub struct TokenizerConfig {
+    /// 分词程序路径
+    pub command: String,
+    /// 命令行参数
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 分词器标识，写入搜索索引供搜索时检查，默认使用命令和参数
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl TokenizerConfig {
+    /// 分词器标识
+    pub fn identity(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            std::iter::once(self.command.as_str())
+                .chain(self.args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    }
+}
+
+impl IndexerConfig {
+    /// 页面类型的正文长度下限
+    pub fn content_threshold(&self, page_type: &str) -> ContentThreshold {
+        let mut threshold = self.content_thresholds.get(page_type).copied().unwrap_or_default();
+        if let Some(min_words) = self.min_words {
+            threshold.min_words = min_words;
+        }
+        threshold
+    }
+
+    /// 从JSON文件加载配置
+    pub fn load(path: &str) -> Result<Self, String> {
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("无法读取配置文件 {}: {}", path, e))?;
+        
+        let config: Self = serde_json::from_str(&data)
+            .map_err(|e| format!("解析配置文件失败 {}: {}", path, e))?;
+        if !config.changelog.pages.is_empty() {
+            EntrySelector::parse(&config.changelog.entry_selector)?;
+        }
+        config.content_filter.validate()?;
+        // 预设中的取值写入筛选索引后在浏览器中才会用到，构建时提前检查
+        for preset in &config.presets {
+            preset.rules.validate()
+                .map_err(|e| format!("筛选预设 {} 无效: {}", preset.name, e))?;
+        }
+        Ok(config)
+    }
+}
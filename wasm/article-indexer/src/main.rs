@@ -1,17 +1,29 @@
 use std::fs;
 use std::path::Path;
+use std::collections::HashSet;
 use chrono::Utc;
 use clap::{Command, Arg, ArgAction};
-use html5ever::parse_document;
+use html5ever::{parse_document, Attribute};
 use html5ever::tendril::TendrilSink;
 use markup5ever_rcdom::{Handle, NodeData, RcDom};
 use walkdir::WalkDir;
 
-use utils_common::{ArticleMetadata, Heading};
+mod changelog;
+mod config;
+mod manifest;
+mod tokenizer;
+use changelog::{ChangelogPages, ChangelogState};
+use config::{ContentFilterConfig, ContentThreshold, GeoIndexConfig, IndexerConfig};
+use manifest::{IndexManifest, MANIFEST_FILE_NAME};
+use tokenizer::ExternalTokenizer;
+
+use utils_common::{ArticleMetadata, Heading, Visibility};
+use utils_common::text::{count_words, detect_language, normalize_heading_text};
 
 // 导入筛选和搜索模块
 use article_filter::builder::FilterBuilder;
-use search_wasm::builder::SearchBuilder;
+use search_wasm::builder::{SearchBuilder, BUILTIN_TOKENIZER};
+use geo_wasm::builder::GeoIndexBuilder;
 
 // 主函数
 fn main() {
@@ -42,6 +54,21 @@ fn main() {
             .long("all")
             .help("索引所有页面，包括非文章页面")
             .action(ArgAction::SetTrue))
+        .arg(Arg::new("config")
+            .short('c')
+            .long("config")
+            .value_name("CONFIG_FILE")
+            .help("索引器配置文件路径 (JSON)"))
+        .arg(Arg::new("incremental")
+            .short('i')
+            .long("incremental")
+            .help("在输出目录中已有的搜索索引上增量更新，只重新索引有变化的文章")
+            .action(ArgAction::SetTrue))
+        .arg(Arg::new("min_words")
+            .long("min-words")
+            .value_name("WORDS")
+            .help("正文的最少词数（每个汉字计为一个词），覆盖配置文件中各页面类型的设置")
+            .value_parser(clap::value_parser!(usize)))
         .get_matches();
 
     // 获取参数值
@@ -49,6 +76,20 @@ fn main() {
     let output_dir = matches.get_one::<String>("output").unwrap();
     let verbose = matches.get_flag("verbose");
     let index_all = matches.get_flag("index_all");
+    let incremental = matches.get_flag("incremental");
+    
+    // 加载配置文件
+    let mut config = match matches.get_one::<String>("config") {
+        Some(config_path) => match IndexerConfig::load(config_path) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("错误: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => IndexerConfig::default(),
+    };
+    config.min_words = matches.get_one::<usize>("min_words").copied();
 
     // 检查目录
     let source_path = std::path::Path::new(source_dir);
@@ -71,7 +112,7 @@ fn main() {
     println!("输出目录: {}", output_dir);
 
     // 生成索引
-    match generate_index(source_dir, output_dir, verbose, index_all) {
+    match generate_index(source_dir, output_dir, verbose, index_all, incremental, &config) {
         Ok(_) => println!("索引生成成功！"),
         Err(e) => {
             eprintln!("错误: 索引生成失败: {}", e);
@@ -85,17 +126,30 @@ fn generate_index(
     source_dir: &str, 
     output_dir: &str, 
     verbose: bool, 
-    index_all: bool
+    index_all: bool,
+    incremental: bool,
+    config: &IndexerConfig
 ) -> Result<(), String> {
     // 记录开始时间
     let start_time = std::time::Instant::now();
     
+    // 更新日志页面的条目哈希历史，增量更新时沿用上次构建的记录
+    let changelog_state_path = Path::new(output_dir).join("changelog_state.json");
+    let changelog_state = if incremental {
+        ChangelogState::load(&changelog_state_path)?
+    } else {
+        ChangelogState::default()
+    };
+    let mut changelog = ChangelogPages::new(&config.changelog, changelog_state)?;
+    
     // 扫描HTML文件
     println!("扫描HTML文件...");
-    let (articles, skipped_count) = scan_html_files(source_dir, verbose, index_all)?;
+    let (articles, skipped_count, thin_pages) = scan_html_files(source_dir, verbose, index_all, config, &mut changelog)?;
     
     let article_count = articles.len();
     println!("扫描完成。找到 {} 篇有效文章，跳过 {} 个文件。", article_count, skipped_count);
+    report_thin_pages(&thin_pages, article_count, verbose);
+    report_changelog_pages(&changelog, verbose);
     
     if article_count == 0 {
         return Err("没有找到有效文章".to_string());
@@ -104,23 +158,103 @@ fn generate_index(
     // 创建筛选索引构建器
     let mut filter_builder = FilterBuilder::new();
     
-    // 创建搜索索引构建器
-    let mut search_builder = SearchBuilder::new();
-    
-    // 添加文章到构建器
-    for article in articles {
-        filter_builder.add_article(article.clone());
-        search_builder.add_article(article);
-    }
-    
     // 构建输出路径
     let filter_index_path = format!("{}/filter_index.bin", output_dir);
     let search_index_path = format!("{}/search_index.bin", output_dir);
     
+    // 创建搜索索引构建器，增量更新时加载已有的搜索索引
+    let tokenizer_identity = config.tokenizer.as_ref().map_or(BUILTIN_TOKENIZER.to_string(), |tokenizer| tokenizer.identity());
+    let mut search_builder = match incremental.then(|| SearchBuilder::load_existing(&search_index_path)) {
+        Some(Ok(builder)) if builder.config() == &config.search
+            && builder.existing_tokenizer() == Some(tokenizer_identity.as_str()) => builder,
+        Some(Ok(_)) => {
+            println!("搜索索引配置或分词器已修改，重新构建索引");
+            SearchBuilder::with_config(config.search.clone())
+        }
+        Some(Err(e)) => {
+            println!("无法增量更新，重新构建索引: {}", e);
+            SearchBuilder::with_config(config.search.clone())
+        }
+        None => SearchBuilder::with_config(config.search.clone()),
+    };
+    
+    // 设置索引文件格式
+    filter_builder.set_format(config.format);
+    search_builder.set_format(config.format);
+    
+    // 添加筛选预设
+    for preset in &config.presets {
+        filter_builder.add_preset(preset.clone());
+    }
+    
+    // 设置页面类型分数系数
+    for (page_type, boost) in &config.page_type_boosts {
+        search_builder.set_page_type_boost(page_type, *boost);
+    }
+    
+    // 启动外部分词器
+    if let Some(tokenizer_config) = &config.tokenizer {
+        let tokenizer = ExternalTokenizer::spawn(tokenizer_config)?;
+        println!("使用外部分词器: {}", tokenizer.identity());
+        let identity = tokenizer.identity().to_string();
+        let tokenizer = std::cell::RefCell::new(tokenizer);
+        search_builder.set_tokenizer(&identity, move |text| tokenizer.borrow_mut().tokenize(text));
+    }
+    
+    // 添加文章到构建器
+    if search_builder.is_incremental() {
+        // 移除源目录中已不存在的文章，只重新索引有变化的文章
+        let scanned: HashSet<&str> = articles.iter().map(|article| article.id.as_str()).collect();
+        let mut removed = 0;
+        for id in search_builder.article_ids() {
+            if !scanned.contains(id.as_str()) && search_builder.remove_article(&id)? {
+                removed += 1;
+            }
+        }
+        
+        let mut changed = 0;
+        for article in articles {
+            filter_builder.add_article(article.clone());
+            if search_builder.update_article(article)? {
+                changed += 1;
+            }
+        }
+        println!("增量更新: {} 篇文章有变化，移除 {} 篇文章", changed, removed);
+    } else {
+        for article in articles {
+            filter_builder.add_article(article.clone());
+            search_builder.add_article(article);
+        }
+    }
+    
     // 保存索引
     println!("正在生成和保存索引...");
     filter_builder.save_filter_index(&filter_index_path)?;
-    search_builder.save_search_index(&search_index_path)?;
+    if config.split_content {
+        let content_path = format!("{}/search_content.bin", output_dir);
+        search_builder.save_split_search_index(&search_index_path, &content_path)?;
+    } else {
+        search_builder.save_search_index(&search_index_path)?;
+    }
+    if let Some(geo) = &config.geo {
+        save_geo_index(geo, &format!("{}/geo_index.bin", output_dir), config)?;
+    }
+    
+    // 生成索引清单，记录各文件的大小、版本和内容哈希
+    let mut manifest = IndexManifest::new();
+    manifest.add_file(output_dir, "filter_index.bin")?;
+    manifest.add_file(output_dir, "search_index.bin")?;
+    if config.split_content {
+        manifest.add_file(output_dir, "search_content.bin")?;
+    }
+    if config.geo.is_some() {
+        manifest.add_file(output_dir, "geo_index.bin")?;
+    }
+    manifest.save(output_dir)?;
+    println!("索引清单已写入: {}/{}", output_dir, MANIFEST_FILE_NAME);
+    if !config.changelog.pages.is_empty() {
+        changelog.state().save(&changelog_state_path)?;
+    }
     
     // 计算耗时
     let elapsed = start_time.elapsed();
@@ -129,13 +263,74 @@ fn generate_index(
     Ok(())
 }
 
-// 扫描HTML文件并提取文章数据
+// 读取地图数据并生成地理索引
+fn save_geo_index(geo: &GeoIndexConfig, path: &str, config: &IndexerConfig) -> Result<(), String> {
+    let read = |file: &str| fs::read_to_string(file)
+        .map_err(|e| format!("无法读取地图数据 {}: {}", file, e));
+    
+    let mut builder = GeoIndexBuilder::new(read(&geo.world)?, read(&geo.china)?);
+    builder.set_visited_places(geo.visited.clone());
+    builder.set_format(config.format);
+    builder.save_geo_index(path)
+}
+
+// 因正文过短被跳过的页面
+struct ThinPage {
+    path: String,
+    page_type: String,
+    length: usize,
+    words: usize,
+    threshold: ContentThreshold,
+}
+
+// 报告因正文过短被跳过的页面 - 大量页面被跳过通常意味着正文提取出了问题
+fn report_thin_pages(thin_pages: &[ThinPage], article_count: usize, verbose: bool) {
+    if thin_pages.is_empty() {
+        return;
+    }
+    
+    // 非详细模式下最多列出的页面数量
+    const MAX_LISTED: usize = 20;
+    
+    println!("因正文过短跳过 {} 个页面:", thin_pages.len());
+    let listed = if verbose { thin_pages.len() } else { MAX_LISTED };
+    for page in thin_pages.iter().take(listed) {
+        println!(
+            "- {} ({}): {} 字节 / {} 词，下限 {} 字节 / {} 词",
+            page.path, page.page_type, page.length, page.words, page.threshold.min_length, page.threshold.min_words
+        );
+    }
+    if thin_pages.len() > listed {
+        println!("- ……另有 {} 个页面，使用 --verbose 查看全部", thin_pages.len() - listed);
+    }
+    
+    if thin_pages.len() > article_count {
+        println!("警告: 跳过的页面多于有效文章，请检查正文提取是否正常（如主题修改后找不到 <article> 元素）");
+    }
+}
+
+// 报告更新日志页面的变化 - 非详细模式下只列出有新条目的页面
+fn report_changelog_pages(changelog: &ChangelogPages, verbose: bool) {
+    for update in changelog.updates() {
+        if verbose || update.new_entries > 0 {
+            println!(
+                "更新日志页面 {}: {} 个新增或修改的条目，索引最新的 {} / {} 个条目",
+                update.id, update.new_entries, update.indexed, update.total
+            );
+        }
+    }
+}
+
+// 扫描HTML文件并提取文章数据，同时返回因正文过短被跳过的页面
 fn scan_html_files(
     dir_path: &str, 
     verbose: bool,
-    index_all: bool
-) -> Result<(Vec<ArticleMetadata>, usize), String> {
+    index_all: bool,
+    config: &IndexerConfig,
+    changelog: &mut ChangelogPages,
+) -> Result<(Vec<ArticleMetadata>, usize, Vec<ThinPage>), String> {
     let mut articles = Vec::new();
+    let mut thin_pages = Vec::new();
     let dir_path = Path::new(dir_path);
     let mut processed_files = 0;
     
@@ -148,7 +343,7 @@ fn scan_html_files(
         let entry = entry.map_err(|e| format!("遍历目录时出错: {}", e))?;
         
         // 只处理HTML文件
-        if !entry.file_type().is_file() || !entry.path().extension().map_or(false, |ext| ext == "html") {
+        if !entry.file_type().is_file() || entry.path().extension().is_none_or(|ext| ext != "html") {
             continue;
         }
         
@@ -156,7 +351,7 @@ fn scan_html_files(
         processed_files += 1;
 
         // 解析HTML文件
-        match extract_article_from_html(entry.path(), dir_path, index_all, verbose) {
+        match extract_article_from_html(entry.path(), dir_path, index_all, verbose, config, &mut thin_pages, changelog) {
             Ok(Some(article)) => {
                 articles.push(article);
                 article_files += 1;
@@ -177,11 +372,19 @@ fn scan_html_files(
         println!("总HTML文件数: {}, 识别为文章的文件数: {}", total_files, article_files);
     }
 
-    Ok((articles, processed_files))
+    Ok((articles, processed_files, thin_pages))
 }
 
 // 从HTML文件中提取文章数据
-fn extract_article_from_html(file_path: &Path, base_dir: &Path, index_all: bool, verbose: bool) -> Result<Option<ArticleMetadata>, String> {
+fn extract_article_from_html(
+    file_path: &Path,
+    base_dir: &Path,
+    index_all: bool,
+    verbose: bool,
+    config: &IndexerConfig,
+    thin_pages: &mut Vec<ThinPage>,
+    changelog: &mut ChangelogPages,
+) -> Result<Option<ArticleMetadata>, String> {
     // 读取文件内容
     let html = fs::read_to_string(file_path)
         .map_err(|e| format!("无法读取文件 {}: {}", file_path.display(), e))?;
@@ -254,7 +457,7 @@ fn extract_article_from_html(file_path: &Path, base_dir: &Path, index_all: bool,
     
     // 计算相对路径作为文章ID
     let relative_path = file_path.strip_prefix(base_dir)
-        .map_err(|_| format!("计算相对路径失败"))?;
+        .map_err(|_| "计算相对路径失败".to_string())?;
     
     let id = relative_path.with_extension("")
         .to_string_lossy()
@@ -263,11 +466,33 @@ fn extract_article_from_html(file_path: &Path, base_dir: &Path, index_all: bool,
         .trim_end_matches('/')
         .to_string();
     
-    // 提取正文内容
-    let content = extract_content(&dom.document);
+    // 提取正文内容，更新日志页面只包含最新的条目
+    let changelog_content = config.changelog.matches(&id)
+        .then(|| find_article_element(&dom.document)
+            .or_else(|| find_main_content(&dom.document))
+            .or_else(|| find_body(&dom.document)))
+        .flatten()
+        .and_then(|root| changelog.extract_content(&id, &root, &config.content_filter));
+    let (content, changelog_headings) = match changelog_content {
+        Some((content, headings)) => (content, Some(headings)),
+        None => (extract_content(&dom.document, &config.content_filter), None),
+    };
+    
+    // 提取图片说明等媒体文本
+    let media_text = extract_media_text(&dom.document, &config.content_filter);
     
-    // 内容太少的可能不是有效内容页面
-    if content.trim().len() < 30 {
+    // 内容太少的可能不是有效内容页面，下限按页面类型配置
+    let threshold = config.content_threshold(page_type);
+    let length = content.trim().len();
+    let words = count_words(&content);
+    if length < threshold.min_length || words < threshold.min_words {
+        thin_pages.push(ThinPage {
+            path: relative_path.to_string_lossy().replace('\\', "/"),
+            page_type: page_type.to_string(),
+            length,
+            words,
+            threshold,
+        });
         return Ok(None);
     }
 
@@ -276,7 +501,10 @@ fn extract_article_from_html(file_path: &Path, base_dir: &Path, index_all: bool,
     }
 
     // 提取文章中的标题结构
-    let headings = extract_headings(&dom.document, &content);
+    let mut headings = extract_headings(&dom.document, &content, &config.content_filter);
+    if let Some(changelog_headings) = &changelog_headings {
+        headings.retain(|heading| changelog_headings.contains(&heading.text));
+    }
     
     // 构建URL
     let url = format!("/{}", id);
@@ -321,6 +549,29 @@ fn extract_article_from_html(file_path: &Path, base_dir: &Path, index_all: bool,
             Utc::now()
         });
 
+    // 检测文章语言
+    let lang = detect_language(&format!("{} {}", title, content)).to_string();
+    
+    // 文章可见性 - 来自 <meta name="visibility">，无法识别时视为公开
+    let visibility = meta_tags.get("visibility")
+        .and_then(|value| value.parse::<Visibility>().ok())
+        .unwrap_or_default();
+    
+    // 置顶文章 - 来自 <meta property="article:featured" content="true">
+    let featured = meta_tags.get("article:featured")
+        .is_some_and(|value| matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "yes"));
+    
+    // 所属系列 - 来自 <meta property="article:series" content="系列名称">
+    let series = meta_tags.get("article:series")
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    
+    // 作者 - 来自 <meta property="article:author">，没有时使用 <meta name="author">
+    let author = meta_tags.get("article:author")
+        .or_else(|| meta_tags.get("author"))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    
     // 创建文章元数据，保留原始页面类型信息，并添加标题结构
     let article = ArticleMetadata {
         id,
@@ -332,6 +583,13 @@ fn extract_article_from_html(file_path: &Path, base_dir: &Path, index_all: bool,
         content,
         page_type: page_type.to_string(),
         headings,
+        lang,
+        media_text,
+        visibility,
+        featured,
+        series,
+        author,
+        word_count: words,
     };
 
     Ok(Some(article))
@@ -365,21 +623,11 @@ fn extract_title_tag(handle: &Handle) -> Option<String> {
             }
             None
         }
-        NodeData::Element { ref name, .. } => {
-            if name.local.as_ref() == "title" {
-                // 获取文本内容
-                let mut text = String::new();
-                extract_text_from_node(handle, &mut text);
-                return Some(text.trim().to_string());
-            } else {
-                // 递归查找
-                for child in handle.children.borrow().iter() {
-                    if let Some(title) = extract_title_tag(child) {
-                        return Some(title);
-                    }
-                }
-                None
-            }
+        NodeData::Element { ref name, .. } if name.local.as_ref() == "title" => {
+            // 获取文本内容
+            let mut text = String::new();
+            extract_text_from_node(handle, &mut text);
+            Some(text.trim().to_string())
         }
         _ => {
             // 递归查找
@@ -396,21 +644,11 @@ fn extract_title_tag(handle: &Handle) -> Option<String> {
 // 从DOM中提取<h1>标签内容
 fn extract_h1_tag(handle: &Handle) -> Option<String> {
     match handle.data {
-        NodeData::Element { ref name, .. } => {
-            if name.local.as_ref() == "h1" {
-                // 获取文本内容
-                let mut text = String::new();
-                extract_text_from_node(handle, &mut text);
-                return Some(text.trim().to_string());
-            } else {
-                // 递归查找
-                for child in handle.children.borrow().iter() {
-                    if let Some(h1) = extract_h1_tag(child) {
-                        return Some(h1);
-                    }
-                }
-                None
-            }
+        NodeData::Element { ref name, .. } if name.local.as_ref() == "h1" => {
+            // 获取文本内容
+            let mut text = String::new();
+            extract_text_from_node(handle, &mut text);
+            Some(text.trim().to_string())
         }
         _ => {
             // 递归查找
@@ -486,16 +724,16 @@ fn extract_meta_tags_internal(handle: &Handle, meta_tags: &mut std::collections:
 }
 
 // 从DOM中提取正文内容
-fn extract_content(handle: &Handle) -> String {
+fn extract_content(handle: &Handle, filter: &ContentFilterConfig) -> String {
     let mut content = String::new();
     
     // 根据语义化标签顺序查找内容
     if let Some(article_element) = find_article_element(handle) {
-        extract_text_from_node_filtered(&article_element, &mut content);
+        extract_text_from_node_filtered(&article_element, filter, &mut content);
     } else if let Some(main_content) = find_main_content(handle) {
-        extract_text_from_node_filtered(&main_content, &mut content);
+        extract_text_from_node_filtered(&main_content, filter, &mut content);
     } else if let Some(body) = find_body(handle) {
-        extract_text_from_node_filtered(&body, &mut content);
+        extract_text_from_node_filtered(&body, filter, &mut content);
     }
     
     // 内联处理空格和换行
@@ -600,81 +838,86 @@ fn find_body(handle: &Handle) -> Option<Handle> {
     }
 }
 
+// 判断元素是否为非内容区域（脚本、导航、目录、交互元素等）
+fn is_non_content_element(tag_name: &str, attrs: &[Attribute]) -> bool {
+    // 对于section标签，检查是否为目录区
+    if tag_name == "section" {
+        let is_toc_section = attrs.iter().any(|attr| {
+            (attr.name.local.to_string() == "id" && 
+             (attr.value.contains("toc") || attr.value.contains("directory"))) ||
+            (attr.name.local.to_string() == "class" && 
+             (attr.value.contains("toc") || attr.value.contains("directory")))
+        });
+        
+        if is_toc_section {
+            return true;
+        }
+    }
+    
+    // 跳过交互元素和导航元素
+    let non_content_tags = [
+        // 脚本和样式
+        "script", "style", 
+        // 元数据和链接
+        "head", "meta", "link", 
+        // 语义化页面结构中的非内容区
+        "header", "footer", "nav", "aside",
+        // 其他交互元素
+        "noscript", "iframe", "svg", "path",
+        "button", "input", "form", "select", "option", "textarea", 
+        "template", "dialog", "canvas"
+    ];
+    
+    if non_content_tags.contains(&tag_name) {
+        return true;
+    }
+    
+    // 检查是否是sr-only元素（屏幕阅读器专用）
+    let is_sr_only = attrs.iter().any(|attr| {
+        attr.name.local.to_string() == "class" && 
+        attr.value.contains("sr-only")
+    });
+    
+    if is_sr_only {
+        return true;
+    }
+    
+    // 跳过其他可能的非内容区域（使用通用检测）
+    attrs.iter().any(|attr| {
+        if attr.name.local.to_string() == "class" || attr.name.local.to_string() == "id" {
+            let value = attr.value.to_string().to_lowercase();
+            value.contains("nav") || 
+            value.contains("menu") || 
+            value.contains("sidebar") || 
+            value.contains("comment") ||
+            value.contains("related") ||
+            value.contains("share") ||
+            value.contains("toc") ||
+            value.contains("directory")
+        } else {
+            false
+        }
+    })
+}
+
 // 从节点提取文本，过滤掉非内容标签
-fn extract_text_from_node_filtered(handle: &Handle, text: &mut String) {
+fn extract_text_from_node_filtered(handle: &Handle, filter: &ContentFilterConfig, text: &mut String) {
     match handle.data {
         NodeData::Element { ref name, ref attrs, .. } => {
             let tag_name = name.local.to_string();
             
-            // 跳过aside标签
-            if tag_name == "aside" {
-                return;
-            }
-            
-            // 对于section标签，检查是否为目录区
-            if tag_name == "section" {
-                let attrs = attrs.borrow();
-                let is_toc_section = attrs.iter().any(|attr| {
-                    (attr.name.local.to_string() == "id" && 
-                     (attr.value.contains("toc") || attr.value.contains("directory"))) ||
-                    (attr.name.local.to_string() == "class" && 
-                     (attr.value.contains("toc") || attr.value.contains("directory")))
-                });
-                
-                if is_toc_section {
-                    return;
-                }
-            }
-            
-            // 跳过交互元素和导航元素
-            let non_content_tags = [
-                // 脚本和样式
-                "script", "style", 
-                // 元数据和链接
-                "head", "meta", "link", 
-                // 语义化页面结构中的非内容区
-                "header", "footer", "nav", "aside",
-                // 其他交互元素
-                "noscript", "iframe", "svg", "path",
-                "button", "input", "form", "select", "option", "textarea", 
-                "template", "dialog", "canvas"
-            ];
-            
-            if non_content_tags.contains(&tag_name.as_str()) {
+            if filter.skips(&tag_name, &attrs.borrow()) {
                 return;
             }
             
-            // 检查是否是sr-only元素（屏幕阅读器专用）
-            let attrs = attrs.borrow();
-            let is_sr_only = attrs.iter().any(|attr| {
-                attr.name.local.to_string() == "class" && 
-                attr.value.contains("sr-only")
-            });
-            
-            if is_sr_only {
+            // 图片说明单独提取为媒体文本，不计入正文
+            if tag_name == "figcaption" {
                 return;
             }
             
-            // 跳过其他可能的非内容区域（使用通用检测）
-            for attr in attrs.iter() {
-                if attr.name.local.to_string() == "class" || attr.name.local.to_string() == "id" {
-                    let value = attr.value.to_string().to_lowercase();
-                    if value.contains("nav") || 
-                       value.contains("menu") || 
-                       value.contains("sidebar") || 
-                       value.contains("comment") ||
-                       value.contains("related") ||
-                       value.contains("share") ||
-                       value.contains("toc") ||
-                       value.contains("directory") {
-                        return;
-                    }
-                }
-            }
-            
             // 递归处理子节点
             for child in handle.children.borrow().iter() {
-                extract_text_from_node_filtered(child, text);
+                extract_text_from_node_filtered(child, filter, text);
             }
         }
         NodeData::Text { ref contents } => {
@@ -688,9 +931,69 @@ fn extract_text_from_node_filtered(handle: &Handle, text: &mut String) {
         _ => {
             // 递归处理子节点
             for child in handle.children.borrow().iter() {
-                extract_text_from_node_filtered(child, text);
+                extract_text_from_node_filtered(child, filter, text);
+            }
+        }
+    }
+}
+
+// 可以携带说明文字属性的媒体元素
+const MEDIA_TAGS: &[&str] = &["img", "figure", "picture", "video", "audio"];
+
+// 提取媒体说明文本 - 图片的alt/title属性和figcaption中的文字
+fn extract_media_text(handle: &Handle, filter: &ContentFilterConfig) -> String {
+    let mut parts = Vec::new();
+    
+    // 与正文使用相同的内容区域
+    let root = find_article_element(handle)
+        .or_else(|| find_main_content(handle))
+        .or_else(|| find_body(handle));
+    if let Some(root) = root {
+        collect_media_text(&root, filter, &mut parts);
+    }
+    
+    // alt与figcaption经常重复，只保留第一次出现的文本
+    let mut seen = std::collections::HashSet::new();
+    parts.retain(|part| seen.insert(part.clone()));
+    
+    parts.join(" ")
+}
+
+// 递归收集媒体说明文本
+fn collect_media_text(handle: &Handle, filter: &ContentFilterConfig, parts: &mut Vec<String>) {
+    if let NodeData::Element { ref name, ref attrs, .. } = handle.data {
+        let tag_name = name.local.to_string();
+        let attrs = attrs.borrow();
+        
+        if filter.skips(&tag_name, &attrs) {
+            return;
+        }
+        
+        if MEDIA_TAGS.contains(&tag_name.as_str()) {
+            for attr in attrs.iter() {
+                let attr_name = attr.name.local.to_string();
+                if attr_name == "alt" || attr_name == "title" {
+                    let value = attr.value.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if !value.is_empty() {
+                        parts.push(value);
+                    }
+                }
             }
         }
+        
+        if tag_name == "figcaption" {
+            let mut caption = String::new();
+            extract_text_from_node(handle, &mut caption);
+            let caption = caption.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !caption.is_empty() {
+                parts.push(caption);
+            }
+            return;
+        }
+    }
+    
+    for child in handle.children.borrow().iter() {
+        collect_media_text(child, filter, parts);
     }
 }
 
@@ -711,20 +1014,20 @@ fn extract_text_from_node(handle: &Handle, text: &mut String) {
 }
 
 // 从HTML内容中提取标题结构
-fn extract_headings(handle: &Handle, content: &str) -> Vec<Heading> {
+fn extract_headings(handle: &Handle, content: &str, filter: &ContentFilterConfig) -> Vec<Heading> {
     let mut headings = Vec::new();
     
     // 首先尝试从article标签提取标题 - 这是语义化的文章内容区
     if let Some(article_element) = find_article_element(handle) {
         // 只从文章主体提取标题，避免其他区域
-        extract_headings_from_element(&article_element, &mut headings, 0);
+        extract_headings_from_element(&article_element, filter, &mut headings, 0);
     } else {
         // 备选：如果找不到article标签，尝试从main提取
         if let Some(main_element) = find_main_content(handle) {
-            extract_headings_from_element(&main_element, &mut headings, 0);
+            extract_headings_from_element(&main_element, filter, &mut headings, 0);
         } else {
             // 最后的备选：从整个文档提取，但排除header、aside、section
-            extract_headings_internal(handle, &mut headings, 0);
+            extract_headings_internal(handle, filter, &mut headings, 0);
         }
     }
     
@@ -753,31 +1056,43 @@ fn extract_headings(handle: &Handle, content: &str) -> Vec<Heading> {
     headings
 }
 // 从指定元素提取标题（通常是article标签）
-fn extract_headings_from_element(handle: &Handle, headings: &mut Vec<Heading>, position: usize) {
+fn extract_headings_from_element(handle: &Handle, filter: &ContentFilterConfig, headings: &mut Vec<Heading>, position: usize) {
     match handle.data {
-        NodeData::Element { ref name, .. } => {
+        NodeData::Element { ref name, ref attrs, .. } => {
             let tag_name = name.local.to_string();
             
+            // 跳过配置排除的区域
+            if filter.excludes(&attrs.borrow()) {
+                return;
+            }
+            
             // 检查是否是标题标签
             if tag_name.starts_with('h') && tag_name.len() == 2 {
                 if let Some(level) = tag_name.chars().nth(1).unwrap_or('0').to_digit(10) {
-                    if level >= 1 && level <= 6 {
+                    if (1..=6).contains(&level) {
                         // 提取标题文本
                         let mut title_text = String::new();
                         extract_text_from_node(handle, &mut title_text);
                         
                         let trimmed_text = title_text.trim().to_string();
                         
+                        // 去除锚点符号并合并空白
+                        let normalized_text = normalize_heading_text(&trimmed_text);
+                        
                         // 只添加非空标题
-                        if !trimmed_text.is_empty() {
+                        if !normalized_text.is_empty() {
                             // 检查是否重复
-                            if !headings.iter().any(|h| h.text == trimmed_text) {
+                            if !headings.iter().any(|h| h.text == normalized_text) {
+                                // 规范化改变了文本时保留原文
+                                let raw_text = (normalized_text != trimmed_text).then_some(trimmed_text);
+                                
                                 // 创建标题对象
                                 headings.push(Heading {
                                     level: level as usize,
-                                    text: trimmed_text,
+                                    text: normalized_text,
                                     position,
                                     end_position: None, // 稍后填充
+                                    raw_text,
                                 });
                             }
                         }
@@ -787,24 +1102,29 @@ fn extract_headings_from_element(handle: &Handle, headings: &mut Vec<Heading>, p
             
             // 递归处理子节点
             for child in handle.children.borrow().iter() {
-                extract_headings_from_element(child, headings, position);
+                extract_headings_from_element(child, filter, headings, position);
             }
         }
         _ => {
             // 递归处理子节点
             for child in handle.children.borrow().iter() {
-                extract_headings_from_element(child, headings, position);
+                extract_headings_from_element(child, filter, headings, position);
             }
         }
     }
 }
 
 // 递归辅助函数，提取标题标签 (h1, h2, h3, etc.)，同时排除非内容区域
-fn extract_headings_internal(handle: &Handle, headings: &mut Vec<Heading>, position: usize) {
+fn extract_headings_internal(handle: &Handle, filter: &ContentFilterConfig, headings: &mut Vec<Heading>, position: usize) {
     match handle.data {
         NodeData::Element { ref name, ref attrs, .. } => {
             let tag_name = name.local.to_string();
             
+            // 跳过配置排除的区域
+            if filter.excludes(&attrs.borrow()) {
+                return;
+            }
+            
             // 排除header、aside、section(目录)标签区域
             if tag_name == "header" || tag_name == "aside" || tag_name == "section" {
                 // 检查section是否是目录区域
@@ -819,7 +1139,7 @@ fn extract_headings_internal(handle: &Handle, headings: &mut Vec<Heading>, posit
                     // 如果不是目录，可以递归处理
                     if !is_toc {
                         for child in handle.children.borrow().iter() {
-                            extract_headings_internal(child, headings, position);
+                            extract_headings_internal(child, filter, headings, position);
                         }
                     }
                 }
@@ -841,23 +1161,30 @@ fn extract_headings_internal(handle: &Handle, headings: &mut Vec<Heading>, posit
             // 处理标题标签
             if tag_name.starts_with('h') && tag_name.len() == 2 {
                 if let Some(level) = tag_name.chars().nth(1).unwrap_or('0').to_digit(10) {
-                    if level >= 1 && level <= 6 {
+                    if (1..=6).contains(&level) {
                         // 提取标题文本
                         let mut title_text = String::new();
                         extract_text_from_node(handle, &mut title_text);
                         
                         let trimmed_text = title_text.trim().to_string();
                         
+                        // 去除锚点符号并合并空白
+                        let normalized_text = normalize_heading_text(&trimmed_text);
+                        
                         // 只添加非空标题
-                        if !trimmed_text.is_empty() {
+                        if !normalized_text.is_empty() {
                             // 检查是否重复
-                            if !headings.iter().any(|h| h.text == trimmed_text) {
+                            if !headings.iter().any(|h| h.text == normalized_text) {
+                                // 规范化改变了文本时保留原文
+                                let raw_text = (normalized_text != trimmed_text).then_some(trimmed_text);
+                                
                                 // 创建标题对象
                                 headings.push(Heading {
                                     level: level as usize,
-                                    text: trimmed_text,
+                                    text: normalized_text,
                                     position,
                                     end_position: None, // 稍后填充
+                                    raw_text,
                                 });
                             }
                         }
@@ -867,13 +1194,13 @@ fn extract_headings_internal(handle: &Handle, headings: &mut Vec<Heading>, posit
             
             // 递归处理子节点
             for child in handle.children.borrow().iter() {
-                extract_headings_internal(child, headings, position);
+                extract_headings_internal(child, filter, headings, position);
             }
         }
         _ => {
             // 递归处理子节点
             for child in handle.children.borrow().iter() {
-                extract_headings_internal(child, headings, position);
+                extract_headings_internal(child, filter, headings, position);
             }
         }
     }
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::Path;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use utils_common::compression::read_index_metadata;
+
+/// 索引清单文件名 - 写入输出目录，与索引文件放在一起
+pub const MANIFEST_FILE_NAME: &str = "index_manifest.json";
+
+/// 索引清单 - 描述本次生成的各个索引文件
+///
+/// 前端可以先获取清单，用内容哈希作为缓存键（如 `filter_index.bin?v=<sha256前缀>`），
+/// 并把 `integrity` 传给 `fetch` 校验下载的文件。
+#[derive(Serialize, Debug)]
+pub struct IndexManifest {
+    /// 清单生成时间
+    pub generated_at: DateTime<Utc>,
+    /// 索引文件，按写入顺序排列
+    pub files: Vec<ManifestEntry>,
+}
+
+/// 清单中的一个文件
+#[derive(Serialize, Debug)]
+pub struct ManifestEntry {
+    /// 相对于输出目录的文件路径
+    pub path: String,
+    /// 文件大小（字节）
+    pub bytes: u64,
+    /// 索引格式版本（如 "8.0"），没有头部的文件（如拆分的内容文件）为空
+    pub version: Option<String>,
+    /// 索引包含的文章数量，没有头部的文件为空
+    pub article_count: Option<usize>,
+    /// 文件内容的SHA-256摘要（十六进制）
+    pub sha256: String,
+    /// 子资源完整性值（`sha256-` 加上Base64编码的摘要），可直接用于 `fetch` 的 `integrity` 选项
+    pub integrity: String,
+}
+
+impl IndexManifest {
+    pub fn new() -> Self {
+        Self {
+            generated_at: Utc::now(),
+            files: Vec::new(),
+        }
+    }
+
+    /// 读取输出目录中的文件并加入清单，索引头部的版本和文章数量一并记录
+    pub fn add_file(&mut self, output_dir: &str, path: &str) -> Result<(), String> {
+        let full_path = Path::new(output_dir).join(path);
+        let data = fs::read(&full_path)
+            .map_err(|e| format!("无法读取索引文件 {}: {}", full_path.display(), e))?;
+
+        // 文件刚由本次构建写入，不限制版本；没有头部或无法识别的文件只记录大小和哈希
+        let metadata = read_index_metadata(&data, u8::MAX).ok().flatten();
+        // 与浏览器 `crypto.subtle.digest("SHA-256")` 的结果相同，前端可以自行校验
+        let digest = Sha256::digest(&data);
+
+        self.files.push(ManifestEntry {
+            path: path.to_string(),
+            bytes: data.len() as u64,
+            version: metadata.as_ref().map(|metadata| metadata.version.clone()),
+            article_count: metadata.as_ref().map(|metadata| metadata.article_count),
+            sha256: digest.iter().map(|byte| format!("{:02x}", byte)).collect(),
+            integrity: format!("sha256-{}", BASE64.encode(digest)),
+        });
+        Ok(())
+    }
+
+    /// 保存清单到输出目录
+    pub fn save(&self, output_dir: &str) -> Result<(), String> {
+        let path = Path::new(output_dir).join(MANIFEST_FILE_NAME);
+        let data = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("序列化索引清单失败: {}", e))?;
+        fs::write(&path, data)
+            .map_err(|e| format!("无法写入索引清单 {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,71 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use crate::config::TokenizerConfig;
+
+/// 外部分词器 - 在子进程中运行分词程序，整个构建过程只启动一次
+///
+/// 通信协议为逐行JSON：每次向标准输入写入一行JSON字符串（待分词的文本），
+/// 程序从标准输出返回一行JSON字符串数组（词汇列表）。
+pub struct ExternalTokenizer {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    identity: String,
+}
+
+impl ExternalTokenizer {
+    /// 启动分词程序
+    pub fn spawn(config: &TokenizerConfig) -> Result<Self, String> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("无法启动分词程序 {}: {}", config.command, e))?;
+
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take()
+            .ok_or_else(|| format!("无法读取分词程序输出: {}", config.command))?;
+
+        Ok(ExternalTokenizer {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            identity: config.identity(),
+        })
+    }
+
+    /// 分词器标识
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    /// 对文本分词
+    pub fn tokenize(&mut self, text: &str) -> Result<Vec<String>, String> {
+        let request = serde_json::to_string(text)
+            .map_err(|e| format!("序列化分词请求失败: {}", e))?;
+
+        let stdin = self.stdin.as_mut().ok_or("分词程序已关闭")?;
+        writeln!(stdin, "{}", request)
+            .and_then(|_| stdin.flush())
+            .map_err(|e| format!("写入分词程序失败: {}", e))?;
+
+        let mut response = String::new();
+        let read = self.stdout.read_line(&mut response)
+            .map_err(|e| format!("读取分词程序输出失败: {}", e))?;
+        if read == 0 {
+            return Err("分词程序意外退出".to_string());
+        }
+
+        serde_json::from_str(&response)
+            .map_err(|e| format!("解析分词结果失败: {}", e))
+    }
+}
+
+impl Drop for ExternalTokenizer {
+    fn drop(&mut self) {
+        // 关闭标准输入通知程序退出，再回收子进程
+        self.stdin.take();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,67 @@
+// 地理索引构建器 - 在构建站点时把世界和中国地图的GeoJSON及已访问地点转换为压缩的地理索引，
+// 浏览器通过 GeoProcessor::load_binary 加载，不需要下载和解析GeoJSON。
+
+use std::fs;
+use chrono::Utc;
+use utils_common::compression::{to_compressed_with_format, SerializationFormat};
+use utils_common::models::{IndexMetadata, IndexType};
+use crate::models::GeoIndex;
+use crate::INDEX_VERSION;
+
+// 地理索引构建器
+#[derive(Default)]
+pub struct GeoIndexBuilder {
+    world_json: String,
+    china_json: String,
+    visited_places: Vec<String>,
+    // 保存索引文件使用的序列化格式
+    format: SerializationFormat,
+}
+
+impl GeoIndexBuilder {
+    // 由世界地图和中国地图的GeoJSON文本创建构建器
+    pub fn new(world_json: String, china_json: String) -> Self {
+        Self {
+            world_json,
+            china_json,
+            ..Self::default()
+        }
+    }
+
+    // 设置已访问地点（与 process_geojson 的已访问地点相同，如 "日本"、"中国-北京"）
+    pub fn set_visited_places(&mut self, visited_places: Vec<String>) {
+        self.visited_places = visited_places;
+    }
+
+    // 设置保存索引文件使用的序列化格式，默认为bincode
+    pub fn set_format(&mut self, format: SerializationFormat) {
+        self.format = format;
+    }
+
+    // 构建地理索引
+    pub fn build_geo_index(&self) -> Result<GeoIndex, String> {
+        GeoIndex::from_geojson(&self.world_json, &self.china_json, &self.visited_places)
+    }
+
+    // 构建地理索引并压缩保存到文件，头部写入索引元数据
+    pub fn save_geo_index(&self, path: &str) -> Result<(), String> {
+        let geo_index = self.build_geo_index()?;
+
+        let metadata = IndexMetadata {
+            article_count: 0,
+            tag_count: 0,
+            created_at: Utc::now(),
+            version: format!("{}.{}", INDEX_VERSION[0], INDEX_VERSION[1]),
+            index_type: IndexType::Geo,
+            token_count: 0,
+        };
+        let data = to_compressed_with_format(&geo_index, INDEX_VERSION, Some(&metadata), self.format)
+            .map_err(|e| format!("压缩地理索引失败: {}", e))?;
+        fs::write(path, &data)
+            .map_err(|e| format!("无法写入地理索引文件 {}: {}", path, e))?;
+
+        let visited = geo_index.regions.iter().filter(|region| region.is_visited).count();
+        println!("地理索引已写入: {}，区域: {}（已访问 {}），大小: {} 字节", path, geo_index.regions.len(), visited, data.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+// 已访问数据对比 - 已访问地点更新后，找出状态变化的区域及其边界线，
+// 前端只需为变化的区域播放颜色过渡动画，无需重新渲染整个地球。
+// 两份快照中不属于任何已处理区域的名称会被忽略。
+
+use std::collections::{BTreeMap, BTreeSet};
+use serde::Serialize;
+use crate::GeoProcessor;
+
+// 区域及其边界线在 get_boundary_lines 结果中的下标
+#[derive(Clone, Debug, Serialize)]
+pub struct RegionLines {
+    pub name: String,
+    pub line_indices: Vec<usize>,
+}
+
+// 两份已访问快照的差异，各列表按区域名称排序
+#[derive(Clone, Debug, Serialize)]
+pub struct VisitedDiff {
+    // 新访问的区域
+    pub newly_visited: Vec<RegionLines>,
+    // 不再标记为已访问的区域
+    pub no_longer_visited: Vec<RegionLines>,
+    // 两份快照中都已访问的区域
+    pub unchanged: Vec<RegionLines>,
+}
+
+// 对比两份已访问地点列表
+pub(crate) fn diff_visited(processor: &GeoProcessor, old_visited: &[String], new_visited: &[String]) -> VisitedDiff {
+    let known = |name: &&String| processor.regions.contains_key(name.as_str());
+    let old: BTreeSet<&String> = old_visited.iter().filter(known).collect();
+    let new: BTreeSet<&String> = new_visited.iter().filter(known).collect();
+
+    let mut lines: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (index, line) in processor.boundary_lines.iter().enumerate() {
+        lines.entry(line.region_name.as_str()).or_default().push(index);
+    }
+    let region_lines = |names: Vec<&String>| -> Vec<RegionLines> {
+        names.into_iter()
+            .map(|name| RegionLines {
+                name: name.clone(),
+                line_indices: lines.get(name.as_str()).cloned().unwrap_or_default(),
+            })
+            .collect()
+    };
+
+    VisitedDiff {
+        newly_visited: region_lines(new.difference(&old).copied().collect()),
+        no_longer_visited: region_lines(old.difference(&new).copied().collect()),
+        unchanged: region_lines(old.intersection(&new).copied().collect()),
+    }
+}
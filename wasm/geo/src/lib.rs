@@ -1,11 +1,34 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use geojson::{Feature, GeoJson, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::f64::consts::PI;
 use kdtree::KdTree;
 use kdtree::distance::squared_euclidean;
-use serde_wasm_bindgen;
+
+mod scene;
+use scene::{BoundaryBuffer, SceneDescriptor};
+mod tour;
+mod diff;
+mod stats;
+mod search;
+mod precision;
+pub mod polygon;
+pub mod simplify;
+mod prepared;
+use prepared::PreparedGeo;
+use polygon::{vector3_to_lat_long, RegionPolygon};
+pub mod models;
+use models::GeoIndex;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod builder;
+
+// 构建器写入的地理索引版本，索引结构变化时递增主版本号
+pub const INDEX_VERSION: [u8; 2] = [2, 0];
+// 支持的最大地理索引版本
+pub const MAX_INDEX_VERSION: u8 = INDEX_VERSION[0];
+// 支持的最小地理索引版本，更早的索引需要重新生成
+pub const MIN_INDEX_VERSION: u8 = 2;
+pub use precision::{BoundaryPoints, CoordinatePrecision, Position, Vector3f};
 
 // 初始化错误处理
 #[wasm_bindgen(start)]
@@ -90,6 +113,44 @@ impl BoundingBox {
     }
 }
 
+// 最近区域查找使用的距离度量
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DistanceMetric {
+    // 三维空间中的直线距离（默认，与早期版本一致）
+    #[default]
+    Euclidean,
+    // 投影到单位球面后的弦长，不受查询点半径影响
+    Chord,
+    // 大圆距离（弧度），由单位向量点积的反余弦计算
+    GreatCircle,
+}
+
+// 单位化向量，零向量保持不变
+fn unit_vector(v: &Vector3) -> [f64; 3] {
+    let len = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+    if len == 0.0 {
+        [v.x, v.y, v.z]
+    } else {
+        [v.x / len, v.y / len, v.z / len]
+    }
+}
+
+// 按度量计算两点间的距离
+fn metric_distance(metric: DistanceMetric, a: &Vector3, b: &Vector3) -> f64 {
+    match metric {
+        DistanceMetric::Euclidean => {
+            squared_euclidean(&[a.x, a.y, a.z], &[b.x, b.y, b.z]).sqrt()
+        }
+        DistanceMetric::Chord => squared_euclidean(&unit_vector(a), &unit_vector(b)).sqrt(),
+        DistanceMetric::GreatCircle => {
+            let (ua, ub) = (unit_vector(a), unit_vector(b));
+            let dot = ua[0] * ub[0] + ua[1] * ub[1] + ua[2] * ub[2];
+            dot.clamp(-1.0, 1.0).acos()
+        }
+    }
+}
+
 // 区域信息结构
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RegionInfo {
@@ -97,12 +158,15 @@ pub struct RegionInfo {
     pub is_visited: bool,
     pub center: Vector3,
     pub bounding_box: BoundingBox,
+    // 区域各多边形外环的原始顶点数和简化后（第0级）边界线的顶点数，用于评估简化容差
+    pub vertex_count: usize,
+    pub simplified_vertex_count: usize,
 }
 
 // 表示带有属性的边界线的结构
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BoundaryLine {
-    pub points: Vec<Vector3>,
+    pub points: BoundaryPoints,
     pub region_name: String,
     pub is_visited: bool,
 }
@@ -110,9 +174,74 @@ pub struct BoundaryLine {
 // 地理处理器
 #[wasm_bindgen]
 pub struct GeoProcessor {
+    // 区域中心点方向（单位向量）的KD树
     region_tree: Option<KdTree<f64, String, [f64; 3]>>,
     regions: HashMap<String, RegionInfo>,
     boundary_lines: Vec<BoundaryLine>,
+    // 较粗细节级别（第1级起）的边界线，边界线的顺序与 boundary_lines 相同
+    lod_boundary_lines: Vec<Vec<BoundaryLine>>,
+    // 区域邻接图：共享边界顶点的区域互为相邻
+    adjacency: BTreeMap<String, BTreeSet<String>>,
+    // 最近区域查找使用的距离度量
+    distance_metric: DistanceMetric,
+    // 边界线和标记的存储/输出精度
+    precision: CoordinatePrecision,
+    // 区域的其他名称（全称、各语言名称等），用于按名称搜索区域
+    region_aliases: BTreeMap<String, Vec<String>>,
+    // 各区域的原始多边形（经纬度），用于判断点所在的区域
+    region_polygons: Vec<RegionPolygon>,
+}
+
+// 各细节级别边界线的简化容差（度），第0级为完整细节，级别越大越粗略
+const LOD_TOLERANCES: [f64; 3] = [0.0, 0.1, 0.5];
+
+// 将经纬度转换为三维坐标
+fn lat_long_to_vector3(lat: f64, lon: f64, radius: f64) -> Vector3 {
+    // 确保经度在 -180 到 180 之间
+    let lon = if lon > 180.0 { lon - 360.0 } else if lon < -180.0 { lon + 360.0 } else { lon };
+    
+    let phi = (90.0 - lat) * PI / 180.0;
+    let theta = (lon + 180.0) * PI / 180.0;
+
+    let x = -radius * phi.sin() * theta.cos();
+    let y = radius * phi.cos();
+    let z = radius * phi.sin() * theta.sin();
+
+    Vector3 { x, y, z }
+}
+
+// 贪心图着色：按度数从高到低（同度数按名称）依次为区域选择相邻区域未使用的最小颜色编号。
+// 调色板不足时选择相邻区域中使用次数最少的颜色，尽量减少冲突。
+fn greedy_coloring(adjacency: &BTreeMap<String, BTreeSet<String>>, palette_size: usize) -> BTreeMap<String, usize> {
+    let mut colors = BTreeMap::new();
+    if palette_size == 0 {
+        return colors;
+    }
+    
+    let mut order: Vec<&String> = adjacency.keys().collect();
+    order.sort_by(|a, b| adjacency[*b].len().cmp(&adjacency[*a].len()).then_with(|| a.cmp(b)));
+    
+    for name in order {
+        let mut used = vec![0usize; palette_size];
+        for neighbor in &adjacency[name] {
+            if let Some(&color) = colors.get(neighbor) {
+                used[color] += 1;
+            }
+        }
+        
+        let color = used.iter().position(|&count| count == 0).unwrap_or_else(|| {
+            (0..palette_size).min_by_key(|&color| used[color]).unwrap_or(0)
+        });
+        colors.insert(name.clone(), color);
+    }
+    
+    colors
+}
+
+impl Default for GeoProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[wasm_bindgen]
@@ -123,232 +252,134 @@ impl GeoProcessor {
             region_tree: None,
             regions: HashMap::new(),
             boundary_lines: Vec::new(),
+            lod_boundary_lines: Vec::new(),
+            adjacency: BTreeMap::new(),
+            distance_metric: DistanceMetric::default(),
+            precision: CoordinatePrecision::default(),
+            region_aliases: BTreeMap::new(),
+            region_polygons: Vec::new(),
         }
     }
 
     // 将经纬度转换为三维坐标
     pub fn lat_long_to_vector3(&self, lat: f64, lon: f64, radius: f64) -> Vector3 {
-        // 确保经度在 -180 到 180 之间
-        let lon = if lon > 180.0 { lon - 360.0 } else if lon < -180.0 { lon + 360.0 } else { lon };
-        
-        let phi = (90.0 - lat) * PI / 180.0;
-        let theta = (lon + 180.0) * PI / 180.0;
-
-        let x = -radius * phi.sin() * theta.cos();
-        let y = radius * phi.cos();
-        let z = radius * phi.sin() * theta.sin();
-
-        Vector3 { x, y, z }
+        lat_long_to_vector3(lat, lon, radius)
     }
 
     // 处理GeoJSON数据并构建优化的空间索引和边界线
+    // precision 指定边界线和标记的存储/输出精度，省略时使用双精度
+    // tolerance 为边界线简化容差（度），省略或为0时不简化
     #[wasm_bindgen]
-    pub fn process_geojson(&mut self, world_json: &str, china_json: &str, visited_places_json: &str, scale: f64) -> Result<(), JsValue> {
+    pub fn process_geojson(
+        &mut self,
+        world_json: &str,
+        china_json: &str,
+        visited_places_json: &str,
+        scale: f64,
+        precision: Option<CoordinatePrecision>,
+        tolerance: Option<f64>,
+    ) -> Result<(), JsValue> {
         // 解析访问过的地点
         let visited_places: Vec<String> = serde_json::from_str(visited_places_json)
             .map_err(|e| JsValue::from_str(&format!("Error parsing visited places: {}", e)))?;
         
-        // 解析世界数据
-        let world_geojson: GeoJson = world_json.parse()
-            .map_err(|e| JsValue::from_str(&format!("Error parsing world GeoJSON: {}", e)))?;
-        
-        // 解析中国数据
-        let china_geojson: GeoJson = china_json.parse()
-            .map_err(|e| JsValue::from_str(&format!("Error parsing China GeoJSON: {}", e)))?;
-        
-        // 创建空间索引
-        let mut region_tree = KdTree::new(3);
-        let mut regions = HashMap::new();
-        let mut boundary_lines = Vec::new();
+        // 解析世界和中国地图数据
+        let index = GeoIndex::from_geojson(world_json, china_json, &visited_places)
+            .map_err(|e| JsValue::from_str(&e))?;
         
-        // 处理世界地图的特征
-        if let GeoJson::FeatureCollection(collection) = world_geojson {
-            for feature in collection.features {
-                // 跳过中国，因为会用更详细的中国地图数据
-                if let Some(props) = &feature.properties {
-                    if let Some(serde_json::Value::String(name)) = props.get("name") {
-                        if name == "中国" {
-                            continue;
-                        }
-                        
-                        self.process_feature(&feature, &visited_places, None, scale, 
-                                             &mut region_tree, &mut regions, &mut boundary_lines)?;
-                    }
-                }
-            }
-        }
-        
-        // 处理中国地图数据
-        if let GeoJson::FeatureCollection(collection) = china_geojson {
-            for feature in collection.features {
-                self.process_feature(&feature, &visited_places, Some("中国"), scale, 
-                                     &mut region_tree, &mut regions, &mut boundary_lines)?;
-            }
-        }
-        
-        // 保存处理结果
-        self.region_tree = Some(region_tree);
-        self.regions = regions;
-        self.boundary_lines = boundary_lines;
-        
-        Ok(())
+        self.load_index(&index, scale, precision.unwrap_or_default(), tolerance.unwrap_or(0.0))
     }
     
-    // 处理单个地理特征
-    fn process_feature(
-        &self,
-        feature: &Feature,
-        visited_places: &[String],
-        parent_name: Option<&str>,
+    // 加载原生构建器生成的地理索引，跳过GeoJSON解析，结果与 process_geojson 相同
+    // visited_places_json 不为空时代替构建时记录的已访问地点
+    #[wasm_bindgen]
+    pub fn load_binary(
+        &mut self,
+        data: &[u8],
         scale: f64,
-        region_tree: &mut KdTree<f64, String, [f64; 3]>,
-        regions: &mut HashMap<String, RegionInfo>,
-        boundary_lines: &mut Vec<BoundaryLine>
+        precision: Option<CoordinatePrecision>,
+        visited_places_json: Option<String>,
+        tolerance: Option<f64>,
     ) -> Result<(), JsValue> {
-        if let Some(props) = &feature.properties {
-            if let Some(serde_json::Value::String(name)) = props.get("name") {
-                // 确定完整的区域名称
-                let region_name = if let Some(parent) = parent_name {
-                    format!("{}-{}", parent, name)
-                } else {
-                    name.clone()
-                };
-                
-                // 检查是否已访问
-                let is_visited = visited_places.contains(&region_name);
-                
-                // 处理几何体
-                if let Some(geom) = &feature.geometry {
-                    match &geom.value {
-                        Value::Polygon(polygon) => {
-                            self.process_polygon(polygon, &region_name, is_visited, scale, 
-                                                 region_tree, regions, boundary_lines)?;
-                        }
-                        Value::MultiPolygon(multi_polygon) => {
-                            for polygon in multi_polygon {
-                                self.process_polygon(polygon, &region_name, is_visited, scale, 
-                                                     region_tree, regions, boundary_lines)?;
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
+        let mut index = GeoIndex::from_compressed(data)
+            .map_err(|e| JsValue::from_str(&format!("Error parsing geo index: {}", e)))?;
+        
+        if let Some(visited_places_json) = visited_places_json {
+            let visited_places: Vec<String> = serde_json::from_str(&visited_places_json)
+                .map_err(|e| JsValue::from_str(&format!("Error parsing visited places: {}", e)))?;
+            index.set_visited(&visited_places);
         }
-        Ok(())
+        
+        self.load_index(&index, scale, precision.unwrap_or_default(), tolerance.unwrap_or(0.0))
     }
     
-    // 处理多边形
-    fn process_polygon(
-        &self,
-        polygon: &Vec<Vec<Vec<f64>>>,
-        region_name: &str,
-        is_visited: bool,
-        scale: f64,
-        region_tree: &mut KdTree<f64, String, [f64; 3]>,
-        regions: &mut HashMap<String, RegionInfo>,
-        boundary_lines: &mut Vec<BoundaryLine>
-    ) -> Result<(), JsValue> {
-        if polygon.is_empty() || polygon[0].is_empty() {
-            return Ok(());
+    // 由地理索引中预处理的数据按缩放比例和精度生成空间索引和边界线
+    fn load_index(&mut self, index: &GeoIndex, scale: f64, precision: CoordinatePrecision, tolerance: f64) -> Result<(), JsValue> {
+        if !tolerance.is_finite() || tolerance < 0.0 {
+            return Err(JsValue::from_str(&format!("Invalid simplification tolerance: {}", tolerance)));
         }
         
-        // 外环
-        let exterior = &polygon[0];
-        
-        // 计算中心点和边界盒
-        let mut center_lon = 0.0;
-        let mut center_lat = 0.0;
-        let mut count = 0;
-        
-        let mut min_x = f64::INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut min_z = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
-        let mut max_z = f64::NEG_INFINITY;
-        
-        let mut points = Vec::new();
+        // 各细节级别的容差不小于调用方指定的容差，与预处理时的容差不同时重新计算
+        let tolerances: Vec<f64> = LOD_TOLERANCES.iter().map(|&level| level.max(tolerance)).collect();
+        let rebuilt;
+        let prepared = if index.prepared.tolerances == tolerances {
+            &index.prepared
+        } else {
+            rebuilt = PreparedGeo::build(&index.regions, &tolerances).map_err(|e| JsValue::from_str(&e))?;
+            &rebuilt
+        };
         
-        // 遍历所有点
-        for point in exterior {
-            if point.len() >= 2 {
-                let lon = point[0];
-                let lat = point[1];
-                
-                center_lon += lon;
-                center_lat += lat;
-                count += 1;
-                
-                // 转换为3D坐标
-                let vertex = self.lat_long_to_vector3(lat, lon, scale);
-                points.push(vertex);
-                
-                // 更新边界盒
-                min_x = min_x.min(vertex.x);
-                min_y = min_y.min(vertex.y);
-                min_z = min_z.min(vertex.z);
-                max_x = max_x.max(vertex.x);
-                max_y = max_y.max(vertex.y);
-                max_z = max_z.max(vertex.z);
-            }
-        }
+        let visited: HashMap<&str, bool> = index.regions.iter()
+            .map(|region| (region.name.as_str(), region.is_visited))
+            .collect();
+        let mut levels = prepared.scaled_boundary_lines(&index.regions, scale, precision)
+            .map_err(|e| JsValue::from_str(&e))?;
         
-        if count > 0 {
-            // 计算中心点
-            center_lon /= count as f64;
-            center_lat /= count as f64;
-            
-            // 创建中心点3D坐标
-            let center = self.lat_long_to_vector3(center_lat, center_lon, scale + 0.005);
-            
-            // 创建边界盒
-            let bounding_box = BoundingBox {
-                min_x, min_y, min_z,
-                max_x, max_y, max_z,
-            };
-            
-            // 保存区域信息
-            let region_info = RegionInfo {
-                name: region_name.to_string(),
-                is_visited,
-                center,
-                bounding_box,
-            };
-            
-            // 添加到区域索引
-            regions.insert(region_name.to_string(), region_info);
-            
-            // 添加到KD树
-            let coord_key = [center.x, center.y, center.z];
-            region_tree.add(coord_key, region_name.to_string())
-                .map_err(|e| JsValue::from_str(&format!("Error adding to KD tree: {}", e)))?;
-            
-            // 创建边界线
-            if points.len() > 1 {
-                let boundary_line = BoundaryLine {
-                    points,
-                    region_name: region_name.to_string(),
-                    is_visited,
-                };
-                
-                boundary_lines.push(boundary_line);
-            }
-        }
+        self.region_tree = Some(prepared.region_tree.clone());
+        self.regions = prepared.scaled_regions(scale, &visited);
+        self.adjacency = prepared.adjacency.clone();
+        self.boundary_lines = levels.remove(0);
+        self.lod_boundary_lines = levels;
+        self.precision = precision;
+        // 记录其他名称，用于按名称搜索
+        self.region_aliases = index.regions.iter()
+            .filter(|region| !region.aliases.is_empty())
+            .map(|region| (region.name.clone(), region.aliases.clone()))
+            .collect();
+        self.region_polygons = index.regions.iter()
+            .flat_map(|region| region.polygons.iter()
+                .filter_map(|polygon| RegionPolygon::from_rings(&region.name, polygon)))
+            .collect();
         
         Ok(())
     }
     
-    // 查找最近的国家/地区
+    // 设置最近区域查找使用的距离度量
+    #[wasm_bindgen]
+    pub fn set_distance_metric(&mut self, metric: DistanceMetric) {
+        self.distance_metric = metric;
+    }
+    
+    // 获取当前的距离度量
+    #[wasm_bindgen]
+    pub fn get_distance_metric(&self) -> DistanceMetric {
+        self.distance_metric
+    }
+    
+    // 获取边界线和标记的存储/输出精度
+    #[wasm_bindgen]
+    pub fn get_precision(&self) -> CoordinatePrecision {
+        self.precision
+    }
+    
+    // 查找点所在或最近的国家/地区：先按原始多边形判断点所在的区域，
+    // 不在任何区域内（如海洋中）时再按中心点距离查找最近的区域
     #[wasm_bindgen]
     pub fn find_nearest_country(&self, point_x: f64, point_y: f64, point_z: f64, _radius: f64) -> Option<String> {
         let point = Vector3 { x: point_x, y: point_y, z: point_z };
         
-        // 先检查点是否在任何边界盒内
-        for (name, region) in &self.regions {
-            if region.bounding_box.distance_to_point(&point) < 0.001 {
-                return Some(name.clone());
-            }
+        if let Some(name) = self.find_containing_region(&point) {
+            return Some(name);
         }
         
         // 全局最近区域
@@ -360,12 +391,15 @@ impl GeoProcessor {
         // KD树搜索的数量
         const K_NEAREST: usize = 10;
         
-        // 使用KD树搜索最近的区域
+        // 使用KD树搜索最近的区域：树中保存的是单位向量，按弦长检索候选，
+        // 再用选定的度量计算距离，小区域加权也使用同一度量
         if let Some(tree) = &self.region_tree {
-            if let Ok(nearest) = tree.nearest(&[point.x, point.y, point.z], K_NEAREST, &squared_euclidean) {
-                for (dist, name) in nearest {
-                    // 转换距离
-                    let distance = dist.sqrt();
+            if let Ok(nearest) = tree.nearest(&unit_vector(&point), K_NEAREST, &squared_euclidean) {
+                for (_, name) in nearest {
+                    let Some(center) = self.regions.get(name).map(|region| region.center) else {
+                        continue;
+                    };
+                    let distance = metric_distance(self.distance_metric, &point, &center);
                     
                     // 检查是否更接近
                     if distance < min_distance {
@@ -407,7 +441,11 @@ impl GeoProcessor {
                 
                 for (region_name, region) in &self.regions {
                     if region_name.starts_with("中国-") {
-                        let distance = region.bounding_box.distance_to_point(&point);
+                        // 直线距离使用边界盒，其他度量使用省份中心点，与上面的距离保持可比
+                        let distance = match self.distance_metric {
+                            DistanceMetric::Euclidean => region.bounding_box.distance_to_point(&point),
+                            metric => metric_distance(metric, &point, &region.center),
+                        };
                         if distance < min_province_distance {
                             min_province_distance = distance;
                             closest_province = Some(region_name.clone());
@@ -432,9 +470,104 @@ impl GeoProcessor {
         serde_wasm_bindgen::to_value(&self.boundary_lines).unwrap_or(JsValue::NULL)
     }
     
-    // 获取所有区域信息
+    // 获取指定细节级别的边界线：0为完整细节，级别越大越粗略，超出范围时使用最粗的级别
+    // 各级别边界线的顺序与 get_boundary_lines 相同，缩放时切换级别不需要重新处理
+    #[wasm_bindgen]
+    pub fn get_boundary_lines_lod(&self, level: usize) -> JsValue {
+        serde_wasm_bindgen::to_value(self.boundary_lines_at(level)).unwrap_or(JsValue::NULL)
+    }
+    
+    // 以类型化数组获取边界线：positions 为所有顶点的 x, y, z（Float32Array），
+    // lines 为每条边界线的区域名称、访问状态、第一个顶点的序号和顶点数，
+    // 渲染器可以直接用 positions 创建 BufferGeometry。level 为细节级别，省略时为0
+    #[wasm_bindgen]
+    pub fn get_boundary_positions(&self, level: Option<usize>) -> Result<JsValue, JsValue> {
+        BoundaryBuffer::build(self.boundary_lines_at(level.unwrap_or(0))).to_js()
+    }
+    
+    // 边界线细节级别的数量
+    #[wasm_bindgen]
+    pub fn get_lod_count(&self) -> usize {
+        LOD_TOLERANCES.len()
+    }
+    
+    // 获取所有区域信息，包含简化前后的顶点数
     #[wasm_bindgen]
     pub fn get_regions(&self) -> JsValue {
         serde_wasm_bindgen::to_value(&self.regions).unwrap_or(JsValue::NULL)
     }
+    
+    // 为区域分配调色板颜色编号，相邻区域的编号不同，相同数据每次结果一致
+    #[wasm_bindgen]
+    pub fn get_region_colors(&self, palette_size: usize) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.region_colors(palette_size)).unwrap_or(JsValue::NULL)
+    }
+    
+    // 一次性导出渲染器初始化所需的全部数据：边界线缓冲区（类型化数组）、
+    // 已访问区域标记、区域颜色、标注点和统计信息，只需跨越一次wasm边界
+    #[wasm_bindgen]
+    pub fn get_scene_descriptor(&self, palette_size: usize) -> Result<JsValue, JsValue> {
+        SceneDescriptor::build(self, palette_size).to_js()
+    }
+    
+    // 生成首页巡游动画的相机路径：按最近邻顺序依次飞过各区域（可只包含已访问区域），
+    // 每个关键帧包含相机位置、注视点、开始时间和停留时长
+    #[wasm_bindgen]
+    pub fn build_tour(&self, visited_only: bool, seconds_per_region: f64) -> Result<JsValue, JsValue> {
+        if !seconds_per_region.is_finite() || seconds_per_region <= 0.0 {
+            return Err(JsValue::from_str(&format!("Invalid seconds per region: {}", seconds_per_region)));
+        }
+        let keyframes = tour::build_tour(self, visited_only, seconds_per_region);
+        Ok(serde_wasm_bindgen::to_value(&keyframes)?)
+    }
+    
+    // 对比已访问地点更新前后的两份列表，返回新访问、不再访问和保持访问的区域
+    // 及其边界线下标，前端据此只为变化的区域播放颜色过渡
+    #[wasm_bindgen]
+    pub fn diff_visited(&self, old_json: &str, new_json: &str) -> Result<JsValue, JsValue> {
+        let old_visited: Vec<String> = serde_json::from_str(old_json)
+            .map_err(|e| JsValue::from_str(&format!("Error parsing old visited places: {}", e)))?;
+        let new_visited: Vec<String> = serde_json::from_str(new_json)
+            .map_err(|e| JsValue::from_str(&format!("Error parsing new visited places: {}", e)))?;
+        let diff = diff::diff_visited(self, &old_visited, &new_visited);
+        Ok(serde_wasm_bindgen::to_value(&diff)?)
+    }
+    
+    // 已访问统计：已访问的国家和中国省份数量、占区域总数的百分比，以及已访问区域的名称和中心点
+    #[wasm_bindgen]
+    pub fn get_visited_stats(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&stats::visited_stats(self))?)
+    }
+    
+    // 按名称搜索区域（前缀或包含匹配，包括全称和其他语言的名称，不区分大小写），
+    // 返回最多 limit 个区域及其中心点和访问状态，供“跳转到国家/地区”搜索框使用
+    #[wasm_bindgen]
+    pub fn search_regions(&self, query: &str, limit: usize) -> Result<JsValue, JsValue> {
+        let matches = search::search_regions(self, query, limit);
+        Ok(serde_wasm_bindgen::to_value(&matches)?)
+    }
+    
+    // 查找包含该点的区域，多个区域包含该点时（数据重叠）取经纬度范围最小的区域
+    fn find_containing_region(&self, point: &Vector3) -> Option<String> {
+        let (lat, lon) = vector3_to_lat_long(point);
+        self.region_polygons.iter()
+            .filter(|polygon| polygon.contains(lon, lat))
+            .min_by(|a, b| a.bounds_area().total_cmp(&b.bounds_area()))
+            .map(|polygon| polygon.region_name.clone())
+    }
+    
+    // 指定细节级别的边界线，超出范围时为最粗的级别
+    pub(crate) fn boundary_lines_at(&self, level: usize) -> &[BoundaryLine] {
+        match level {
+            0 => &self.boundary_lines,
+            _ => self.lod_boundary_lines.get(level - 1)
+                .or(self.lod_boundary_lines.last())
+                .unwrap_or(&self.boundary_lines),
+        }
+    }
+    
+    // 计算区域颜色编号（区域名称 -> 调色板索引）
+    pub(crate) fn region_colors(&self, palette_size: usize) -> BTreeMap<String, usize> {
+        greedy_coloring(&self.adjacency, palette_size)
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,139 @@
+// 地理索引 - GeoJSON预处理后的区域表：区域名称、访问状态、其他名称和经纬度多边形。
+// 原生构建器把世界和中国地图的GeoJSON转换为地理索引并压缩保存，浏览器加载时不需要解析
+// 体积很大的GeoJSON字符串；直接传入GeoJSON时也先转换为地理索引，两种方式得到相同的结果。
+// 经纬度在内存中为双精度，写入索引时舍入为单精度（误差约1米），压缩后的体积约为双精度的一半。
+// 地理索引同时保存由区域表预处理的KD树、区域信息、各细节级别的边界线和邻接图（见 prepared），
+// 坐标在单位球面上，加载时只需按缩放比例换算。第1版索引只有区域表，不再支持，需要重新生成。
+
+use std::io;
+use serde::{Deserialize, Serialize};
+use geojson::{Feature, GeoJson, Value};
+use utils_common::compression::from_compressed_with_version_range;
+use crate::prepared::PreparedGeo;
+use crate::{search, LOD_TOLERANCES, MAX_INDEX_VERSION, MIN_INDEX_VERSION};
+
+// 地理索引
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GeoIndex {
+    // 区域，按GeoJSON中要素的顺序（先世界地图，后中国省级区域）
+    pub regions: Vec<GeoRegion>,
+    // 按默认细节级别预处理的数据
+    pub prepared: PreparedGeo,
+}
+
+// 区域及其多边形
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GeoRegion {
+    // 区域名称，中国省级区域为“中国-省名”
+    pub name: String,
+    pub is_visited: bool,
+    // 其他名称（全称、各语言名称等），用于按名称搜索区域
+    pub aliases: Vec<String>,
+    // 多边形，每个多边形的第一个环为外环，其余为内环，点为 [经度, 纬度]；
+    // 由GeoJSON创建时保留原始精度，索引中以单精度保存
+    #[serde(with = "single_precision")]
+    pub polygons: Vec<Vec<Vec<[f64; 2]>>>,
+}
+
+impl GeoIndex {
+    // 由世界地图和中国地图的GeoJSON创建，世界地图中的“中国”由中国地图的省级区域代替
+    pub fn from_geojson(world_json: &str, china_json: &str, visited_places: &[String]) -> Result<Self, String> {
+        let world_geojson: GeoJson = world_json.parse()
+            .map_err(|e| format!("Error parsing world GeoJSON: {}", e))?;
+        let china_geojson: GeoJson = china_json.parse()
+            .map_err(|e| format!("Error parsing China GeoJSON: {}", e))?;
+
+        let mut regions = Vec::new();
+        if let GeoJson::FeatureCollection(collection) = world_geojson {
+            regions.extend(collection.features.iter()
+                .filter_map(|feature| GeoRegion::from_feature(feature, None, visited_places))
+                .filter(|region| region.name != "中国"));
+        }
+        if let GeoJson::FeatureCollection(collection) = china_geojson {
+            regions.extend(collection.features.iter()
+                .filter_map(|feature| GeoRegion::from_feature(feature, Some("中国"), visited_places)));
+        }
+
+        Self::from_regions(regions)
+    }
+
+    // 由区域表创建，按默认细节级别预处理
+    pub fn from_regions(regions: Vec<GeoRegion>) -> Result<Self, String> {
+        let prepared = PreparedGeo::build(&regions, &LOD_TOLERANCES)?;
+        Ok(GeoIndex { regions, prepared })
+    }
+
+    // 解码压缩的地理索引，早于最小版本的索引返回要求重新生成的错误
+    pub fn from_compressed(data: &[u8]) -> Result<Self, io::Error> {
+        from_compressed_with_version_range(data, MIN_INDEX_VERSION, MAX_INDEX_VERSION)
+    }
+
+    // 按已访问地点列表重新设置各区域的访问状态
+    pub fn set_visited(&mut self, visited_places: &[String]) {
+        for region in &mut self.regions {
+            region.is_visited = visited_places.contains(&region.name);
+        }
+    }
+}
+
+impl GeoRegion {
+    // 由带有 name 属性的要素创建，`parent_name` 为上级区域名称；没有名称的要素返回None
+    fn from_feature(feature: &Feature, parent_name: Option<&str>, visited_places: &[String]) -> Option<Self> {
+        let props = feature.properties.as_ref()?;
+        let Some(serde_json::Value::String(name)) = props.get("name") else {
+            return None;
+        };
+        let region_name = match parent_name {
+            Some(parent) => format!("{}-{}", parent, name),
+            None => name.clone(),
+        };
+
+        let polygons = match feature.geometry.as_ref().map(|geometry| &geometry.value) {
+            Some(Value::Polygon(polygon)) => vec![polygon_rings(polygon)],
+            Some(Value::MultiPolygon(multi_polygon)) => multi_polygon.iter().map(|polygon| polygon_rings(polygon)).collect(),
+            _ => Vec::new(),
+        };
+
+        Some(GeoRegion {
+            is_visited: visited_places.contains(&region_name),
+            aliases: search::alternative_names(props, name, &region_name),
+            name: region_name,
+            polygons,
+        })
+    }
+}
+
+// GeoJSON多边形的各个环，忽略不足两个坐标的点
+fn polygon_rings(polygon: &[Vec<Vec<f64>>]) -> Vec<Vec<[f64; 2]>> {
+    polygon.iter()
+        .map(|ring| ring.iter()
+            .filter(|point| point.len() >= 2)
+            .map(|point| [point[0], point[1]])
+            .collect())
+        .collect()
+}
+
+// 多边形坐标的序列化 - 写入时舍入为单精度，读取时转换回双精度
+mod single_precision {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    type Polygons<T> = Vec<Vec<Vec<[T; 2]>>>;
+
+    pub fn serialize<S: Serializer>(polygons: &[Vec<Vec<[f64; 2]>>], serializer: S) -> Result<S::Ok, S::Error> {
+        let rounded: Polygons<f32> = polygons.iter()
+            .map(|polygon| polygon.iter()
+                .map(|ring| ring.iter().map(|&[lon, lat]| [lon as f32, lat as f32]).collect())
+                .collect())
+            .collect();
+        rounded.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Polygons<f64>, D::Error> {
+        let rounded = Polygons::<f32>::deserialize(deserializer)?;
+        Ok(rounded.into_iter()
+            .map(|polygon| polygon.into_iter()
+                .map(|ring| ring.into_iter().map(|[lon, lat]| [f64::from(lon), f64::from(lat)]).collect())
+                .collect())
+            .collect())
+    }
+}
@@ -0,0 +1,138 @@
+// 区域多边形 - 以经纬度保存GeoJSON中的原始多边形，判断点击位置落在哪个区域内。
+// 使用射线法（奇偶规则）：从点向东发出射线，与所有环（外环和内环）的交点数为奇数时点在多边形内，
+// 落在内环（湖泊、飞地等洞）中的点自然被排除。每个多边形先用经纬度范围快速排除。
+// 落在边界（外环或内环的边）上的点算作在多边形内。跨越180度经线的多边形（如斐济、楚科奇）
+// 把经度换算到 [0, 360) 区间后再判断，否则经度范围会覆盖整个地球。
+
+use crate::Vector3;
+
+// 区域的一个多边形
+#[derive(Clone, Debug)]
+pub struct RegionPolygon {
+    pub region_name: String,
+    // 外环和内环，每个点为 [经度, 纬度]
+    rings: Vec<Vec<[f64; 2]>>,
+    // 外环的经纬度范围 [最小经度, 最小纬度, 最大经度, 最大纬度]
+    bounds: [f64; 4],
+    // 是否跨越180度经线，是时各环的经度已换算到 [0, 360) 区间
+    wraps_antimeridian: bool,
+}
+
+// 判断点是否在边上的容差（度）
+const EDGE_EPSILON: f64 = 1e-9;
+
+impl RegionPolygon {
+    // 由多边形的各个环（第一个环为外环）创建，外环少于3个点时返回None
+    pub fn from_rings(region_name: &str, polygon: &[Vec<[f64; 2]>]) -> Option<Self> {
+        let mut rings: Vec<Vec<[f64; 2]>> = polygon.iter()
+            .filter(|ring| ring.len() >= 3)
+            .cloned()
+            .collect();
+
+        // 外环有跨越180度经线的边时按换算后的经度判断；环绕极点的环（如南极洲）
+        // 本身覆盖所有经度，保持原样
+        let exterior = rings.first()?;
+        let wraps_antimeridian = has_antimeridian_edge(exterior) && !encloses_pole(exterior);
+        if wraps_antimeridian {
+            for point in rings.iter_mut().flatten() {
+                *point = wrap_point(*point);
+            }
+        }
+        let exterior = &rings[0];
+
+        let mut bounds = [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY];
+        for &[lon, lat] in exterior {
+            bounds[0] = bounds[0].min(lon);
+            bounds[1] = bounds[1].min(lat);
+            bounds[2] = bounds[2].max(lon);
+            bounds[3] = bounds[3].max(lat);
+        }
+
+        Some(RegionPolygon { region_name: region_name.to_string(), rings, bounds, wraps_antimeridian })
+    }
+
+    // 经纬度范围的面积（平方度），多个区域包含同一点时用于选择更具体的区域
+    pub fn bounds_area(&self) -> f64 {
+        (self.bounds[2] - self.bounds[0]) * (self.bounds[3] - self.bounds[1])
+    }
+
+    // 点是否在多边形内（不在任何内环中），在边界上时为true
+    pub fn contains(&self, lon: f64, lat: f64) -> bool {
+        let [lon, lat] = if self.wraps_antimeridian { wrap_point([lon, lat]) } else { [lon, lat] };
+        let [min_lon, min_lat, max_lon, max_lat] = self.bounds;
+        if lon < min_lon || lon > max_lon || lat < min_lat || lat > max_lat {
+            return false;
+        }
+
+        let mut inside = false;
+        for ring in &self.rings {
+            // 环首尾不闭合时同样连接最后一个点和第一个点
+            let mut previous = ring[ring.len() - 1];
+            for &current in ring {
+                if on_segment([lon, lat], previous, current) {
+                    return true;
+                }
+                let ([x1, y1], [x2, y2]) = (previous, current);
+                if (y1 > lat) != (y2 > lat) {
+                    let crossing = x1 + (lat - y1) / (y2 - y1) * (x2 - x1);
+                    if lon < crossing {
+                        inside = !inside;
+                    }
+                }
+                previous = current;
+            }
+        }
+        inside
+    }
+}
+
+// 环中是否有跨越180度经线的边（相邻两点的经度相差超过180度）
+fn has_antimeridian_edge(ring: &[[f64; 2]]) -> bool {
+    let mut previous = ring[ring.len() - 1];
+    ring.iter().any(|&current| {
+        let crosses = (current[0] - previous[0]).abs() > 180.0;
+        previous = current;
+        crosses
+    })
+}
+
+// 环是否环绕极点：沿环走一圈经度累计变化约360度，跨越180度经线的边按较短的方向计算
+fn encloses_pole(ring: &[[f64; 2]]) -> bool {
+    let mut previous = ring[ring.len() - 1];
+    let winding: f64 = ring.iter()
+        .map(|&current| {
+            let delta = current[0] - previous[0];
+            previous = current;
+            if delta > 180.0 { delta - 360.0 } else if delta < -180.0 { delta + 360.0 } else { delta }
+        })
+        .sum();
+    winding.abs() > 180.0
+}
+
+// 经度换算到 [0, 360) 区间
+fn wrap_point([lon, lat]: [f64; 2]) -> [f64; 2] {
+    [if lon < 0.0 { lon + 360.0 } else { lon }, lat]
+}
+
+// 点是否在线段上（允许浮点误差）
+fn on_segment([x, y]: [f64; 2], [x1, y1]: [f64; 2], [x2, y2]: [f64; 2]) -> bool {
+    let cross = (x2 - x1) * (y - y1) - (y2 - y1) * (x - x1);
+    cross.abs() <= EDGE_EPSILON
+        && x >= x1.min(x2) - EDGE_EPSILON && x <= x1.max(x2) + EDGE_EPSILON
+        && y >= y1.min(y2) - EDGE_EPSILON && y <= y1.max(y2) + EDGE_EPSILON
+}
+
+// 三维坐标对应的纬度和经度（度），为 lat_long_to_vector3 的逆变换，与半径无关
+pub fn vector3_to_lat_long(point: &Vector3) -> (f64, f64) {
+    let radius = (point.x * point.x + point.y * point.y + point.z * point.z).sqrt();
+    if radius == 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let lat = 90.0 - (point.y / radius).clamp(-1.0, 1.0).acos().to_degrees();
+    let mut lon = point.z.atan2(-point.x).to_degrees() - 180.0;
+    if lon < -180.0 {
+        lon += 360.0;
+    }
+    (lat, lon)
+}
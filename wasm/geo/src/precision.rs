@@ -0,0 +1,109 @@
+// 坐标精度 - 边界线顶点和标记位置可以用单精度保存和输出，内存和传输量减半，
+// 渲染所需的精度足够。区域中心、边界盒、KD树和邻接图始终使用双精度计算，
+// 拾取结果不受存储精度影响。
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use crate::Vector3;
+
+// 边界线和标记的存储/输出精度
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoordinatePrecision {
+    // 双精度（f64），默认
+    #[default]
+    Double,
+    // 单精度（f32）
+    Single,
+}
+
+// 单精度三维坐标，序列化结构与 Vector3 相同
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Vector3f {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl From<Vector3> for Vector3f {
+    fn from(v: Vector3) -> Self {
+        Vector3f { x: v.x as f32, y: v.y as f32, z: v.z as f32 }
+    }
+}
+
+impl From<Vector3f> for Vector3 {
+    fn from(v: Vector3f) -> Self {
+        Vector3 { x: v.x as f64, y: v.y as f64, z: v.z as f64 }
+    }
+}
+
+// 按精度保存的坐标，序列化为 {x, y, z}
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Position {
+    Double(Vector3),
+    Single(Vector3f),
+}
+
+impl Position {
+    pub fn new(v: Vector3, precision: CoordinatePrecision) -> Position {
+        match precision {
+            CoordinatePrecision::Double => Position::Double(v),
+            CoordinatePrecision::Single => Position::Single(v.into()),
+        }
+    }
+}
+
+// 按精度保存的边界线顶点，序列化为 [{x, y, z}, ...]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BoundaryPoints {
+    Double(Vec<Vector3>),
+    Single(Vec<Vector3f>),
+}
+
+impl BoundaryPoints {
+    // 转换为指定精度
+    pub fn into_precision(self, precision: CoordinatePrecision) -> BoundaryPoints {
+        match (self, precision) {
+            (BoundaryPoints::Double(points), CoordinatePrecision::Single) => {
+                BoundaryPoints::Single(points.into_iter().map(Vector3f::from).collect())
+            }
+            (BoundaryPoints::Single(points), CoordinatePrecision::Double) => {
+                BoundaryPoints::Double(points.into_iter().map(Vector3::from).collect())
+            }
+            (points, _) => points,
+        }
+    }
+
+    // 按双精度遍历顶点
+    pub fn iter(&self) -> Box<dyn Iterator<Item = Vector3> + '_> {
+        match self {
+            BoundaryPoints::Double(points) => Box::new(points.iter().copied()),
+            BoundaryPoints::Single(points) => Box::new(points.iter().map(|&p| Vector3::from(p))),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            BoundaryPoints::Double(points) => points.len(),
+            BoundaryPoints::Single(points) => points.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 转换为单精度坐标，依次为 x, y, z
+    pub fn extend_f32(&self, out: &mut Vec<f32>) {
+        match self {
+            BoundaryPoints::Double(points) => {
+                out.extend(points.iter().flat_map(|p| [p.x as f32, p.y as f32, p.z as f32]));
+            }
+            BoundaryPoints::Single(points) => {
+                out.extend(points.iter().flat_map(|p| [p.x, p.y, p.z]));
+            }
+        }
+    }
+}
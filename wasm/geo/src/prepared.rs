@@ -0,0 +1,237 @@
+// 预处理的地理数据 - 由区域表计算的KD树、区域信息、各细节级别的边界线和邻接图。
+// 坐标在单位球面上（半径为1），与缩放比例和输出精度无关，加载时只需按缩放比例换算。
+// 边界线只保存简化后保留的顶点序号，加载时由区域表中的经纬度换算为三维坐标，
+// 索引体积与只保存区域表时相近。构建器把预处理结果与区域表一起写入地理索引，
+// 浏览器加载时不需要简化边界线和构建KD树；直接传入GeoJSON时同样先计算预处理结果，
+// 两种方式得到相同的结果。
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use kdtree::KdTree;
+use serde::{Deserialize, Serialize};
+use crate::models::GeoRegion;
+use crate::simplify::simplify_indices;
+use crate::{lat_long_to_vector3, unit_vector, BoundaryLine, BoundaryPoints, BoundingBox, CoordinatePrecision, RegionInfo, Vector3};
+
+// 标记中心点高出球面的距离，避免与边界线重叠
+const CENTER_ELEVATION: f64 = 0.005;
+
+// 边界顶点的量化精度（单位球面上），用于判断不同区域的顶点是否重合
+const VERTEX_KEY_PRECISION: f64 = 1e5;
+
+// 预处理的地理数据
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PreparedGeo {
+    // 计算各细节级别边界线使用的简化容差（度）
+    pub tolerances: Vec<f64>,
+    // 区域中心点方向（单位向量）的KD树
+    pub region_tree: KdTree<f64, String, [f64; 3]>,
+    // 区域信息，按区域名称排序
+    pub regions: BTreeMap<String, PreparedRegion>,
+    // 各细节级别的边界线，第0级为完整细节，各级别的边界线一一对应
+    pub boundary_lines: Vec<Vec<PreparedLine>>,
+    // 区域邻接图：共享边界顶点的区域互为相邻
+    pub adjacency: BTreeMap<String, BTreeSet<String>>,
+}
+
+// 区域信息，中心点和边界盒在单位球面上
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PreparedRegion {
+    pub center: Vector3,
+    pub bounding_box: BoundingBox,
+    pub vertex_count: usize,
+    pub simplified_vertex_count: usize,
+}
+
+// 边界线：区域表中一个多边形的外环简化后保留的顶点
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PreparedLine {
+    // 区域在区域表中的序号
+    pub region: u32,
+    // 多边形在区域中的序号
+    pub polygon: u32,
+    // 保留的顶点在外环中的序号（升序）
+    pub vertices: Vec<u32>,
+}
+
+impl PreparedGeo {
+    // 由区域表按各细节级别的容差计算
+    pub fn build(regions: &[GeoRegion], tolerances: &[f64]) -> Result<Self, String> {
+        let mut prepared = PreparedGeo {
+            tolerances: tolerances.to_vec(),
+            region_tree: KdTree::new(3),
+            regions: BTreeMap::new(),
+            boundary_lines: vec![Vec::new(); tolerances.len()],
+            adjacency: BTreeMap::new(),
+        };
+        for (region_id, region) in regions.iter().enumerate() {
+            for (polygon_id, polygon) in region.polygons.iter().enumerate() {
+                prepared.add_polygon(&region.name, polygon, [region_id as u32, polygon_id as u32])?;
+            }
+        }
+        prepared.adjacency = build_adjacency(regions);
+        Ok(prepared)
+    }
+
+    // 加入多边形：区域信息、KD树中的中心点和各细节级别的边界线，`id` 为区域和多边形的序号
+    fn add_polygon(&mut self, region_name: &str, polygon: &[Vec<[f64; 2]>], id: [u32; 2]) -> Result<(), String> {
+        // 外环
+        let Some(lon_lat) = polygon.first().filter(|ring| !ring.is_empty()) else {
+            return Ok(());
+        };
+
+        let points: Vec<Vector3> = lon_lat.iter()
+            .map(|&[lon, lat]| lat_long_to_vector3(lat, lon, 1.0))
+            .collect();
+
+        // 中心点为顶点经纬度的平均值，边界盒由所有顶点计算
+        let count = lon_lat.len() as f64;
+        let center_lon = lon_lat.iter().map(|point| point[0]).sum::<f64>() / count;
+        let center_lat = lon_lat.iter().map(|point| point[1]).sum::<f64>() / count;
+        let center = lat_long_to_vector3(center_lat, center_lon, 1.0);
+
+        let mut bounding_box = BoundingBox::new(
+            f64::INFINITY, f64::INFINITY, f64::INFINITY,
+            f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY,
+        );
+        for vertex in &points {
+            bounding_box.min_x = bounding_box.min_x.min(vertex.x);
+            bounding_box.min_y = bounding_box.min_y.min(vertex.y);
+            bounding_box.min_z = bounding_box.min_z.min(vertex.z);
+            bounding_box.max_x = bounding_box.max_x.max(vertex.x);
+            bounding_box.max_y = bounding_box.max_y.max(vertex.y);
+            bounding_box.max_z = bounding_box.max_z.max(vertex.z);
+        }
+
+        // 按各细节级别的容差简化边界线，中心点和边界盒仍由原始顶点计算
+        let level_vertices: Vec<Vec<u32>> = self.tolerances.iter()
+            .map(|&tolerance| simplify_indices(lon_lat, tolerance)
+                .into_iter()
+                .map(|i| i as u32)
+                .collect())
+            .collect();
+
+        // 多边形区域的顶点数按所有多边形累计，中心点和边界盒为最后一个多边形的
+        let (previous_vertices, previous_simplified) = self.regions.get(region_name)
+            .map_or((0, 0), |region| (region.vertex_count, region.simplified_vertex_count));
+        self.regions.insert(region_name.to_string(), PreparedRegion {
+            center,
+            bounding_box,
+            vertex_count: previous_vertices + points.len(),
+            simplified_vertex_count: previous_simplified + level_vertices[0].len(),
+        });
+
+        // KD树中保存中心点方向，任何度量下按弦长检索的近邻顺序都一致
+        self.region_tree.add(unit_vector(&center), region_name.to_string())
+            .map_err(|e| format!("Error adding to KD tree: {}", e))?;
+
+        // 简化保留首尾点，各级别的边界线一一对应
+        if points.len() > 1 {
+            let [region, polygon] = id;
+            for (lines, vertices) in self.boundary_lines.iter_mut().zip(level_vertices) {
+                lines.push(PreparedLine { region, polygon, vertices });
+            }
+        }
+
+        Ok(())
+    }
+
+    // 按缩放比例换算的区域信息，`visited` 为各区域的访问状态
+    pub fn scaled_regions(&self, scale: f64, visited: &HashMap<&str, bool>) -> HashMap<String, RegionInfo> {
+        self.regions.iter()
+            .map(|(name, region)| {
+                let b = &region.bounding_box;
+                let info = RegionInfo {
+                    name: name.clone(),
+                    is_visited: visited.get(name.as_str()).copied().unwrap_or(false),
+                    center: scaled(&region.center, scale + CENTER_ELEVATION),
+                    bounding_box: BoundingBox::new(
+                        b.min_x * scale, b.min_y * scale, b.min_z * scale,
+                        b.max_x * scale, b.max_y * scale, b.max_z * scale,
+                    ),
+                    vertex_count: region.vertex_count,
+                    simplified_vertex_count: region.simplified_vertex_count,
+                };
+                (name.clone(), info)
+            })
+            .collect()
+    }
+
+    // 按缩放比例和精度换算的各细节级别边界线，`regions` 为预处理时使用的区域表
+    pub fn scaled_boundary_lines(
+        &self,
+        regions: &[GeoRegion],
+        scale: f64,
+        precision: CoordinatePrecision,
+    ) -> Result<Vec<Vec<BoundaryLine>>, String> {
+        self.boundary_lines.iter()
+            .map(|lines| lines.iter()
+                .map(|line| {
+                    let region = regions.get(line.region as usize)
+                        .ok_or_else(|| format!("Invalid region in boundary line: {}", line.region))?;
+                    let points = line_points(regions, line)?
+                        .map(|[lon, lat]| lat_long_to_vector3(lat, lon, scale))
+                        .collect();
+                    Ok(BoundaryLine {
+                        points: BoundaryPoints::Double(points).into_precision(precision),
+                        region_name: region.name.clone(),
+                        is_visited: region.is_visited,
+                    })
+                })
+                .collect())
+            .collect()
+    }
+}
+
+fn scaled(v: &Vector3, scale: f64) -> Vector3 {
+    Vector3 { x: v.x * scale, y: v.y * scale, z: v.z * scale }
+}
+
+// 边界线保留的顶点的经纬度，序号超出区域表时返回错误
+fn line_points<'a>(regions: &'a [GeoRegion], line: &'a PreparedLine) -> Result<impl Iterator<Item = [f64; 2]> + 'a, String> {
+    let ring = regions.get(line.region as usize)
+        .and_then(|region| region.polygons.get(line.polygon as usize))
+        .and_then(|polygon| polygon.first())
+        .ok_or_else(|| format!("Invalid polygon in boundary line: {}/{}", line.region, line.polygon))?;
+    if line.vertices.iter().any(|&i| i as usize >= ring.len()) {
+        return Err(format!("Invalid vertex in boundary line: {}/{}", line.region, line.polygon));
+    }
+    Ok(line.vertices.iter().map(move |&i| ring[i as usize]))
+}
+
+// 量化顶点坐标作为哈希键
+fn vertex_key(v: &Vector3) -> (i64, i64, i64) {
+    (
+        (v.x * VERTEX_KEY_PRECISION).round() as i64,
+        (v.y * VERTEX_KEY_PRECISION).round() as i64,
+        (v.z * VERTEX_KEY_PRECISION).round() as i64,
+    )
+}
+
+// 根据各多边形外环的原始顶点构建区域邻接图，不受边界线简化容差的影响
+fn build_adjacency(regions: &[GeoRegion]) -> BTreeMap<String, BTreeSet<String>> {
+    let mut vertex_regions: HashMap<(i64, i64, i64), BTreeSet<&str>> = HashMap::new();
+    let mut adjacency: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for region in regions {
+        let exteriors = region.polygons.iter()
+            .filter_map(|polygon| polygon.first())
+            .filter(|ring| ring.len() > 1);
+        for ring in exteriors {
+            adjacency.entry(region.name.clone()).or_default();
+            for &[lon, lat] in ring {
+                let point = lat_long_to_vector3(lat, lon, 1.0);
+                vertex_regions.entry(vertex_key(&point)).or_default().insert(&region.name);
+            }
+        }
+    }
+    for names in vertex_regions.values().filter(|names| names.len() > 1) {
+        for a in names {
+            for b in names {
+                if a != b {
+                    adjacency.entry(a.to_string()).or_default().insert(b.to_string());
+                }
+            }
+        }
+    }
+
+    adjacency
+}
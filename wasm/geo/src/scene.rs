@@ -0,0 +1,174 @@
+// 场景描述 - 一次调用导出渲染器构建地球所需的全部数据
+// 边界线顶点以类型化数组传递，渲染器可以直接写入顶点缓冲区，
+// 其余数据（标记、标注、颜色、统计）以普通JS对象传递。
+
+use std::collections::BTreeMap;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use crate::{BoundaryLine, GeoProcessor, Position, Vector3};
+
+// 区域标注点
+#[derive(Clone, Debug, Serialize)]
+pub struct LabelPoint {
+    pub name: String,
+    pub position: Vector3,
+    pub is_visited: bool,
+}
+
+// 已访问区域的标记，位置使用处理器的存储/输出精度
+#[derive(Clone, Debug, Serialize)]
+pub struct Marker {
+    pub name: String,
+    pub position: Position,
+}
+
+// 场景统计
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SceneStats {
+    pub region_count: usize,
+    pub visited_count: usize,
+    pub boundary_line_count: usize,
+    pub vertex_count: usize,
+}
+
+// 边界线缓冲区中一条边界线的信息，offset 和 count 以顶点为单位
+#[derive(Clone, Debug, Serialize)]
+pub struct BoundarySpan {
+    pub region_name: String,
+    pub is_visited: bool,
+    // 第一个顶点的序号，在 positions 中的下标为 offset * 3
+    pub offset: u32,
+    pub count: u32,
+}
+
+// 扁平化的边界线缓冲区
+#[derive(Clone, Debug, Default)]
+pub struct BoundaryBuffer {
+    // 所有边界线的顶点坐标，依次为 x, y, z
+    pub positions: Vec<f32>,
+    // 每条边界线的信息，顺序与边界线相同
+    pub lines: Vec<BoundarySpan>,
+}
+
+impl BoundaryBuffer {
+    pub(crate) fn build(lines: &[BoundaryLine]) -> BoundaryBuffer {
+        let mut buffer = BoundaryBuffer {
+            positions: Vec::with_capacity(lines.iter().map(|line| line.points.len() * 3).sum()),
+            lines: Vec::with_capacity(lines.len()),
+        };
+        for line in lines {
+            let offset = (buffer.positions.len() / 3) as u32;
+            line.points.extend_f32(&mut buffer.positions);
+            buffer.lines.push(BoundarySpan {
+                region_name: line.region_name.clone(),
+                is_visited: line.is_visited,
+                offset,
+                count: line.points.len() as u32,
+            });
+        }
+        buffer
+    }
+
+    // 转换为JS对象 {positions: Float32Array, lines: [...]}
+    pub fn to_js(&self) -> Result<JsValue, JsValue> {
+        let object = js_sys::Object::new();
+        js_sys::Reflect::set(&object, &JsValue::from_str("positions"),
+                             &js_sys::Float32Array::from(self.positions.as_slice()).into())?;
+        js_sys::Reflect::set(&object, &JsValue::from_str("lines"), &serde_wasm_bindgen::to_value(&self.lines)?)?;
+        Ok(object.into())
+    }
+}
+
+// 场景描述
+#[derive(Clone, Debug, Default)]
+pub struct SceneDescriptor {
+    // 所有边界线的顶点坐标，依次为 x, y, z
+    pub boundary_positions: Vec<f32>,
+    // 每条边界线第一个顶点的序号，最后一项为顶点总数
+    pub boundary_offsets: Vec<u32>,
+    // 每条边界线所属区域在 labels 中的序号
+    pub boundary_regions: Vec<u32>,
+    // 每条边界线所属区域是否已访问（0或1）
+    pub boundary_visited: Vec<u8>,
+    // 已访问区域的标记，按名称排序
+    pub markers: Vec<Marker>,
+    // 区域颜色编号（区域名称 -> 调色板索引）
+    pub region_colors: BTreeMap<String, usize>,
+    // 所有区域的标注点，按名称排序
+    pub labels: Vec<LabelPoint>,
+    pub stats: SceneStats,
+}
+
+impl SceneDescriptor {
+    // 从处理后的地理数据生成场景描述
+    pub(crate) fn build(processor: &GeoProcessor, palette_size: usize) -> SceneDescriptor {
+        let mut names: Vec<&String> = processor.regions.keys().collect();
+        names.sort();
+
+        let labels: Vec<LabelPoint> = names.iter()
+            .map(|name| {
+                let region = &processor.regions[*name];
+                LabelPoint {
+                    name: region.name.clone(),
+                    position: region.center,
+                    is_visited: region.is_visited,
+                }
+            })
+            .collect();
+
+        let markers: Vec<Marker> = labels.iter()
+            .filter(|label| label.is_visited)
+            .map(|label| Marker {
+                name: label.name.clone(),
+                position: Position::new(label.position, processor.precision),
+            })
+            .collect();
+
+        let region_index: BTreeMap<&str, u32> = names.iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i as u32))
+            .collect();
+
+        let mut scene = SceneDescriptor {
+            boundary_offsets: vec![0],
+            region_colors: processor.region_colors(palette_size),
+            ..Default::default()
+        };
+        for line in &processor.boundary_lines {
+            line.points.extend_f32(&mut scene.boundary_positions);
+            scene.boundary_offsets.push((scene.boundary_positions.len() / 3) as u32);
+            // 没有中心点的区域不会生成边界线，这里总能找到
+            scene.boundary_regions.push(region_index.get(line.region_name.as_str()).copied().unwrap_or(u32::MAX));
+            scene.boundary_visited.push(line.is_visited as u8);
+        }
+
+        scene.stats = SceneStats {
+            region_count: labels.len(),
+            visited_count: markers.len(),
+            boundary_line_count: processor.boundary_lines.len(),
+            vertex_count: scene.boundary_positions.len() / 3,
+        };
+        scene.labels = labels;
+        scene.markers = markers;
+        scene
+    }
+
+    // 转换为JS对象
+    pub fn to_js(&self) -> Result<JsValue, JsValue> {
+        let object = js_sys::Object::new();
+        let set = |key: &str, value: JsValue| {
+            js_sys::Reflect::set(&object, &JsValue::from_str(key), &value).map(|_| ())
+        };
+
+        set("boundary_positions", js_sys::Float32Array::from(self.boundary_positions.as_slice()).into())?;
+        set("boundary_offsets", js_sys::Uint32Array::from(self.boundary_offsets.as_slice()).into())?;
+        set("boundary_regions", js_sys::Uint32Array::from(self.boundary_regions.as_slice()).into())?;
+        set("boundary_visited", js_sys::Uint8Array::from(self.boundary_visited.as_slice()).into())?;
+        set("markers", serde_wasm_bindgen::to_value(&self.markers)?)?;
+        set("region_colors", serde_wasm_bindgen::to_value(&self.region_colors)?)?;
+        set("labels", serde_wasm_bindgen::to_value(&self.labels)?)?;
+        set("stats", serde_wasm_bindgen::to_value(&self.stats)?)?;
+
+        Ok(object.into())
+    }
+}
@@ -0,0 +1,96 @@
+// 区域名称搜索 - 为旅行页面的“跳转到国家/地区”搜索框按名称查找区域，
+// 匹配区域名称以及GeoJSON属性中的其他名称（全称、各语言的 name_* 名称），
+// 中国省级区域同时匹配不带“中国-”前缀的省名。不区分大小写。
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::{GeoProcessor, Vector3};
+
+// 匹配方式，按优先级排列
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    // 名称与查询相同
+    Exact,
+    // 名称以查询开头
+    Prefix,
+    // 名称包含查询
+    Contains,
+}
+
+// 搜索结果
+#[derive(Clone, Debug, Serialize)]
+pub struct RegionMatch {
+    // 区域名称（与 get_regions 的键相同）
+    pub name: String,
+    // 匹配到的名称，可能是区域名称或其他名称
+    pub matched_name: String,
+    pub match_kind: MatchKind,
+    pub center: Vector3,
+    pub is_visited: bool,
+}
+
+// 从GeoJSON要素属性中收集区域的其他名称，`name` 为属性中的名称
+pub(crate) fn alternative_names(props: &Map<String, Value>, name: &str, region_name: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    if name != region_name {
+        names.push(name.to_string());
+    }
+    for (key, value) in props {
+        let is_name = key == "full_name" || key == "fullname" || key.starts_with("name_");
+        if let (true, Value::String(value)) = (is_name, value) {
+            let value = value.trim();
+            if !value.is_empty() && value != region_name && !names.iter().any(|n| n == value) {
+                names.push(value.to_string());
+            }
+        }
+    }
+    names
+}
+
+// 按名称搜索区域，结果按匹配方式、名称长度和区域名称排序
+pub(crate) fn search_regions(processor: &GeoProcessor, query: &str, limit: usize) -> Vec<RegionMatch> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() || limit == 0 {
+        return Vec::new();
+    }
+
+    let match_kind = |name: &str| {
+        let name = name.to_lowercase();
+        if name == query {
+            Some(MatchKind::Exact)
+        } else if name.starts_with(&query) {
+            Some(MatchKind::Prefix)
+        } else if name.contains(&query) {
+            Some(MatchKind::Contains)
+        } else {
+            None
+        }
+    };
+
+    let mut matches: Vec<RegionMatch> = processor.regions.iter()
+        .filter_map(|(region_name, region)| {
+            // 每个区域只保留最好的匹配，相同时保留较短的名称
+            let names = std::iter::once(region_name)
+                .chain(processor.region_aliases.get(region_name).into_iter().flatten());
+            let (kind, matched_name) = names
+                .filter_map(|name| match_kind(name).map(|kind| (kind, name)))
+                .min_by_key(|(kind, name)| (*kind, name.chars().count()))?;
+            Some(RegionMatch {
+                name: region_name.clone(),
+                matched_name: matched_name.clone(),
+                match_kind: kind,
+                center: region.center,
+                is_visited: region.is_visited,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        a.match_kind.cmp(&b.match_kind)
+            .then_with(|| a.matched_name.chars().count().cmp(&b.matched_name.chars().count()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    matches.truncate(limit);
+    matches
+}
@@ -0,0 +1,58 @@
+// 边界线简化 - Douglas-Peucker算法：保留首尾点，找出离首尾连线最远的点，
+// 距离超过容差时保留该点并分别处理两侧，否则舍弃中间的所有点。
+// 在经纬度平面上计算距离，容差单位为度（0.01度约1公里）。
+// 只简化用于渲染的边界线，区域中心、边界盒和点击判断仍使用原始顶点。
+
+// 简化后保留的顶点序号（升序，包含首尾点），容差不大于0或点数不超过2时保留全部顶点
+pub fn simplify_indices(points: &[[f64; 2]], tolerance: f64) -> Vec<usize> {
+    let len = points.len();
+    if tolerance <= 0.0 || len <= 2 {
+        return (0..len).collect();
+    }
+
+    let mut keep = vec![false; len];
+    keep[0] = true;
+    keep[len - 1] = true;
+
+    // 使用显式栈代替递归，边界环可能有上万个顶点
+    let mut stack = vec![(0, len - 1)];
+    while let Some((start, end)) = stack.pop() {
+        if end <= start + 1 {
+            continue;
+        }
+
+        let mut farthest = start;
+        let mut max_distance = 0.0;
+        for i in start + 1..end {
+            let distance = segment_distance(points[i], points[start], points[end]);
+            if distance > max_distance {
+                max_distance = distance;
+                farthest = i;
+            }
+        }
+
+        if max_distance > tolerance {
+            keep[farthest] = true;
+            stack.push((start, farthest));
+            stack.push((farthest, end));
+        }
+    }
+
+    keep.iter()
+        .enumerate()
+        .filter_map(|(i, &kept)| kept.then_some(i))
+        .collect()
+}
+
+// 点到线段的距离；首尾相同（闭合环）时为到该点的距离
+fn segment_distance(point: [f64; 2], start: [f64; 2], end: [f64; 2]) -> f64 {
+    let [dx, dy] = [end[0] - start[0], end[1] - start[1]];
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared > 0.0 {
+        (((point[0] - start[0]) * dx + (point[1] - start[1]) * dy) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let [px, py] = [start[0] + t * dx - point[0], start[1] + t * dy - point[1]];
+    (px * px + py * py).sqrt()
+}
@@ -0,0 +1,85 @@
+// 已访问统计 - 足迹页面显示的国家和省份数量、访问比例和已访问区域列表，
+// 不需要在JS中遍历区域数据重新计算。
+// 中国地图的省级区域名称为“中国-省名”（见 models），访问任一省份即计为访问了中国。
+
+use serde::Serialize;
+use crate::polygon::vector3_to_lat_long;
+use crate::{GeoProcessor, Vector3};
+
+// 中国省级区域名称的前缀
+const CHINA_PROVINCE_PREFIX: &str = "中国-";
+
+// 已访问区域及其中心点
+#[derive(Clone, Debug, Serialize)]
+pub struct VisitedRegion {
+    pub name: String,
+    // 中心点的三维坐标（与标注点相同）
+    pub center: Vector3,
+    // 中心点的纬度和经度（度）
+    pub lat: f64,
+    pub lon: f64,
+}
+
+// 已访问统计
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct VisitedStats {
+    // 国家数量，中国的省级区域合计为一个国家
+    pub country_count: usize,
+    pub visited_country_count: usize,
+    // 中国省级区域数量
+    pub province_count: usize,
+    pub visited_province_count: usize,
+    // 区域总数（国家和中国省级区域，不含中国本身）
+    pub region_count: usize,
+    pub visited_region_count: usize,
+    // 已访问区域占区域总数的百分比（0 - 100）
+    pub visited_percentage: f64,
+    // 已访问区域，按名称排序
+    pub visited: Vec<VisitedRegion>,
+}
+
+// 统计已处理区域的访问情况
+pub(crate) fn visited_stats(processor: &GeoProcessor) -> VisitedStats {
+    let mut stats = VisitedStats::default();
+
+    let mut names: Vec<&String> = processor.regions.keys().collect();
+    names.sort();
+    for name in names {
+        let region = &processor.regions[name];
+        let is_province = name.starts_with(CHINA_PROVINCE_PREFIX);
+        if is_province {
+            stats.province_count += 1;
+        } else {
+            stats.country_count += 1;
+        }
+        if !region.is_visited {
+            continue;
+        }
+
+        if is_province {
+            stats.visited_province_count += 1;
+        } else {
+            stats.visited_country_count += 1;
+        }
+        let (lat, lon) = vector3_to_lat_long(&region.center);
+        stats.visited.push(VisitedRegion {
+            name: name.clone(),
+            center: region.center,
+            lat,
+            lon,
+        });
+    }
+
+    if stats.province_count > 0 {
+        stats.country_count += 1;
+    }
+    if stats.visited_province_count > 0 {
+        stats.visited_country_count += 1;
+    }
+    stats.region_count = processor.regions.len();
+    stats.visited_region_count = stats.visited.len();
+    if stats.region_count > 0 {
+        stats.visited_percentage = stats.visited_region_count as f64 * 100.0 / stats.region_count as f64;
+    }
+    stats
+}
@@ -0,0 +1,74 @@
+// 巡游路径 - 为首页动画生成依次飞过各区域的相机关键帧
+// 访问顺序使用最近邻贪心：从名称最小的区域出发，每次前往大圆距离最近的未访问区域，
+// 相同数据每次生成的路径一致。相机位于区域中心沿球面法线方向向外延伸的位置，注视区域中心。
+
+use serde::Serialize;
+use crate::{metric_distance, unit_vector, DistanceMetric, GeoProcessor, Vector3};
+
+// 相机到球心的距离与区域中心到球心距离的比值
+const CAMERA_DISTANCE_FACTOR: f64 = 2.5;
+
+// 相机关键帧
+#[derive(Clone, Debug, Serialize)]
+pub struct TourKeyframe {
+    // 区域名称
+    pub name: String,
+    // 相机位置
+    pub position: Vector3,
+    // 相机注视点（区域中心）
+    pub target: Vector3,
+    // 关键帧开始时间（秒）
+    pub start_time: f64,
+    // 停留时长（秒）
+    pub duration: f64,
+}
+
+// 生成巡游关键帧
+pub(crate) fn build_tour(processor: &GeoProcessor, visited_only: bool, seconds_per_region: f64) -> Vec<TourKeyframe> {
+    let mut remaining: Vec<(&String, Vector3)> = processor.regions.iter()
+        .filter(|(_, region)| !visited_only || region.is_visited)
+        .map(|(name, region)| (name, region.center))
+        .collect();
+    remaining.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut keyframes: Vec<TourKeyframe> = Vec::with_capacity(remaining.len());
+    let mut current: Option<Vector3> = None;
+    while !remaining.is_empty() {
+        // 距离相同时保留名称较小的区域
+        let next = match current {
+            None => 0,
+            Some(from) => {
+                let mut best = 0;
+                let mut best_distance = f64::INFINITY;
+                for (i, (_, center)) in remaining.iter().enumerate() {
+                    let distance = metric_distance(DistanceMetric::GreatCircle, &from, center);
+                    if distance < best_distance {
+                        best_distance = distance;
+                        best = i;
+                    }
+                }
+                best
+            }
+        };
+        let (name, center) = remaining.remove(next);
+
+        keyframes.push(TourKeyframe {
+            name: name.clone(),
+            position: camera_position(&center),
+            target: center,
+            start_time: keyframes.len() as f64 * seconds_per_region,
+            duration: seconds_per_region,
+        });
+        current = Some(center);
+    }
+
+    keyframes
+}
+
+// 区域中心沿法线方向向外延伸得到的相机位置
+fn camera_position(center: &Vector3) -> Vector3 {
+    let radius = (center.x * center.x + center.y * center.y + center.z * center.z).sqrt();
+    let [x, y, z] = unit_vector(center);
+    let distance = radius * CAMERA_DISTANCE_FACTOR;
+    Vector3 { x: x * distance, y: y * distance, z: z * distance }
+}
@@ -0,0 +1,150 @@
+// 地理索引测试 - 构建器保存的索引包含预处理的数据，加载结果与直接传入GeoJSON相同；
+// 经纬度在内存中保持双精度，只在写入索引时舍入为单精度；只有区域表的第1版索引不再支持。
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use serde::Serialize;
+use geo_wasm::builder::GeoIndexBuilder;
+use geo_wasm::models::{GeoIndex, GeoRegion};
+use geo_wasm::polygon::RegionPolygon;
+use geo_wasm::GeoProcessor;
+use utils_common::compression::{to_compressed_with_format, SerializationFormat};
+
+const SCALE: f64 = 2.01;
+
+// 世界地图：两个相邻的国家、跨越180度经线的岛国，以及被中国地图代替的“中国”
+const WORLD: &str = r#"{"type": "FeatureCollection", "features": [
+    {"type": "Feature", "properties": {"name": "甲国"}, "geometry": {"type": "Polygon",
+        "coordinates": [[[0, 0], [10, 0], [10, 10], [5, 12], [0, 10], [0, 0]]]}},
+    {"type": "Feature", "properties": {"name": "乙国"}, "geometry": {"type": "Polygon",
+        "coordinates": [[[10, 0], [20, 0], [20, 10], [10, 10], [10, 0]]]}},
+    {"type": "Feature", "properties": {"name": "丙国"}, "geometry": {"type": "MultiPolygon",
+        "coordinates": [[[[170, -10], [180, -10], [180, 10], [170, 10], [170, -10]]],
+                        [[[-180, -10], [-170, -10], [-170, 10], [-180, 10], [-180, -10]]]]}},
+    {"type": "Feature", "properties": {"name": "中国"}, "geometry": {"type": "Polygon",
+        "coordinates": [[[100, 20], [120, 20], [120, 40], [100, 40], [100, 20]]]}}
+]}"#;
+
+// 中国地图：两个相邻的省级区域
+const CHINA: &str = r#"{"type": "FeatureCollection", "features": [
+    {"type": "Feature", "properties": {"name": "北京"}, "geometry": {"type": "Polygon",
+        "coordinates": [[[110, 30], [115, 30], [115, 35], [110, 35], [110, 30]]]}},
+    {"type": "Feature", "properties": {"name": "河北"}, "geometry": {"type": "Polygon",
+        "coordinates": [[[115, 30], [120, 30], [120, 35], [115, 35], [115, 30]]]}}
+]}"#;
+
+fn builder() -> GeoIndexBuilder {
+    let mut builder = GeoIndexBuilder::new(WORLD.to_string(), CHINA.to_string());
+    builder.set_visited_places(vec!["乙国".to_string(), "中国-北京".to_string()]);
+    builder
+}
+
+fn saved_index(builder: &GeoIndexBuilder) -> Vec<u8> {
+    // 各测试并行运行，每次保存使用不同的文件
+    static SAVED: AtomicUsize = AtomicUsize::new(0);
+    let count = SAVED.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("geo-index-{}-{}.bin", std::process::id(), count));
+    builder.save_geo_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    data
+}
+
+fn from_geojson(tolerance: Option<f64>) -> GeoProcessor {
+    let mut processor = GeoProcessor::new();
+    processor.process_geojson(WORLD, CHINA, r#"["乙国", "中国-北京"]"#, SCALE, None, tolerance).unwrap();
+    processor
+}
+
+fn from_binary(data: &[u8], tolerance: Option<f64>) -> GeoProcessor {
+    let mut processor = GeoProcessor::new();
+    processor.load_binary(data, SCALE, None, None, tolerance).unwrap();
+    processor
+}
+
+// 网格上各点所在或最近的区域
+fn nearest_regions(processor: &GeoProcessor) -> Vec<Option<String>> {
+    let mut regions = Vec::new();
+    for lat in (-60..=60).step_by(3) {
+        for lon in (-180..180).step_by(3) {
+            let point = processor.lat_long_to_vector3(f64::from(lat) + 0.5, f64::from(lon) + 0.5, SCALE);
+            regions.push(processor.find_nearest_country(point.x, point.y, point.z, 0.0));
+        }
+    }
+    regions
+}
+
+#[test]
+fn saved_index_contains_prepared_data() {
+    let builder = builder();
+    let built = builder.build_geo_index().unwrap();
+    let decoded = GeoIndex::from_compressed(&saved_index(&builder)).unwrap();
+
+    assert_eq!(format!("{:?}", decoded.prepared), format!("{:?}", built.prepared));
+    assert_eq!(format!("{:?}", decoded.regions), format!("{:?}", built.regions));
+    assert!(decoded.regions.iter().all(|region| region.name != "中国"));
+
+    // 共享边界顶点的区域互为相邻
+    let adjacency = &decoded.prepared.adjacency;
+    assert!(adjacency["甲国"].contains("乙国"));
+    assert!(adjacency["中国-北京"].contains("中国-河北"));
+    assert!(adjacency["丙国"].is_empty());
+}
+
+#[test]
+fn load_binary_matches_process_geojson() {
+    let data = saved_index(&builder());
+
+    for tolerance in [None, Some(0.5)] {
+        let expected = from_geojson(tolerance);
+        let loaded = from_binary(&data, tolerance);
+        assert_eq!(loaded.get_lod_count(), expected.get_lod_count());
+        assert_eq!(nearest_regions(&loaded), nearest_regions(&expected));
+    }
+
+    let loaded = from_binary(&data, None);
+    let point = loaded.lat_long_to_vector3(0.0, 179.5, SCALE);
+    assert_eq!(loaded.find_nearest_country(point.x, point.y, point.z, 0.0).as_deref(), Some("丙国"));
+    let point = loaded.lat_long_to_vector3(32.0, 112.0, SCALE);
+    assert_eq!(loaded.find_nearest_country(point.x, point.y, point.z, 0.0).as_deref(), Some("中国-北京"));
+}
+
+#[test]
+fn keeps_double_precision_until_saved() {
+    // 东边界的经度无法用单精度表示，舍入后为10
+    let world = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"name": "甲国"}, "geometry": {"type": "Polygon",
+            "coordinates": [[[0, 0], [10.0000001, 0], [10.0000001, 10], [0, 10], [0, 0]]]}}
+    ]}"#;
+    let china = r#"{"type": "FeatureCollection", "features": []}"#;
+    let builder = GeoIndexBuilder::new(world.to_string(), china.to_string());
+
+    let built = builder.build_geo_index().unwrap();
+    let polygon = &built.regions[0].polygons[0];
+    assert_eq!(polygon[0][1], [10.0000001, 0.0]);
+    let region = RegionPolygon::from_rings("甲国", polygon).unwrap();
+    assert!(region.contains(10.00000005, 5.0));
+
+    let decoded = GeoIndex::from_compressed(&saved_index(&builder)).unwrap();
+    let polygon = &decoded.regions[0].polygons[0];
+    assert_eq!(polygon[0][1], [f64::from(10.0000001f32), 0.0]);
+    assert!(!RegionPolygon::from_rings("甲国", polygon).unwrap().contains(10.00000005, 5.0));
+}
+
+// 第1版索引的布局
+#[derive(Serialize)]
+struct GeoIndexV1<'a> {
+    regions: &'a [GeoRegion],
+}
+
+#[test]
+fn rejects_version_1_index() {
+    let built = builder().build_geo_index().unwrap();
+    let data = to_compressed_with_format(
+        &GeoIndexV1 { regions: &built.regions },
+        [1, 0],
+        None::<&()>,
+        SerializationFormat::default(),
+    ).unwrap();
+
+    assert!(GeoIndex::from_compressed(&data).is_err());
+}
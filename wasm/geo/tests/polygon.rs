@@ -0,0 +1,99 @@
+// 区域判断测试 - 点在多边形内、外、边上、内环中以及跨越180度经线的多边形，
+// 以及经纬度与三维坐标的相互转换。
+
+use geo_wasm::polygon::{vector3_to_lat_long, RegionPolygon};
+use geo_wasm::GeoProcessor;
+
+fn polygon(rings: &[&[[f64; 2]]]) -> RegionPolygon {
+    let rings: Vec<Vec<[f64; 2]>> = rings.iter().map(|ring| ring.to_vec()).collect();
+    RegionPolygon::from_rings("测试", &rings).unwrap()
+}
+
+const SQUARE: &[[f64; 2]] = &[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0], [0.0, 0.0]];
+const HOLE: &[[f64; 2]] = &[[4.0, 4.0], [6.0, 4.0], [6.0, 6.0], [4.0, 6.0], [4.0, 4.0]];
+
+#[test]
+fn point_inside_and_outside() {
+    let square = polygon(&[SQUARE]);
+
+    assert!(square.contains(5.0, 5.0));
+    assert!(square.contains(0.5, 9.5));
+    assert!(!square.contains(-0.5, 5.0));
+    assert!(!square.contains(5.0, 10.5));
+    // 在经纬度范围内但在凹多边形外
+    let notched = polygon(&[&[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [5.0, 5.0], [0.0, 10.0]]]);
+    assert!(!notched.contains(5.0, 8.0));
+    assert!(notched.contains(5.0, 2.0));
+}
+
+#[test]
+fn point_on_edge_is_inside() {
+    let square = polygon(&[SQUARE]);
+
+    assert!(square.contains(0.0, 5.0));
+    assert!(square.contains(10.0, 5.0));
+    assert!(square.contains(5.0, 0.0));
+    assert!(square.contains(5.0, 10.0));
+    assert!(square.contains(10.0, 10.0));
+    // 斜边
+    let triangle = polygon(&[&[[0.0, 0.0], [10.0, 0.0], [0.0, 10.0]]]);
+    assert!(triangle.contains(5.0, 5.0));
+    assert!(!triangle.contains(5.5, 5.5));
+}
+
+#[test]
+fn point_in_hole_is_outside() {
+    let with_hole = polygon(&[SQUARE, HOLE]);
+
+    assert!(!with_hole.contains(5.0, 5.0));
+    assert!(with_hole.contains(2.0, 2.0));
+    assert!(with_hole.contains(8.0, 5.0));
+    // 内环的边也是多边形的边界
+    assert!(with_hole.contains(4.0, 5.0));
+}
+
+#[test]
+fn polygon_across_antimeridian() {
+    let fiji = polygon(&[&[[170.0, -10.0], [-170.0, -10.0], [-170.0, 10.0], [170.0, 10.0]]]);
+
+    assert!(fiji.contains(180.0, 0.0));
+    assert!(fiji.contains(-180.0, 0.0));
+    assert!(fiji.contains(175.0, 5.0));
+    assert!(fiji.contains(-175.0, -5.0));
+    assert!(fiji.contains(-170.0, 0.0));
+    assert!(!fiji.contains(0.0, 0.0));
+    assert!(!fiji.contains(160.0, 0.0));
+    assert!(!fiji.contains(-160.0, 0.0));
+    assert!(!fiji.contains(175.0, 15.0));
+
+    // 沿180度经线闭合的环覆盖所有经度，不换算经度
+    let antarctica = polygon(&[&[[-180.0, -60.0], [0.0, -60.0], [180.0, -60.0], [180.0, -90.0], [-180.0, -90.0]]]);
+    assert!(antarctica.contains(0.0, -80.0));
+    assert!(antarctica.contains(179.0, -70.0));
+    assert!(antarctica.contains(-179.0, -70.0));
+    assert!(!antarctica.contains(0.0, -50.0));
+}
+
+#[test]
+fn lat_long_round_trip() {
+    let processor = GeoProcessor::new();
+
+    for lat in (-85..=85).step_by(5) {
+        for lon in (-175..=180).step_by(5) {
+            let (lat, lon) = (f64::from(lat), f64::from(lon));
+            for radius in [1.0, 2.5] {
+                let point = processor.lat_long_to_vector3(lat, lon, radius);
+                let (round_lat, round_lon) = vector3_to_lat_long(&point);
+                assert!((round_lat - lat).abs() < 1e-9, "纬度 {} 转换后为 {}", lat, round_lat);
+                // 180度与-180度为同一经线
+                let lon_error = ((round_lon - lon + 540.0).rem_euclid(360.0) - 180.0).abs();
+                assert!(lon_error < 1e-9, "经度 {} 转换后为 {}", lon, round_lon);
+            }
+        }
+    }
+
+    // 超出范围的经度先换算到 -180 到 180 之间
+    let point = processor.lat_long_to_vector3(30.0, 190.0, 1.0);
+    let (lat, lon) = vector3_to_lat_long(&point);
+    assert!((lat - 30.0).abs() < 1e-9 && (lon + 170.0).abs() < 1e-9);
+}
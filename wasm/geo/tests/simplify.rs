@@ -0,0 +1,46 @@
+// 边界线简化测试 - 共线点被舍弃、按容差保留偏离的点、首尾点总是保留。
+
+use geo_wasm::simplify::simplify_indices;
+
+#[test]
+fn removes_collinear_points() {
+    let line = [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 0.0], [3.0, 1.0], [3.0, 2.0], [3.0, 3.0]];
+
+    assert_eq!(simplify_indices(&line, 0.01), vec![0, 3, 6]);
+    // 容差为0时保留全部顶点，包括共线点
+    assert_eq!(simplify_indices(&line, 0.0), vec![0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn keeps_points_beyond_tolerance() {
+    // 中间点偏离首尾连线0.5度
+    let line = [[0.0, 0.0], [5.0, 0.5], [10.0, 0.0]];
+
+    assert_eq!(simplify_indices(&line, 0.4), vec![0, 1, 2]);
+    assert_eq!(simplify_indices(&line, 0.5), vec![0, 2]);
+    assert_eq!(simplify_indices(&line, 0.6), vec![0, 2]);
+
+    // 偏离较小的点在较大的容差下被舍弃，偏离较大的点仍保留
+    let line = [[0.0, 0.0], [2.0, 0.2], [5.0, 3.0], [8.0, 0.2], [10.0, 0.0]];
+    assert_eq!(simplify_indices(&line, 0.1), vec![0, 1, 2, 3, 4]);
+    assert_eq!(simplify_indices(&line, 1.0), vec![0, 2, 4]);
+}
+
+#[test]
+fn always_keeps_endpoints() {
+    // 闭合环：首尾相同，其余点按到首点的距离判断
+    let ring = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0], [0.0, 0.0]];
+    let kept = simplify_indices(&ring, 100.0);
+    assert_eq!(kept, vec![0, 4]);
+
+    for tolerance in [0.0, 0.1, 5.0, 1e9] {
+        let kept = simplify_indices(&ring, tolerance);
+        assert_eq!(kept.first(), Some(&0));
+        assert_eq!(kept.last(), Some(&(ring.len() - 1)));
+    }
+
+    // 不超过两个点时原样保留
+    assert_eq!(simplify_indices(&[[1.0, 1.0], [2.0, 2.0]], 100.0), vec![0, 1]);
+    assert_eq!(simplify_indices(&[[1.0, 1.0]], 100.0), vec![0]);
+    assert!(simplify_indices(&[], 1.0).is_empty());
+}
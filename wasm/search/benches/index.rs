@@ -0,0 +1,178 @@
+//! 索引构建和查询延迟基准测试 - 用合成语料评估排序和索引格式的修改
+//!
+//! 语料由固定种子生成，文章数量默认为500，可通过环境变量 `SEARCH_BENCH_ARTICLES` 修改。
+//! 测量构建时间、冷加载时间（解压和校验索引、加载到全局搜索引擎）和各代表性查询的延迟，
+//! 索引文件大小在开始时输出。运行 `cargo bench -p search-wasm`。
+
+use std::time::Duration;
+use chrono::{TimeZone, Utc};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use search_wasm::builder::SearchBuilder;
+use search_wasm::engine::decode_index;
+use utils_common::models::{ArticleMetadata, Heading, Visibility};
+
+/// 默认文章数量
+const DEFAULT_ARTICLES: usize = 500;
+
+/// 代表性查询：常见英文词、汉字词组、多词、前缀、拼写错误、无结果
+const QUERIES: &[(&str, &str)] = &[
+    ("term", "rust"),
+    ("cjk", "安装"),
+    ("multi_word", "webassembly performance"),
+    ("prefix", "kube"),
+    ("typo", "kubernets"),
+    ("no_match", "zzzzzz"),
+];
+
+const ENGLISH_WORDS: &[&str] = &[
+    "rust", "webassembly", "performance", "kubernetes", "docker", "search", "index", "compiler",
+    "memory", "async", "runtime", "network", "database", "cache", "deploy", "release",
+    "browser", "server", "thread", "pipeline", "config", "module", "testing", "latency",
+];
+
+const CHINESE_WORDS: &[&str] = &[
+    "安装", "配置", "入门", "性能", "部署", "编译", "内存", "异步", "网络", "数据库",
+    "缓存", "发布", "浏览器", "服务器", "线程", "测试", "模块", "索引", "搜索", "优化",
+];
+
+const TAGS: &[&str] = &["rust", "wasm", "docker", "linux", "web", "database", "tutorial", "notes"];
+
+/// 固定种子的伪随机数生成器（xorshift64），保证每次生成相同的语料
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+
+    /// 中英文混合的一段文字
+    fn sentence(&mut self, words: usize) -> String {
+        (0..words)
+            .map(|_| if self.below(3) == 0 { self.pick(CHINESE_WORDS) } else { self.pick(ENGLISH_WORDS) })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// 生成合成语料 - 每篇文章有标题、标签、4个章节和中英文混合的正文
+fn corpus(count: usize) -> Vec<ArticleMetadata> {
+    let mut rng = Rng(0x9e3779b97f4a7c15);
+    (0..count)
+        .map(|n| {
+            let title_words = 3 + rng.below(4);
+            let title = rng.sentence(title_words);
+            let mut content = String::new();
+            let mut headings = Vec::new();
+            for section in 0..4 {
+                let text = format!("{} {}", section + 1, rng.sentence(2));
+                let position = content.len();
+                content.push_str(&text);
+                content.push(' ');
+                let body_words = 40 + rng.below(40);
+                content.push_str(&rng.sentence(body_words));
+                content.push(' ');
+                headings.push(Heading {
+                    level: 2,
+                    text,
+                    position,
+                    end_position: Some(content.len()),
+                    raw_text: None,
+                });
+            }
+            let mut tags: Vec<String> = (0..2).map(|_| rng.pick(TAGS).to_string()).collect();
+            tags.sort();
+            tags.dedup();
+
+            ArticleMetadata {
+                id: format!("articles/{:05}", n),
+                summary: content.chars().take(200).collect(),
+                date: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + chrono::Duration::days(n as i64),
+                tags,
+                url: format!("/articles/{:05}", n),
+                page_type: "article".to_string(),
+                headings,
+                lang: String::new(),
+                media_text: rng.sentence(4),
+                visibility: Visibility::default(),
+                featured: false,
+                series: None,
+                author: None,
+                word_count: 0,
+                title,
+                content,
+            }
+        })
+        .collect()
+}
+
+fn article_count() -> usize {
+    std::env::var("SEARCH_BENCH_ARTICLES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_ARTICLES)
+}
+
+fn builder(articles: &[ArticleMetadata]) -> SearchBuilder {
+    let mut builder = SearchBuilder::new();
+    for article in articles {
+        builder.add_article(article.clone());
+    }
+    builder
+}
+
+/// 按索引器的方式保存索引文件并读回，得到与部署时相同的索引数据
+fn index_data(articles: &[ArticleMetadata]) -> Vec<u8> {
+    let path = std::env::temp_dir().join(format!("search-bench-{}.bin", std::process::id()));
+    builder(articles).save_search_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    data
+}
+
+fn benchmarks(c: &mut Criterion) {
+    let count = article_count();
+    let articles = corpus(count);
+    let data = index_data(&articles);
+    println!("索引大小: {} 字节（{} 篇文章，平均每篇 {} 字节）", data.len(), count, data.len() / count.max(1));
+
+    let mut build = c.benchmark_group("build");
+    build.throughput(Throughput::Elements(count as u64));
+    build.sample_size(10);
+    build.measurement_time(Duration::from_secs(10));
+    build.bench_function(format!("{}_articles", count), |b| {
+        b.iter_batched(|| builder(&articles), |builder| builder.build_search_index().unwrap(), BatchSize::LargeInput)
+    });
+    build.finish();
+
+    let mut load = c.benchmark_group("cold_load");
+    load.throughput(Throughput::Bytes(data.len() as u64));
+    load.bench_function("decode", |b| b.iter(|| decode_index(black_box(&data)).unwrap()));
+    load.bench_function("load_search_index", |b| {
+        b.iter(|| search_wasm::load_search_index(black_box(&data), None).map_err(|_| "加载索引失败").unwrap())
+    });
+    load.finish();
+
+    search_wasm::load_search_index(&data, None).map_err(|_| "加载索引失败").unwrap();
+    let mut query = c.benchmark_group("query");
+    for (name, text) in QUERIES {
+        let request = serde_json::json!({ "query": text }).to_string();
+        query.bench_function(*name, |b| {
+            b.iter(|| search_wasm::search_loaded_indexes(black_box(&request)).map_err(|_| "搜索失败").unwrap())
+        });
+    }
+    query.finish();
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
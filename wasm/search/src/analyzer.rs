@@ -0,0 +1,208 @@
+// 字段分析器 - 构建索引时把各字段的文本切分为词条，搜索时对查询做相同的处理
+// 标题使用 exact：保留短词和原样的单词；正文和媒体说明使用 segment：切分汉字词组并去除
+// 停用词和过短的词。各字段使用的分析器标识写入索引配置，搜索时按标识重建相同的分析器。
+
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use crate::builder::SearchBuilderConfig;
+
+/// 保留原样单词的分析器标识
+pub const EXACT_ANALYZER: &str = "exact";
+
+/// 切分词组并去除停用词的分析器标识
+pub const SEGMENT_ANALYZER: &str = "segment";
+
+/// 字段分析器
+pub trait Analyzer {
+    /// 分析器标识，写入索引
+    fn id(&self) -> &str;
+
+    /// 把文本切分为索引词条，词条为小写且不重复
+    fn analyze(&self, text: &str) -> Vec<String>;
+
+    /// 词条是否写入索引，外部分词器产生的词条同样按此筛选
+    fn accepts(&self, term: &str) -> bool;
+
+    /// 把查询规范化为在该字段中查找的词条，该字段的索引不可能包含查询时返回None
+    fn query_term(&self, query: &str) -> Option<String> {
+        let term = query.trim().to_lowercase();
+        (!term.is_empty() && self.accepts(&term)).then_some(term)
+    }
+}
+
+impl<T: Analyzer + ?Sized> Analyzer for &T {
+    fn id(&self) -> &str {
+        (**self).id()
+    }
+
+    fn analyze(&self, text: &str) -> Vec<String> {
+        (**self).analyze(text)
+    }
+
+    fn accepts(&self, term: &str) -> bool {
+        (**self).accepts(term)
+    }
+
+    fn query_term(&self, query: &str) -> Option<String> {
+        (**self).query_term(query)
+    }
+}
+
+/// 各字段使用的分析器标识 - 写入索引配置
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct FieldAnalyzers {
+    /// 标题
+    pub title: String,
+    /// 章节标题
+    pub heading: String,
+    /// 正文
+    pub content: String,
+    /// 媒体说明
+    pub media: String,
+}
+
+impl Default for FieldAnalyzers {
+    fn default() -> Self {
+        Self {
+            title: EXACT_ANALYZER.to_string(),
+            heading: EXACT_ANALYZER.to_string(),
+            content: SEGMENT_ANALYZER.to_string(),
+            media: SEGMENT_ANALYZER.to_string(),
+        }
+    }
+}
+
+impl FieldAnalyzers {
+    /// 所有字段的分析器标识
+    pub fn ids(&self) -> [&str; 4] {
+        [&self.title, &self.heading, &self.content, &self.media]
+    }
+}
+
+/// 按标识创建内置分析器，参数取自构建配置；不是内置分析器时返回None
+pub fn builtin<'a>(id: &str, config: &'a SearchBuilderConfig) -> Option<Box<dyn Analyzer + 'a>> {
+    match id {
+        EXACT_ANALYZER => Some(Box::new(ExactAnalyzer { config })),
+        SEGMENT_ANALYZER => Some(Box::new(SegmentAnalyzer { config })),
+        _ => None,
+    }
+}
+
+/// 按索引中记录的分析器规范化查询；自定义分析器在运行时不可用，只转为小写
+pub fn query_term(id: &str, config: &SearchBuilderConfig, query: &str) -> Option<String> {
+    match builtin(id, config) {
+        Some(analyzer) => analyzer.query_term(query),
+        None => Some(query.trim().to_lowercase()).filter(|term| !term.is_empty()),
+    }
+}
+
+/// 保留原样单词的分析器 - 在切分出的词组之外加入按空白分隔的各个单词（包括单字），
+/// 不去除停用词
+pub struct ExactAnalyzer<'a> {
+    config: &'a SearchBuilderConfig,
+}
+
+impl Analyzer for ExactAnalyzer<'_> {
+    fn id(&self) -> &str {
+        EXACT_ANALYZER
+    }
+
+    fn analyze(&self, text: &str) -> Vec<String> {
+        let mut terms = segment(text, self.config.ngram_span);
+        let mut seen: HashSet<String> = terms.iter().cloned().collect();
+        for word in text.to_lowercase().split_whitespace().map(trim_word) {
+            if !word.is_empty() && seen.insert(word.to_string()) {
+                terms.push(word.to_string());
+            }
+        }
+        terms
+    }
+
+    fn accepts(&self, term: &str) -> bool {
+        !term.is_empty()
+    }
+
+    fn query_term(&self, query: &str) -> Option<String> {
+        let query = query.trim().to_lowercase();
+        // 单个单词的查询与索引中的单词一样去掉首尾的标点
+        let term = if query.contains(char::is_whitespace) { query.as_str() } else { trim_word(&query) };
+        (!term.is_empty()).then(|| term.to_string())
+    }
+}
+
+/// 切分词组的分析器 - 去除停用词和短于最小长度的词条
+pub struct SegmentAnalyzer<'a> {
+    config: &'a SearchBuilderConfig,
+}
+
+impl Analyzer for SegmentAnalyzer<'_> {
+    fn id(&self) -> &str {
+        SEGMENT_ANALYZER
+    }
+
+    fn analyze(&self, text: &str) -> Vec<String> {
+        segment(text, self.config.ngram_span)
+            .into_iter()
+            .filter(|term| self.accepts(term))
+            .collect()
+    }
+
+    fn accepts(&self, term: &str) -> bool {
+        term.len() >= self.config.min_term_length && !self.config.stop_words.iter().any(|word| word == term)
+    }
+}
+
+/// 去掉单词首尾的标点
+fn trim_word(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '_' && c != '-')
+}
+
+/// 切分文本 - 字母数字（包括汉字）组成的单词整体作为词条，其他非空白、非ASCII标点的
+/// 连续字符按1到 `ngram_span` 个组合，只保留长度不少于2字节的词条，并过滤纯数字
+pub fn segment(text: &str, ngram_span: usize) -> Vec<String> {
+    let clean_text = text.trim().to_lowercase();
+    let mut keywords = HashSet::new();
+    let mut current_word = String::new();
+    let mut chinese_chars: Vec<char> = Vec::new();
+
+    // 连续字符的组合（1到 ngram_span 个）
+    let flush_chinese = |chinese_chars: &mut Vec<char>, keywords: &mut HashSet<String>| {
+        for i in 1..=chinese_chars.len().min(ngram_span) {
+            for start in 0..=chinese_chars.len() - i {
+                let term: String = chinese_chars[start..start + i].iter().collect();
+                if term.len() >= 2 {
+                    keywords.insert(term);
+                }
+            }
+        }
+        chinese_chars.clear();
+    };
+
+    for c in clean_text.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '-' {
+            flush_chinese(&mut chinese_chars, &mut keywords);
+            current_word.push(c);
+        } else {
+            if current_word.len() >= 2 {
+                keywords.insert(std::mem::take(&mut current_word));
+            }
+            if c.is_whitespace() || c.is_ascii_punctuation() {
+                flush_chinese(&mut chinese_chars, &mut keywords);
+            } else {
+                chinese_chars.push(c);
+            }
+        }
+    }
+    if current_word.len() >= 2 {
+        keywords.insert(current_word);
+    }
+    flush_chinese(&mut chinese_chars, &mut keywords);
+
+    // 过滤纯数字的关键词
+    let mut keywords: Vec<String> = keywords.into_iter()
+        .filter(|keyword| !keyword.chars().all(|c| c.is_ascii_digit()))
+        .collect();
+    keywords.sort();
+    keywords
+}
@@ -1,211 +1,695 @@
-use utils_common::models::ArticleMetadata;
-use utils_common::compression::to_compressed;
-use crate::models::{ArticleSearchIndex, HeadingIndexEntry};
-use std::collections::{HashMap, HashSet};
+use utils_common::models::{ArticleMetadata, Heading, IndexMetadata, IndexType, Visibility};
+use utils_common::compression::{to_compressed_with_format, SerializationFormat};
+use utils_common::text::{detect_language, normalize_heading_text};
+use crate::dictionary::TermDictionary;
+use crate::positions::{find_positions, PositionIndex, TermPositions};
+use crate::models::{ArticleSearchIndex, CommonTerm, FieldAverages, FieldLengths, HeadingIndexEntry, TermField};
+use crate::content::{self, ContentRange};
+use crate::related;
+use crate::analyzer::{self, Analyzer, FieldAnalyzers};
+use crate::engine::INDEX_VERSION;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::collections::hash_map::Entry;
 use std::fs::File;
 use std::io::Write;
-use regex::Regex;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
 
-/// 简单移除字符串中的HTML标签
-fn remove_html_tags(text: &str) -> String {
-    let mut result = String::new();
-    let mut in_tag = false;
-    
-    for c in text.chars() {
-        if c == '<' {
-            in_tag = true;
-        } else if c == '>' {
-            in_tag = false;
-        } else if !in_tag {
-            result.push(c);
+/// 默认停用词表
+const DEFAULT_STOP_WORDS: &[&str] = &[
+    "的", "是", "在", "了", "和", "与", "或", "而", "但", "如果", "因为",
+    "所以", "这", "那", "这个", "那个", "这些", "那些", "并", "可以", "把",
+    "被", "将", "已", "就", "也", "很", "到", "上", "下", "中", "为"
+];
+
+/// 搜索索引构建配置 - 写入索引，运行时增删文章和搜索时按构建时的配置处理
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct SearchBuilderConfig {
+    /// 关键词的最小长度（字节），更短的词不写入索引
+    pub min_term_length: usize,
+    /// 常用词汇的最大数量
+    pub max_common_terms: usize,
+    /// 文档频率阈值 - 出现在超过该比例文章中的词汇（如“文章”“阅读”等模板词）
+    /// 在常用词汇和搜索建议中按比例降权
+    pub common_term_max_df: f64,
+    /// 连续汉字切分词组的最大字数
+    pub ngram_span: usize,
+    /// 各字段匹配的分数系数
+    pub field_weights: FieldWeights,
+    /// 停用词，不写入正文和媒体说明索引
+    pub stop_words: Vec<String>,
+    /// 是否索引普通页面（页面类型为 page）
+    pub index_pages: bool,
+    /// 每篇文章预先计算的相关文章数量，为0时不计算
+    pub related_count: usize,
+    /// 各字段使用的分析器标识
+    pub analyzers: FieldAnalyzers,
+}
+
+impl Default for SearchBuilderConfig {
+    fn default() -> Self {
+        Self {
+            min_term_length: 2,
+            max_common_terms: 500,
+            common_term_max_df: 0.5,
+            ngram_span: 3,
+            field_weights: FieldWeights::default(),
+            stop_words: DEFAULT_STOP_WORDS.iter().map(|word| word.to_string()).collect(),
+            index_pages: true,
+            related_count: 5,
+            analyzers: FieldAnalyzers::default(),
         }
     }
+}
+
+/// 字段分数系数 - 搜索时与各字段的基础分数相乘，默认均为1
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct FieldWeights {
+    /// 标题关键词
+    pub title: f64,
+    /// 章节标题关键词
+    pub heading: f64,
+    /// 正文关键词
+    pub content: f64,
+    /// 媒体说明关键词
+    pub media: f64,
+}
+
+impl Default for FieldWeights {
+    fn default() -> Self {
+        Self { title: 1.0, heading: 1.0, content: 1.0, media: 1.0 }
+    }
+}
+
+/// 补充缺失的语言信息
+fn fill_language(article: &mut ArticleMetadata) {
+    if article.lang.is_empty() {
+        article.lang = detect_language(&format!("{} {}", article.title, article.content)).to_string();
+    }
+}
+
+/// 文档频率权重 - 出现在超过 `max_df` 比例文章中的词汇按比例降权，
+/// 出现在所有文章中时权重为0；只出现在一篇文章中的词汇不降权
+pub(crate) fn document_frequency_weight(documents: usize, total: usize, max_df: f64) -> f64 {
+    if documents <= 1 || total == 0 || max_df >= 1.0 {
+        return 1.0;
+    }
     
-    result.trim().to_string()
+    let ratio = documents as f64 / total as f64;
+    if ratio <= max_df {
+        1.0
+    } else {
+        ((1.0 - ratio) / (1.0 - max_df)).max(0.0)
+    }
+}
+
+/// 输出索引构建统计
+fn print_stats(index: &ArticleSearchIndex) {
+    println!("索引构建统计:");
+    println!("- 文章数量: {}", index.articles.len() - index.tombstones.len());
+    if !index.tombstones.is_empty() {
+        println!("- 已移除文章: {}", index.tombstones.len());
+    }
+    println!("- 标题词汇: {}", index.title_term_index.len());
+    println!("- 标题结构: {}", index.heading_index.len());
+    println!("- 内容词汇: {}", index.content_term_index.len());
+    println!("- 媒体词汇: {}", index.media_term_index.len());
+    println!("- 常用词汇: {}", index.common_terms.len());
+    println!("- 平均正文词条: {:.1}", index.average_field_lengths.content);
+}
+
+/// 生成写入索引文件头部的索引元数据
+fn index_metadata(index: &ArticleSearchIndex, version: [u8; 2]) -> IndexMetadata {
+    let live: Vec<&ArticleMetadata> = index.articles.iter()
+        .enumerate()
+        .filter(|(article_id, _)| !index.tombstones.contains(article_id))
+        .map(|(_, article)| article)
+        .collect();
+    let tags: HashSet<&str> = live.iter()
+        .flat_map(|article| article.tags.iter().map(String::as_str))
+        .collect();
+    
+    IndexMetadata {
+        article_count: live.len(),
+        tag_count: tags.len(),
+        created_at: Utc::now(),
+        version: format!("{}.{}", version[0], version[1]),
+        index_type: IndexType::Search,
+        token_count: index.title_term_index.len() + index.content_term_index.len() + index.media_term_index.len(),
+    }
+}
+
+/// 校准标题位置，返回按文档顺序排列的（级别, 规范化文本, 位置）
+///
+/// 索引器记录的位置是标题文本在正文中第一次出现的位置，可能指向标题之前对同一文本的
+/// 提及，找不到时为0。这里按文档顺序逐个校准：记录的位置不早于上一个标题且确实是标题
+/// 文本时直接使用，否则从上一个标题之后重新查找（忽略大小写和空白差异），仍找不到时
+/// 紧跟上一个标题。校准后的位置单调不减，并且都在字符边界上。
+fn reconcile_heading_positions(headings: &[Heading], content: &str) -> Vec<(usize, String, usize)> {
+    let mut cursor = 0;
+    headings.iter()
+        .filter_map(|heading| {
+            // 旧索引器产生的标题可能未规范化
+            let text = normalize_heading_text(&heading.text);
+            if text.is_empty() {
+                return None;
+            }
+
+            let recorded = (heading.position >= cursor)
+                .then(|| heading_match_end(content, heading.position, &text))
+                .flatten()
+                .map(|end| (heading.position, end));
+            let found = recorded.or_else(|| {
+                content[cursor..].char_indices()
+                    .map(|(offset, _)| cursor + offset)
+                    .find_map(|start| heading_match_end(content, start, &text).map(|end| (start, end)))
+            });
+
+            let position = match found {
+                Some((start, end)) => {
+                    cursor = end;
+                    start
+                }
+                None => cursor,
+            };
+            Some((heading.level, text, position))
+        })
+        .collect()
+}
+
+/// 正文在 `start` 处是否为标题文本（忽略大小写和空白），是则返回匹配结束的位置
+fn heading_match_end(content: &str, start: usize, text: &str) -> Option<usize> {
+    if start >= content.len() || !content.is_char_boundary(start) {
+        return None;
+    }
+
+    let mut expected = text.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    let mut end = start;
+    for (offset, c) in content[start..].char_indices() {
+        if expected.peek().is_none() {
+            break;
+        }
+        if c.is_whitespace() {
+            // 标题文本不能从空白开始匹配
+            if offset == 0 {
+                return None;
+            }
+            continue;
+        }
+        for lower in c.to_lowercase() {
+            if expected.next() != Some(lower) {
+                return None;
+            }
+        }
+        end = start + offset + c.len_utf8();
+    }
+
+    expected.peek().is_none().then_some(end)
+}
+
+/// 查找各关键词在小写化文本中的位置，文本中找不到的关键词不记录
+fn term_positions(text: &str, terms: &HashSet<String>) -> HashMap<String, Vec<u32>> {
+    let text = text.to_lowercase();
+    terms.iter()
+        .map(|term| (term.clone(), find_positions(&text, term)))
+        .filter(|(_, positions)| !positions.is_empty())
+        .collect()
+}
+
+/// 按小写化文本中的位置取出词条在原文中的形式，小写化改变了字节长度而无法对应时返回None
+fn original_form<'a>(text: &'a str, term: &str, position: u32) -> Option<&'a str> {
+    let start = position as usize;
+    let form = text.get(start..start + term.len())?;
+    (form.to_lowercase() == term).then_some(form)
+}
+
+/// 单篇文章对建议词典中一个词条的贡献
+#[derive(Clone, Debug)]
+struct TermContribution {
+    /// 对常用词汇频率的贡献
+    frequency: usize,
+    /// 出现的最重要的字段
+    field: TermField,
+    /// 原文中各大小写形式的权重，与频率使用相同的字段权重
+    forms: HashMap<String, usize>,
+}
+
+impl TermContribution {
+    fn new(field: TermField) -> Self {
+        Self { frequency: 0, field, forms: HashMap::new() }
+    }
+
+    /// 记录一次出现
+    fn add(&mut self, weight: usize, field: TermField) {
+        self.frequency += weight;
+        self.field = self.field.min(field);
+    }
+
+    /// 记录原文中的形式
+    fn add_forms<'a>(&mut self, forms: impl IntoIterator<Item = &'a str>, weight: usize) {
+        for form in forms {
+            *self.forms.entry(form.to_string()).or_insert(0) += weight;
+        }
+    }
+
+    /// 累加另一篇文章的贡献
+    fn absorb(&mut self, other: TermContribution) {
+        self.add(other.frequency, other.field);
+        for (form, weight) in other.forms {
+            *self.forms.entry(form).or_insert(0) += weight;
+        }
+    }
+
+    /// 建议词典条目 - 展示形式取权重最高的形式，权重相同时取字节序较小的，没有记录时使用词条本身
+    fn into_common_term(self, term: &str, frequency: usize) -> CommonTerm {
+        let display = self.forms.into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+            .map_or_else(|| term.to_string(), |(form, _)| form);
+        CommonTerm { frequency, display, field: self.field }
+    }
+}
+
+/// 单篇文章的索引词条 - 构建索引和在已构建的索引中增删文章时共用
+struct ArticleTerms {
+    /// 标题关键词（包括标题中的各个单词）
+    title_terms: HashSet<String>,
+    /// 标题结构
+    headings: HashMap<String, HeadingIndexEntry>,
+    /// 每个标题的关键词
+    heading_terms: Vec<(String, Vec<String>)>,
+    /// 正文关键词
+    content_terms: HashSet<String>,
+    /// 媒体说明关键词
+    media_terms: HashSet<String>,
+    /// 标题关键词在小写化标题中的位置
+    title_positions: HashMap<String, Vec<u32>>,
+    /// 正文关键词在小写化正文中的位置
+    content_positions: HashMap<String, Vec<u32>>,
+    /// 媒体说明关键词在小写化媒体说明中的位置
+    media_positions: HashMap<String, Vec<u32>>,
+    /// 对常用词汇统计的贡献
+    frequency: HashMap<String, TermContribution>,
+    /// 各字段的词条数量
+    lengths: FieldLengths,
+}
+
+impl ArticleTerms {
+    /// 把词条写入倒排列表，标题结构直接写入索引
+    fn insert_into(&self, index: &mut ArticleSearchIndex, postings: &mut IndexPostings, article_id: usize) {
+        for term in &self.title_terms {
+            postings.title.entry(term.clone()).or_default().insert(article_id);
+        }
+        for term in &self.content_terms {
+            postings.content.entry(term.clone()).or_default().insert(article_id);
+        }
+        for term in &self.media_terms {
+            postings.media.entry(term.clone()).or_default().insert(article_id);
+        }
+        for (positions, field) in [
+            (&mut postings.title_positions, &self.title_positions),
+            (&mut postings.content_positions, &self.content_positions),
+            (&mut postings.media_positions, &self.media_positions),
+        ] {
+            for (term, term_positions) in field {
+                positions.entry(term.clone()).or_default().insert(article_id, term_positions.clone());
+            }
+        }
+        for (heading_id, keywords) in &self.heading_terms {
+            for keyword in keywords {
+                index.heading_term_index.entry(keyword.clone()).or_default().insert(heading_id.clone());
+            }
+        }
+        index.heading_index.extend(self.headings.iter().map(|(id, entry)| (id.clone(), entry.clone())));
+    }
+
+    /// 从倒排列表和索引中移除词条，不再指向任何文章的词汇一并删除
+    fn remove_from(&self, index: &mut ArticleSearchIndex, postings: &mut IndexPostings, article_id: usize) {
+        fn remove_posting(map: &mut BTreeMap<String, BTreeSet<usize>>, term: &str, article_id: usize) {
+            if let Some(article_ids) = map.get_mut(term) {
+                article_ids.remove(&article_id);
+                if article_ids.is_empty() {
+                    map.remove(term);
+                }
+            }
+        }
+
+        for term in &self.title_terms {
+            remove_posting(&mut postings.title, term, article_id);
+        }
+        for term in &self.content_terms {
+            remove_posting(&mut postings.content, term, article_id);
+        }
+        for term in &self.media_terms {
+            remove_posting(&mut postings.media, term, article_id);
+        }
+        for (positions, field) in [
+            (&mut postings.title_positions, &self.title_positions),
+            (&mut postings.content_positions, &self.content_positions),
+            (&mut postings.media_positions, &self.media_positions),
+        ] {
+            for term in field.keys() {
+                if let Some(articles) = positions.get_mut(term) {
+                    articles.remove(&article_id);
+                    if articles.is_empty() {
+                        positions.remove(term);
+                    }
+                }
+            }
+        }
+        for (heading_id, keywords) in &self.heading_terms {
+            for keyword in keywords {
+                if let Some(heading_ids) = index.heading_term_index.get_mut(keyword) {
+                    heading_ids.remove(heading_id);
+                    if heading_ids.is_empty() {
+                        index.heading_term_index.remove(keyword);
+                    }
+                }
+            }
+            index.heading_index.remove(heading_id);
+        }
+    }
 }
 
+/// 构建中的倒排列表 - 词典构建后不可修改，先在这里累积词条，最后统一写入索引
+#[derive(Default)]
+struct IndexPostings {
+    title: BTreeMap<String, BTreeSet<usize>>,
+    content: BTreeMap<String, BTreeSet<usize>>,
+    media: BTreeMap<String, BTreeSet<usize>>,
+    title_positions: TermPositions,
+    content_positions: TermPositions,
+    media_positions: TermPositions,
+}
+
+impl IndexPostings {
+    /// 读取索引中已有的词典
+    fn from_index(index: &ArticleSearchIndex) -> Self {
+        IndexPostings {
+            title: index.title_term_index.to_postings(),
+            content: index.content_term_index.to_postings(),
+            media: index.media_term_index.to_postings(),
+            title_positions: index.title_position_index.to_positions(),
+            content_positions: index.content_position_index.to_positions(),
+            media_positions: index.media_position_index.to_positions(),
+        }
+    }
+
+    /// 重新构建词典并写入索引
+    fn write_to(self, index: &mut ArticleSearchIndex) {
+        index.title_term_index = TermDictionary::from_postings(self.title);
+        index.content_term_index = TermDictionary::from_postings(self.content);
+        index.media_term_index = TermDictionary::from_postings(self.media);
+        index.title_position_index = PositionIndex::from_positions(self.title_positions);
+        index.content_position_index = PositionIndex::from_positions(self.content_positions);
+        index.media_position_index = PositionIndex::from_positions(self.media_positions);
+    }
+}
+
+/// 压缩已移除文章的位置 - 删除已移除的文章，其余文章按原顺序重新编号
+///
+/// 倒排列表、位置索引和与文章一一对应的列表随之改写；标题ID以文章ID开头，
+/// 不受影响。相关文章在压缩后重新计算。
+fn compact_tombstones(index: &mut ArticleSearchIndex, postings: &mut IndexPostings) -> Result<(), String> {
+    if index.tombstones.is_empty() {
+        return Ok(());
+    }
+
+    // 原位置到新位置的映射，已移除的文章为None
+    let mut next = 0;
+    let remap: Vec<Option<usize>> = (0..index.articles.len())
+        .map(|article_id| {
+            if index.tombstones.contains(&article_id) {
+                return None;
+            }
+            next += 1;
+            Some(next - 1)
+        })
+        .collect();
+    let new_id = |article_id: usize| remap.get(article_id).copied().flatten();
+
+    fn retain_live<T>(items: &mut Vec<T>, remap: &[Option<usize>]) {
+        let mut article_id = 0;
+        items.retain(|_| {
+            article_id += 1;
+            remap.get(article_id - 1).is_some_and(Option::is_some)
+        });
+    }
+    retain_live(&mut index.articles, &remap);
+    retain_live(&mut index.field_lengths, &remap);
+    retain_live(&mut index.content_ranges, &remap);
+    index.related.clear();
+
+    for map in [&mut postings.title, &mut postings.content, &mut postings.media] {
+        for article_ids in map.values_mut() {
+            *article_ids = article_ids.iter().filter_map(|&article_id| new_id(article_id)).collect();
+        }
+        map.retain(|_, article_ids| !article_ids.is_empty());
+    }
+    for positions in [&mut postings.title_positions, &mut postings.content_positions, &mut postings.media_positions] {
+        for articles in positions.values_mut() {
+            *articles = std::mem::take(articles).into_iter()
+                .filter_map(|(article_id, term_positions)| Some((new_id(article_id)?, term_positions)))
+                .collect();
+        }
+        positions.retain(|_, articles| !articles.is_empty());
+    }
+
+    index.tombstones.clear();
+    index.rebuild_ordinals()
+}
+
+/// 默认的页面类型分数系数 - 工具页面等普通页面排在文章之后
+const DEFAULT_PAGE_TYPE_BOOSTS: &[(&str, f64)] = &[("article", 1.0), ("page", 0.6)];
+
+/// 内置分词器的标识
+pub const BUILTIN_TOKENIZER: &str = "builtin";
+
+/// 外部分词函数 - 接收文本，返回词汇列表
+pub type TokenizeFn = Box<dyn Fn(&str) -> Result<Vec<String>, String>>;
+
 /// 搜索索引构建器
 pub struct SearchBuilder {
     articles: Vec<ArticleMetadata>,
+    page_type_boosts: HashMap<String, f64>,
+    /// 外部分词器的标识和分词函数，为空时使用各字段的分析器切分文本
+    tokenizer: Option<(String, TokenizeFn)>,
+    /// 自定义分析器，按标识查找，优先于内置分析器
+    analyzers: HashMap<String, Box<dyn Analyzer>>,
+    config: SearchBuilderConfig,
+    /// 增量更新时加载的已有索引，`articles` 中的文章在构建时写入该索引
+    base: Option<ArticleSearchIndex>,
+    /// 增量更新时待从已有索引中移除的文章ID，构建时与新增和修改的文章一起处理
+    removed: BTreeSet<String>,
+    /// 保存索引文件使用的序列化格式
+    format: SerializationFormat,
+}
+
+impl Default for SearchBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SearchBuilder {
     /// 创建新的搜索索引构建器
     pub fn new() -> Self {
+        Self::with_config(SearchBuilderConfig::default())
+    }
+
+    /// 使用指定配置创建搜索索引构建器
+    pub fn with_config(config: SearchBuilderConfig) -> Self {
         Self {
             articles: Vec::new(),
+            page_type_boosts: DEFAULT_PAGE_TYPE_BOOSTS.iter()
+                .map(|(page_type, boost)| (page_type.to_string(), *boost))
+                .collect(),
+            tokenizer: None,
+            analyzers: HashMap::new(),
+            config,
+            base: None,
+            removed: BTreeSet::new(),
+            format: SerializationFormat::default(),
         }
     }
 
+    /// 加载已有的搜索索引进行增量更新，只需处理新增、修改和删除的文章
+    ///
+    /// 构建器使用索引中保存的配置和页面类型分数系数。正文拆分到内容文件的索引
+    /// 会从同一目录的内容文件中恢复正文。构建时已删除文章的位置被压缩，
+    /// 其他文章按原顺序重新编号。
+    pub fn load_existing(path: &str) -> Result<Self, String> {
+        let data = std::fs::read(path)
+            .map_err(|e| format!("无法读取搜索索引文件 {}: {}", path, e))?;
+        let mut index = crate::engine::decode_index(&data)?;
+        
+        if !index.content_ranges.is_empty() {
+            let content_path = std::path::Path::new(path).with_file_name(content::CONTENT_FILE_NAME);
+            let store = std::fs::read(&content_path)
+                .map_err(|e| format!("无法读取内容文件 {}: {}", content_path.display(), e))?;
+            content::restore_content(&mut index, &store)?;
+        }
+        
+        let mut builder = Self::with_config(index.config.clone());
+        builder.page_type_boosts = index.page_type_boosts.clone();
+        builder.format = utils_common::compression::read_format(&data, crate::engine::MAX_INDEX_VERSION)
+            .map_err(|e| format!("读取索引格式失败: {}", e))?;
+        builder.base = Some(index);
+        Ok(builder)
+    }
+
+    /// 是否在已有索引上增量更新
+    pub fn is_incremental(&self) -> bool {
+        self.base.is_some()
+    }
+
+    /// 已有索引使用的分词器标识，不是增量更新时为None
+    pub fn existing_tokenizer(&self) -> Option<&str> {
+        self.base.as_ref().map(|index| index.tokenizer.as_str())
+    }
+
+    /// 分词器标识，写入索引
+    pub fn tokenizer_identity(&self) -> &str {
+        self.tokenizer.as_ref().map_or(BUILTIN_TOKENIZER, |(identity, _)| identity.as_str())
+    }
+
+    /// 构建配置
+    pub fn config(&self) -> &SearchBuilderConfig {
+        &self.config
+    }
+
+    /// 使用外部分词器替代内置的关键词提取
+    ///
+    /// `identity` 写入索引，搜索时可据此检查查询与索引的分词方式是否一致。
+    /// 使用外部分词器的索引无法在运行时增删文章。
+    pub fn set_tokenizer(&mut self, identity: &str, tokenize: impl Fn(&str) -> Result<Vec<String>, String> + 'static) {
+        self.tokenizer = Some((identity.to_string(), Box::new(tokenize)));
+    }
+
+    /// 注册自定义分析器，配置中的字段分析器标识与之相同时使用
+    ///
+    /// 使用自定义分析器的索引无法在运行时增删文章，搜索时查询只转为小写。
+    pub fn register_analyzer(&mut self, analyzer: Box<dyn Analyzer>) {
+        self.analyzers.insert(analyzer.id().to_string(), analyzer);
+    }
+
+    /// 设置保存索引文件使用的序列化格式，默认为bincode；增量更新时默认沿用已有索引的格式
+    pub fn set_format(&mut self, format: SerializationFormat) {
+        self.format = format;
+    }
+
+    /// 设置页面类型的分数系数，写入索引作为搜索时的默认值
+    pub fn set_page_type_boost(&mut self, page_type: &str, boost: f64) {
+        self.page_type_boosts.insert(page_type.to_string(), boost);
+    }
+
     /// 获取索引构建器中的文章数量
     pub fn get_article_count(&self) -> usize {
         self.articles.len()
     }
 
     /// 添加文章到索引构建器
-    pub fn add_article(&mut self, article: ArticleMetadata) {
+    pub fn add_article(&mut self, mut article: ArticleMetadata) {
         // 只添加非目录页面到索引
-        if article.page_type != "directory" {
+        if self.is_indexed(&article) {
+            fill_language(&mut article);
             self.articles.push(article);
         }
     }
 
-    /// 清理文本，移除不必要的字符和符号
-    fn clean_text(&self, text: &str) -> String {
-        text.trim().to_lowercase()
+    /// 文章是否写入索引 - 目录页面不索引，普通页面按配置决定
+    fn is_indexed(&self, article: &ArticleMetadata) -> bool {
+        match article.page_type.as_str() {
+            "directory" => false,
+            "page" => self.config.index_pages,
+            _ => true,
+        }
     }
 
-    /// 提取关键词
-    fn extract_keywords(&self, text: &str) -> Vec<String> {
-        let clean_text = self.clean_text(text);
-        
-        let mut keywords = HashSet::new();
-        let mut current_word = String::new();
-        let mut chinese_chars = Vec::new();
-        
-        // 遍历文本字符
-        for c in clean_text.chars() {
-            if c.is_alphanumeric() || c == '_' || c == '-' {
-                // 如果之前有收集的中文字符，先处理
-                if !chinese_chars.is_empty() {
-                    // 处理中文词组 (2-3个字符组合)
-                    for i in 1..=chinese_chars.len().min(3) {
-                        for start in 0..=chinese_chars.len() - i {
-                            let term: String = chinese_chars[start..start+i].iter().collect();
-                            if term.len() >= 2 { // 只添加长度>=2的中文词
-                                keywords.insert(term);
-                            }
-                        }
-                    }
-                    chinese_chars.clear();
-                }
-                
-                current_word.push(c);
-            } else if c.is_whitespace() || c.is_ascii_punctuation() {
-                // 处理当前单词
-                if !current_word.is_empty() && current_word.len() >= 2 {
-                    keywords.insert(current_word.clone());
-                    current_word.clear();
-                }
-                
-                // 处理中文字符
-                if !chinese_chars.is_empty() {
-                    for i in 1..=chinese_chars.len().min(3) {
-                        for start in 0..=chinese_chars.len() - i {
-                            let term: String = chinese_chars[start..start+i].iter().collect();
-                            if term.len() >= 2 {
-                                keywords.insert(term);
-                            }
-                        }
-                    }
-                    chinese_chars.clear();
-                }
-            } else {
-                // 处理当前英文词
-                if !current_word.is_empty() && current_word.len() >= 2 {
-                    keywords.insert(current_word.clone());
-                    current_word.clear();
-                }
-                
-                // 收集中文字符
-                chinese_chars.push(c);
-            }
-        }
-        
-        // 处理最后一个单词
-        if !current_word.is_empty() && current_word.len() >= 2 {
-            keywords.insert(current_word);
+    /// 添加或替换文章（按ID匹配），返回文章是否有变化
+    ///
+    /// 与已有文章完全相同时不做处理，增量更新时只重新索引有变化的文章。
+    pub fn update_article(&mut self, mut article: ArticleMetadata) -> Result<bool, String> {
+        if !self.is_indexed(&article) {
+            return self.remove_article(&article.id);
         }
+        fill_language(&mut article);
         
-        // 处理最后的中文字符
-        if !chinese_chars.is_empty() {
-            for i in 1..=chinese_chars.len().min(3) {
-                for start in 0..=chinese_chars.len() - i {
-                    let term: String = chinese_chars[start..start+i].iter().collect();
-                    if term.len() >= 2 {
-                        keywords.insert(term);
-                    }
-                }
-            }
+        // 构建器中的文章比已有索引中的新，待移除的文章不算已有
+        let existing = self.articles.iter().find(|existing| existing.id == article.id).or_else(|| {
+            let index = self.base.as_ref().filter(|_| !self.removed.contains(&article.id))?;
+            index.ordinal(&article.id).map(|article_id| &index.articles[article_id])
+        });
+        if existing == Some(&article) {
+            return Ok(false);
         }
         
-        // 过滤纯数字的关键词
-        keywords.into_iter()
-                .filter(|keyword| !keyword.chars().all(|c| c.is_ascii_digit()))
-                .collect()
+        self.remove_article(&article.id)?;
+        self.add_article(article);
+        Ok(true)
     }
 
-    /// 提取文章中的标题和层级结构
-    fn extract_headings(&self, article: &ArticleMetadata, article_id: usize) -> HashMap<String, HeadingIndexEntry> {
-        let headings = HashMap::new();
-        
-        // 如果内容为空，返回空结果
-        if article.content.is_empty() {
-            return headings;
-        }
-        
-        // 首先尝试使用文章中已解析的标题（如果有）
-        if !article.headings.is_empty() {
-            return self.build_heading_structure_from_extracted(&article.headings, article);
-        }
-        
-        // 使用正则表达式匹配所有h1-h6标签
-        let heading_regex = Regex::new(r"<h([1-6])(?:\s+[^>]*)?>([\s\S]*?)</h\d>").unwrap();
-        
-        // 提取所有标题及其位置和级别
-        let mut extracted_headings = Vec::new();
+    /// 移除文章，返回是否找到该文章
+    ///
+    /// 增量更新时只记录待移除的文章，构建索引时与新增和修改的文章一起处理。
+    pub fn remove_article(&mut self, id: &str) -> Result<bool, String> {
+        let count = self.articles.len();
+        self.articles.retain(|article| article.id != id);
+        let mut removed = self.articles.len() != count;
         
-        for cap in heading_regex.captures_iter(&article.content) {
-            // 获取标题级别
-            let level = cap.get(1)
-                .and_then(|m| m.as_str().parse::<usize>().ok())
-                .unwrap_or(1);
-            
-            // 获取标题文本并清理HTML标签
-            let text_with_tags = cap.get(2).map_or("", |m| m.as_str());
-            let text = remove_html_tags(text_with_tags).trim().to_string();
-            
-            // 跳过空标题
-            if text.is_empty() {
-                continue;
+        if let Some(index) = &self.base {
+            self.check_tokenizer(index)?;
+            if index.ordinal(id).is_some() {
+                removed |= self.removed.insert(id.to_string());
             }
-            
-            // 记录标题在文档中的位置
-            let position = cap.get(0).map_or(0, |m| m.start());
-            
-            // 添加到提取的标题列表
-            extracted_headings.push((level, text, position));
         }
-        
-        // 如果没有找到标题，尝试使用更宽松的正则表达式
-        if extracted_headings.is_empty() {
-            // 使用最宽松的正则表达式再次尝试匹配
-            let fallback_regex = Regex::new(r"<h\d[^>]*>(.*?)</h\d>").unwrap();
-            
-            for cap in fallback_regex.captures_iter(&article.content) {
-                let text_with_tags = cap.get(1).map_or("", |m| m.as_str());
-                let text = remove_html_tags(text_with_tags).trim().to_string();
-                
-                if text.is_empty() {
-                    continue;
-                }
-                
-                let position = cap.get(0).map_or(0, |m| m.start());
-                // 默认级别为1
-                extracted_headings.push((1, text, position));
-            }
+        Ok(removed)
+    }
+
+    /// 构建器中所有文章的ID，增量更新时包括已有索引中未移除的文章
+    pub fn article_ids(&self) -> Vec<String> {
+        let existing = self.base.iter()
+            .flat_map(|index| index.ordinals.keys())
+            .filter(|id| !self.removed.contains(*id))
+            .cloned();
+        existing.chain(self.articles.iter().map(|article| article.id.clone())).collect()
+    }
+
+    /// 按标识查找字段分析器，自定义分析器优先
+    fn analyzer(&self, id: &str) -> Result<Box<dyn Analyzer + '_>, String> {
+        if let Some(analyzer) = self.analyzers.get(id) {
+            return Ok(Box::new(analyzer.as_ref()));
         }
+        analyzer::builtin(id, &self.config).ok_or_else(|| format!("未知的分析器: {}，自定义分析器需要先注册", id))
+    }
+
+    /// 切分字段文本 - 优先使用外部分词器，其词条统一转为小写、去重后按字段分析器筛选
+    fn tokenize(&self, analyzer: &dyn Analyzer, text: &str) -> Result<Vec<String>, String> {
+        let Some((identity, tokenize)) = &self.tokenizer else {
+            return Ok(analyzer.analyze(text));
+        };
         
-        // 如果仍然没有找到标题，返回空结果
-        if extracted_headings.is_empty() {
-            return headings;
+        let tokens = tokenize(text).map_err(|e| format!("外部分词器 {} 分词失败: {}", identity, e))?;
+        let mut seen = HashSet::new();
+        Ok(tokens.into_iter()
+            .map(|token| token.trim().to_lowercase())
+            .filter(|token| !token.is_empty() && analyzer.accepts(token) && seen.insert(token.clone()))
+            .collect())
+    }
+
+    /// 提取文章中的标题和层级结构
+    fn extract_headings(&self, article: &ArticleMetadata) -> HashMap<String, HeadingIndexEntry> {
+        // 标题只来自索引器解析的标题结构，正文为纯文本，没有可供匹配的标签
+        if article.content.is_empty() || article.headings.is_empty() {
+            return HashMap::new();
         }
         
-        // 按位置排序提取的标题
-        extracted_headings.sort_by_key(|h| h.2);
-        
-        // 构建标题层级结构
-        self.build_heading_hierarchy(extracted_headings, article)
+        let reconciled = reconcile_heading_positions(&article.headings, &article.content);
+        self.build_heading_hierarchy(reconciled, article)
     }
     
     /// 从提取的标题数组构建标题层级结构
@@ -245,7 +729,7 @@ impl SearchBuilder {
             // 如果有父标题，添加到父标题的子标题列表
             if let Some(ref pid) = parent_id {
                 children_map.entry(pid.clone())
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(heading_id.clone());
             }
             
@@ -291,124 +775,433 @@ impl SearchBuilder {
         result
     }
     
-    /// 从预解析的标题列表构建层级结构
-    fn build_heading_structure_from_extracted(
-        &self, 
-        headings: &[utils_common::models::Heading], 
-        article: &ArticleMetadata
-    ) -> HashMap<String, HeadingIndexEntry> {
-        // 将预解析的标题转换为(级别, 文本, 位置)的格式
-        let mut extracted: Vec<(usize, String, usize)> = headings.iter()
-            .map(|h| (h.level, h.text.clone(), h.position))
-            .collect();
-        
-        // 按位置排序
-        extracted.sort_by_key(|h| h.2);
-        
-        // 构建层级结构
-        self.build_heading_hierarchy(extracted, article)
-    }
-
     /// 构建搜索索引
     pub fn build_search_index(&self) -> Result<ArticleSearchIndex, String> {
+        // 增量更新 - 词典和位置索引只解码、写入一次，所有删除和修改都在倒排列表上进行
+        if let Some(base) = &self.base {
+            let mut index = base.clone();
+            index.page_type_boosts = self.page_type_boosts.clone();
+            let mut postings = IndexPostings::from_index(&index);
+            for id in &self.removed {
+                self.remove_terms(&mut index, &mut postings, id)?;
+            }
+            for article in &self.articles {
+                self.upsert_terms(&mut index, &mut postings, article.clone())?;
+            }
+            compact_tombstones(&mut index, &mut postings)?;
+            postings.write_to(&mut index);
+            index.update_field_averages();
+            index.rebuild_taxonomy();
+            index.related = related::compute_related(&index, self.config.related_count, self.config.common_term_max_df);
+            print_stats(&index);
+            return Ok(index);
+        }
+        
         if self.articles.is_empty() {
             return Err("无法构建索引: 没有文章数据".to_string());
         }
 
-        // 构建标题关键词到文章的索引
-        let title_term_index = self.build_title_term_index();
+        // 文章按ID排序后再分配位置，输入顺序不影响索引内容
+        let mut articles = self.articles.clone();
+        articles.sort_by(|a, b| a.id.cmp(&b.id));
+        
+        let mut index = ArticleSearchIndex {
+            title_term_index: TermDictionary::default(),
+            articles,
+            ordinals: BTreeMap::new(),
+            heading_index: HashMap::new(),
+            heading_term_index: HashMap::new(),
+            common_terms: HashMap::new(),
+            content_term_index: TermDictionary::default(),
+            media_term_index: TermDictionary::default(),
+            title_position_index: PositionIndex::default(),
+            content_position_index: PositionIndex::default(),
+            media_position_index: PositionIndex::default(),
+            page_type_boosts: self.page_type_boosts.clone(),
+            tombstones: HashSet::new(),
+            tokenizer: self.tokenizer_identity().to_string(),
+            content_ranges: Vec::new(),
+            config: self.config.clone(),
+            related: Vec::new(),
+            field_lengths: Vec::new(),
+            average_field_lengths: FieldAverages::default(),
+            tag_index: BTreeMap::new(),
+            category_index: BTreeMap::new(),
+            max_visibility: Visibility::default(),
+        };
+        index.rebuild_ordinals()?;
         
-        // 提取所有文章的标题结构
-        let mut all_headings = HashMap::new();
+        // 统计词频
+        let mut term_frequency: HashMap<String, TermContribution> = HashMap::new();
+        let mut postings = IndexPostings::default();
         
-        for (article_id, article) in self.articles.iter().enumerate() {
-            // 提取标题结构
-            let article_headings = self.extract_headings(article, article_id);
+        // 提取所有文章的关键词和标题结构，再依次写入倒排列表
+        for (article_id, terms) in self.collect_article_terms(&index.articles)?.into_iter().enumerate() {
+            terms.insert_into(&mut index, &mut postings, article_id);
+            index.field_lengths.push(terms.lengths);
             
-            // 合并到全局索引
-            all_headings.extend(article_headings);
+            for (term, contribution) in terms.frequency {
+                match term_frequency.entry(term) {
+                    Entry::Occupied(mut entry) => entry.get_mut().absorb(contribution),
+                    Entry::Vacant(entry) => {
+                        entry.insert(contribution);
+                    }
+                }
+            }
         }
         
-        // 构建标题关键词索引
-        let heading_term_index = self.build_heading_term_index(&all_headings);
+        // 按文档频率降权，几乎每篇文章都有的词汇不作为常用词汇
+        let article_count = self.articles.len();
+        let mut terms: Vec<(String, CommonTerm)> = term_frequency.into_iter()
+            .map(|(term, contribution)| {
+                let documents = postings.title.get(&term).into_iter()
+                    .chain(postings.content.get(&term))
+                    .flatten()
+                    .collect::<BTreeSet<_>>()
+                    .len();
+                let weight = document_frequency_weight(documents, article_count, self.config.common_term_max_df);
+                let frequency = (contribution.frequency as f64 * weight).round() as usize;
+                let common_term = contribution.into_common_term(&term, frequency);
+                (term, common_term)
+            })
+            .filter(|(_, common_term)| common_term.frequency > 0)
+            .collect();
+        postings.write_to(&mut index);
+        index.update_field_averages();
+        index.rebuild_taxonomy();
         
-        // 定义停用词表
-        let stop_words: HashSet<&str> = [
-            "的", "是", "在", "了", "和", "与", "或", "而", "但", "如果", "因为",
-            "所以", "这", "那", "这个", "那个", "这些", "那些", "并", "可以", "把",
-            "被", "将", "已", "就", "也", "很", "到", "上", "下", "中", "为"
-        ].iter().cloned().collect();
+        // 按降权后的频率降序排序，频率相同时按词汇排序
+        terms.sort_by(|a, b| b.1.frequency.cmp(&a.1.frequency).then_with(|| a.0.cmp(&b.0)));
         
-        // 统计词频
-        let mut term_frequency: HashMap<String, usize> = HashMap::new();
-        
-        // 构建内容关键词索引
-        let mut content_term_index: HashMap<String, HashSet<usize>> = HashMap::new();
-        
-        // 遍历所有文章，提取关键词和构建内容索引
-        for (article_id, article) in self.articles.iter().enumerate() {
-            // 标题关键词
-            let title_keywords = self.extract_keywords(&article.title);
-            for keyword in &title_keywords {
-                if !stop_words.contains(keyword.as_str()) && keyword.len() >= 2 {
-                    *term_frequency.entry(keyword.clone()).or_insert(0) += 3; // 标题权重高
+        // 添加常用词
+        index.common_terms.extend(terms.into_iter().take(self.config.max_common_terms));
+        
+        index.related = related::compute_related(&index, self.config.related_count, self.config.common_term_max_df);
+
+        print_stats(&index);
+        Ok(index)
+    }
+
+    /// 在已构建的索引中添加或替换一篇文章，返回文章在索引中的位置
+    ///
+    /// 按索引中保存的配置使用内置分词器提取词条，供运行时修改已加载的索引。
+    /// 同ID的旧文章会先被移除。常用词汇只做近似更新：已有词汇累加频率、
+    /// 保留原有的展示形式，常用词汇未满时才加入新词。所有文章的相关文章随之重新计算。
+    /// 目录页面不会被索引，返回None。
+    pub fn upsert_into_index(index: &mut ArticleSearchIndex, article: ArticleMetadata) -> Result<Option<usize>, String> {
+        Self::with_config(index.config.clone()).upsert_with(index, article)
+    }
+
+    /// 从已构建的索引中移除文章，返回是否找到该文章
+    ///
+    /// 文章的位置被标记为已移除，不会复用，其他文章的位置保持不变；
+    /// 相关文章随之重新计算。
+    pub fn remove_from_index(index: &mut ArticleSearchIndex, id: &str) -> Result<bool, String> {
+        // 运行时只能使用内置分词器重新提取词条
+        if index.tokenizer != BUILTIN_TOKENIZER {
+            return Err(format!("索引使用外部分词器 {}，无法在运行时修改", index.tokenizer));
+        }
+        let builder = Self::with_config(index.config.clone());
+        let mut postings = IndexPostings::from_index(index);
+        let result = builder.remove_terms(index, &mut postings, id);
+        postings.write_to(index);
+        index.update_field_averages();
+        index.rebuild_taxonomy();
+        index.related = related::compute_related(index, builder.config.related_count, builder.config.common_term_max_df);
+        result
+    }
+
+    /// 合并多个分片索引（如按目录并行构建的索引）为一个索引
+    ///
+    /// 后面分片中的文章位置依次顺延，词典、位置索引和已移除文章随之改写；标题ID
+    /// 以文章ID开头，直接合并。常用词汇累加各分片的频率后重新截取。各分片必须使用
+    /// 相同的配置和分词器构建，且正文未拆分到内容文件；文章ID在各分片间重复时返回错误。
+    pub fn merge(indexes: Vec<ArticleSearchIndex>) -> Result<ArticleSearchIndex, String> {
+        let mut indexes = indexes.into_iter();
+        let Some(first) = indexes.next() else {
+            return Err("无法合并索引: 没有分片".to_string());
+        };
+
+        let mut merged = first;
+        if !merged.content_ranges.is_empty() {
+            return Err("无法合并正文已拆分的索引".to_string());
+        }
+        let mut postings = IndexPostings::from_index(&merged);
+
+        for index in indexes {
+            if index.tokenizer != merged.tokenizer {
+                return Err(format!("分片使用分词器 {}，与 {} 不一致", index.tokenizer, merged.tokenizer));
+            }
+            if index.config != merged.config {
+                return Err("分片的构建配置不一致".to_string());
+            }
+            if !index.content_ranges.is_empty() {
+                return Err("无法合并正文已拆分的索引".to_string());
+            }
+
+            let offset = merged.articles.len();
+            let shard = IndexPostings::from_index(&index);
+            for (merged_map, shard_map) in [
+                (&mut postings.title, shard.title),
+                (&mut postings.content, shard.content),
+                (&mut postings.media, shard.media),
+            ] {
+                for (term, article_ids) in shard_map {
+                    merged_map.entry(term).or_default().extend(article_ids.into_iter().map(|id| id + offset));
                 }
             }
-            
-            // 内容关键词
-            let content_keywords = self.extract_keywords(&article.content);
-            let mut content_term_freq: HashMap<String, usize> = HashMap::new();
-            
-            // 先统计文章内的词频
-            for keyword in content_keywords {
-                if !stop_words.contains(keyword.as_str()) && keyword.len() >= 2 {
-                    *content_term_freq.entry(keyword.clone()).or_insert(0) += 1;
-                    
-                    // 同时添加到内容关键词索引
-                    content_term_index.entry(keyword)
-                                     .or_insert_with(HashSet::new)
-                                     .insert(article_id);
+            for (merged_positions, shard_positions) in [
+                (&mut postings.title_positions, shard.title_positions),
+                (&mut postings.content_positions, shard.content_positions),
+                (&mut postings.media_positions, shard.media_positions),
+            ] {
+                for (term, articles) in shard_positions {
+                    merged_positions.entry(term).or_default()
+                        .extend(articles.into_iter().map(|(id, positions)| (id + offset, positions)));
                 }
             }
-            
-            // 只保留高频词（出现至少2次）添加到全局词频统计
-            for (keyword, freq) in content_term_freq.iter() {
-                if *freq >= 2 {
-                    *term_frequency.entry(keyword.clone()).or_insert(0) += 1;
+
+            merged.heading_index.extend(index.heading_index);
+            for (term, heading_ids) in index.heading_term_index {
+                merged.heading_term_index.entry(term).or_default().extend(heading_ids);
+            }
+            for (term, common_term) in index.common_terms {
+                match merged.common_terms.entry(term) {
+                    Entry::Occupied(mut entry) => {
+                        // 展示形式取频率较高的分片中的形式
+                        let existing = entry.get_mut();
+                        if common_term.frequency > existing.frequency {
+                            existing.display = common_term.display;
+                        }
+                        existing.frequency += common_term.frequency;
+                        existing.field = existing.field.min(common_term.field);
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(common_term);
+                    }
                 }
             }
+            for (page_type, boost) in index.page_type_boosts {
+                merged.page_type_boosts.entry(page_type).or_insert(boost);
+            }
+            merged.tombstones.extend(index.tombstones.into_iter().map(|id| id + offset));
+            merged.field_lengths.extend(index.field_lengths);
+            merged.articles.extend(index.articles);
         }
+        merged.rebuild_ordinals()?;
+        merged.update_field_averages();
+        merged.rebuild_taxonomy();
+        postings.write_to(&mut merged);
+
+        // 与构建索引时相同，按频率降序、同频率按词汇排序后截取
+        let mut terms: Vec<(String, CommonTerm)> = merged.common_terms.drain().collect();
+        terms.sort_by(|a, b| b.1.frequency.cmp(&a.1.frequency).then_with(|| a.0.cmp(&b.0)));
+        merged.common_terms = terms.into_iter().take(merged.config.max_common_terms).collect();
+        merged.related = related::compute_related(&merged, merged.config.related_count, merged.config.common_term_max_df);
+
+        print_stats(&merged);
+        Ok(merged)
+    }
+
+    // 使用本构建器的分词器在索引中添加或替换文章
+    fn upsert_with(&self, index: &mut ArticleSearchIndex, article: ArticleMetadata) -> Result<Option<usize>, String> {
+        let mut postings = IndexPostings::from_index(index);
+        let result = self.upsert_terms(index, &mut postings, article);
+        postings.write_to(index);
+        index.update_field_averages();
+        index.rebuild_taxonomy();
+        index.related = related::compute_related(index, self.config.related_count, self.config.common_term_max_df);
+        result
+    }
+
+    // 在倒排列表中添加或替换文章，词典、平均字段长度和标签分类索引由调用方统一更新
+    fn upsert_terms(&self, index: &mut ArticleSearchIndex, postings: &mut IndexPostings, mut article: ArticleMetadata) -> Result<Option<usize>, String> {
+        self.remove_terms(index, postings, &article.id)?;
         
-        // 选择最常用的词作为常用词汇
-        let mut terms: Vec<(String, usize)> = term_frequency.into_iter().collect();
-        terms.sort_by(|a, b| b.1.cmp(&a.1)); // 按频率降序排序
+        if !self.is_indexed(&article) {
+            return Ok(None);
+        }
+        fill_language(&mut article);
         
-        let mut common_terms = HashMap::new();
+        let article_id = index.articles.len();
+        let terms = self.article_terms(&article)?;
+        index.ordinals.insert(article.id.clone(), article_id);
+        index.articles.push(article);
+        // 拆分正文的索引中，新文章的正文内嵌在索引里
+        if !index.content_ranges.is_empty() {
+            index.content_ranges.push(ContentRange::default());
+        }
+        terms.insert_into(index, postings, article_id);
+        index.field_lengths.push(terms.lengths);
         
-        // 添加常用词
-        for (term, freq) in terms.into_iter().take(500) {
-            common_terms.insert(term, freq);
-        }
-
-        // 输出构建统计
-        println!("索引构建统计:");
-        println!("- 文章数量: {}", self.articles.len());
-        println!("- 标题词汇: {}", title_term_index.len());
-        println!("- 标题结构: {}", all_headings.len());
-        println!("- 内容词汇: {}", content_term_index.len());
-        println!("- 常用词汇: {}", common_terms.len());
-
-        Ok(ArticleSearchIndex {
-            title_term_index,
-            articles: self.articles.clone(),
-            heading_index: all_headings,
-            heading_term_index,
-            common_terms,
-            content_term_index,
+        for (term, contribution) in terms.frequency {
+            if let Some(common_term) = index.common_terms.get_mut(&term) {
+                common_term.frequency += contribution.frequency;
+                common_term.field = common_term.field.min(contribution.field);
+            } else if index.common_terms.len() < index.config.max_common_terms {
+                let frequency = contribution.frequency;
+                let common_term = contribution.into_common_term(&term, frequency);
+                index.common_terms.insert(term, common_term);
+            }
+        }
+        
+        Ok(Some(article_id))
+    }
+
+    // 词条必须用构建索引时的分词器重新提取，才能准确移除
+    fn check_tokenizer(&self, index: &ArticleSearchIndex) -> Result<(), String> {
+        if index.tokenizer != self.tokenizer_identity() {
+            return Err(format!("索引使用分词器 {}，与当前分词器 {} 不一致", index.tokenizer, self.tokenizer_identity()));
+        }
+        Ok(())
+    }
+
+    // 使用本构建器的分词器从倒排列表和索引中移除文章，词典、平均字段长度和标签分类索引由调用方统一更新
+    fn remove_terms(&self, index: &mut ArticleSearchIndex, postings: &mut IndexPostings, id: &str) -> Result<bool, String> {
+        self.check_tokenizer(index)?;
+        
+        // 不可见的文章同样可以移除
+        let Some(article_id) = index.ordinal(id) else {
+            return Ok(false);
+        };
+        
+        // 正文拆分到内容文件时需要先加载正文才能重新提取词条
+        let article = content::with_content(index, article_id)
+            .ok_or_else(|| format!("文章正文尚未加载，无法移除: {}", id))?;
+        let terms = self.article_terms(&article)?;
+        terms.remove_from(index, postings, article_id);
+        
+        for (term, contribution) in &terms.frequency {
+            if let Some(common_term) = index.common_terms.get_mut(term) {
+                common_term.frequency = common_term.frequency.saturating_sub(contribution.frequency);
+                if common_term.frequency == 0 {
+                    index.common_terms.remove(term);
+                }
+            }
+        }
+        
+        // 已移除文章只保留位置，不再保存正文
+        let article = &mut index.articles[article_id];
+        article.content.clear();
+        article.media_text.clear();
+        index.tombstones.insert(article_id);
+        index.ordinals.remove(id);
+        if let Some(lengths) = index.field_lengths.get_mut(article_id) {
+            *lengths = FieldLengths::default();
+        }
+        Ok(true)
+    }
+
+    /// 提取所有文章的索引词条，顺序与文章相同
+    #[cfg(not(feature = "parallel"))]
+    fn collect_article_terms(&self, articles: &[ArticleMetadata]) -> Result<Vec<ArticleTerms>, String> {
+        articles.iter().map(|article| self.article_terms(article)).collect()
+    }
+
+    /// 提取所有文章的索引词条，顺序与文章相同
+    ///
+    /// 内置分析器按文章并行提取，每个线程使用只带配置的构建器；外部分词器
+    /// 是单个子进程、自定义分析器无法在线程间共享，只能依次调用。
+    #[cfg(feature = "parallel")]
+    fn collect_article_terms(&self, articles: &[ArticleMetadata]) -> Result<Vec<ArticleTerms>, String> {
+        use rayon::prelude::*;
+        
+        if self.tokenizer.is_some() || !self.analyzers.is_empty() {
+            return articles.iter().map(|article| self.article_terms(article)).collect();
+        }
+        
+        let config = &self.config;
+        articles.par_iter()
+            .map_init(|| Self::with_config(config.clone()), |builder, article| builder.article_terms(article))
+            .collect()
+    }
+
+    /// 提取单篇文章的索引词条
+    fn article_terms(&self, article: &ArticleMetadata) -> Result<ArticleTerms, String> {
+        let analyzers = &self.config.analyzers;
+        // 常用词汇统计不受字段分析器影响，始终排除停用词和过短的词
+        let min_length = self.config.min_term_length;
+        let is_indexable = |keyword: &str| keyword.len() >= min_length && !self.config.stop_words.iter().any(|word| word == keyword);
+        let mut frequency: HashMap<String, TermContribution> = HashMap::new();
+        
+        // 标题关键词
+        let title_terms: HashSet<String> = self.tokenize(self.analyzer(&analyzers.title)?.as_ref(), &article.title)?
+            .into_iter()
+            .collect();
+        let title_positions = term_positions(&article.title, &title_terms);
+        for keyword in title_terms.iter().filter(|keyword| is_indexable(keyword)) {
+            const TITLE_WEIGHT: usize = 3; // 标题权重高
+            let contribution = frequency.entry(keyword.clone()).or_insert_with(|| TermContribution::new(TermField::Title));
+            contribution.add(TITLE_WEIGHT, TermField::Title);
+            let forms = title_positions.get(keyword).into_iter().flatten()
+                .filter_map(|&position| original_form(&article.title, keyword, position));
+            contribution.add_forms(forms, TITLE_WEIGHT);
+        }
+        
+        // 标题结构及其关键词
+        let headings = self.extract_headings(article);
+        let heading_analyzer = self.analyzer(&analyzers.heading)?;
+        let heading_terms: Vec<(String, Vec<String>)> = headings.iter()
+            .map(|(heading_id, heading)| Ok((heading_id.clone(), self.tokenize(heading_analyzer.as_ref(), &heading.text)?)))
+            .collect::<Result<_, String>>()?;
+        
+        // 内容关键词，先统计文章内的词频
+        let mut content_terms = HashSet::new();
+        let mut content_term_freq: HashMap<String, usize> = HashMap::new();
+        for keyword in self.tokenize(self.analyzer(&analyzers.content)?.as_ref(), &article.content)? {
+            *content_term_freq.entry(keyword.clone()).or_insert(0) += 1;
+            content_terms.insert(keyword);
+        }
+        
+        // 只保留高频词（出现至少2次）添加到全局词频统计
+        let content_positions = term_positions(&article.content, &content_terms);
+        for (keyword, freq) in content_term_freq {
+            if freq >= 2 && is_indexable(&keyword) {
+                let forms = content_positions.get(&keyword).into_iter().flatten()
+                    .filter_map(|&position| original_form(&article.content, &keyword, position));
+                let contribution = frequency.entry(keyword.clone()).or_insert_with(|| TermContribution::new(TermField::Content));
+                contribution.add(1, TermField::Content);
+                contribution.add_forms(forms, 1);
+            }
+        }
+        
+        // 章节标题只记录来源字段，不计入频率
+        for keyword in heading_terms.iter().flat_map(|(_, keywords)| keywords) {
+            if let Some(contribution) = frequency.get_mut(keyword) {
+                contribution.add(0, TermField::Heading);
+            }
+        }
+        
+        // 媒体说明关键词 - 单独索引，不参与常用词统计
+        let media_terms = self.tokenize(self.analyzer(&analyzers.media)?.as_ref(), &article.media_text)?
+            .into_iter()
+            .collect();
+        let media_positions = term_positions(&article.media_text, &media_terms);
+        
+        // 字段长度 - 各关键词的出现次数之和，章节标题没有位置，按关键词数量计
+        let occurrences = |positions: &HashMap<String, Vec<u32>>| positions.values().map(|p| p.len() as u32).sum();
+        let lengths = FieldLengths {
+            title: occurrences(&title_positions),
+            heading: heading_terms.iter().map(|(_, terms)| terms.len() as u32).sum(),
+            content: occurrences(&content_positions),
+            media: occurrences(&media_positions),
+        };
+        
+        Ok(ArticleTerms {
+            title_terms,
+            headings,
+            heading_terms,
+            content_terms,
+            media_terms,
+            title_positions,
+            content_positions,
+            media_positions,
+            frequency,
+            lengths,
         })
     }
 
-    /// 保存搜索索引到文件
+    /// 保存搜索索引到文件，增量更新时已移除文章的位置在构建时压缩
     pub fn save_search_index(&self, path: &str) -> Result<(), String> {
         // 构建搜索索引
         let search_index = self.build_search_index()?;
@@ -417,9 +1210,8 @@ impl SearchBuilder {
         let mut search_file = File::create(path)
             .map_err(|e| format!("无法创建搜索索引文件: {}", e))?;
         
-        // 使用版本号7.0，表示优化版本索引
-        let version = [7, 0];
-        let compressed_data = to_compressed(&search_index, version)
+        // 头部写入索引元数据
+        let compressed_data = to_compressed_with_format(&search_index, INDEX_VERSION, Some(&index_metadata(&search_index, INDEX_VERSION)), self.format)
             .map_err(|e| format!("压缩搜索索引失败: {}", e))?;
         
         search_file.write_all(&compressed_data)
@@ -428,56 +1220,23 @@ impl SearchBuilder {
         Ok(())
     }
 
-    /// 构建标题关键词索引
-    fn build_heading_term_index(&self, headings: &HashMap<String, HeadingIndexEntry>) -> HashMap<String, HashSet<String>> {
-        let mut heading_term_index = HashMap::new();
-        
-        for (heading_id, heading) in headings {
-            // 提取标题文本的关键词
-            let heading_keywords = self.extract_keywords(&heading.text);
-            
-            // 添加到索引
-            for keyword in heading_keywords {
-                heading_term_index.entry(keyword)
-                                 .or_insert_with(HashSet::new)
-                                 .insert(heading_id.clone());
-            }
-        }
+    /// 构建搜索索引并将正文拆分到单独的内容文件中
+    ///
+    /// 内容文件为各篇文章正文直接拼接的UTF-8文本，不压缩，便于按字节区间读取；
+    /// 索引中只记录每篇文章的正文区间，体积远小于内嵌正文的索引。
+    pub fn save_split_search_index(&self, index_path: &str, content_path: &str) -> Result<(), String> {
+        let mut search_index = self.build_search_index()?;
+        let store = content::split_content(&mut search_index)?;
         
-        heading_term_index
-    }
-
-    /// 构建标题关键词到文章的索引
-    fn build_title_term_index(&self) -> HashMap<String, HashSet<usize>> {
-        let mut title_term_index = HashMap::new();
+        std::fs::write(content_path, &store)
+            .map_err(|e| format!("无法写入内容文件: {}", e))?;
         
-        for (article_id, article) in self.articles.iter().enumerate() {
-            // 从标题中提取关键词
-            let title_keywords = self.extract_keywords(&article.title);
-            
-            // 添加到索引
-            for keyword in title_keywords {
-                title_term_index.entry(keyword)
-                              .or_insert_with(HashSet::new)
-                              .insert(article_id);
-            }
-            
-            // 额外处理：标题中的各个单词
-            let title_words: Vec<String> = article.title
-                .to_lowercase()
-                .split_whitespace()
-                .map(|s| s.trim_matches(|c: char| !c.is_alphanumeric() && c != '_' && c != '-'))
-                .filter(|s| s.len() >= 2)
-                .map(|s| s.to_string())
-                .collect();
-                
-            for word in title_words {
-                title_term_index.entry(word)
-                          .or_insert_with(HashSet::new)
-                          .insert(article_id);
-            }
-        }
+        let compressed_data = to_compressed_with_format(&search_index, INDEX_VERSION, Some(&index_metadata(&search_index, INDEX_VERSION)), self.format)
+            .map_err(|e| format!("压缩搜索索引失败: {}", e))?;
+        std::fs::write(index_path, &compressed_data)
+            .map_err(|e| format!("无法写入搜索索引文件: {}", e))?;
         
-        title_term_index
+        println!("正文已拆分: 内容文件 {} 字节，索引 {} 字节", store.len(), compressed_data.len());
+        Ok(())
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,44 @@
+// 搜索取消 - 基于全局代数（epoch）计数器
+// 宿主调用 `cancel_pending()` 推进代数后，之前开始的搜索在长循环中检查到代数变化即提前结束，
+// 返回 `cancelled: true` 的结果。用户快速输入时，排队中的旧请求也可以通过请求中的
+// `epoch` 字段在开始前直接作废。
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use wasm_bindgen::prelude::*;
+
+// 全局搜索代数
+static EPOCH: AtomicU32 = AtomicU32::new(0);
+
+/// 取消所有进行中和排队中的搜索，返回新的代数
+///
+/// 宿主可以把返回值填入之后请求的 `epoch` 字段。
+#[wasm_bindgen]
+pub fn cancel_pending() -> u32 {
+    EPOCH.fetch_add(1, Ordering::SeqCst).wrapping_add(1)
+}
+
+/// 获取当前代数
+#[wasm_bindgen]
+pub fn current_epoch() -> u32 {
+    EPOCH.load(Ordering::SeqCst)
+}
+
+/// 取消令牌 - 记录搜索开始时的代数
+#[derive(Clone, Copy, Debug)]
+pub struct CancelToken {
+    epoch: u32,
+}
+
+impl CancelToken {
+    /// 为请求创建取消令牌，请求未指定代数时使用当前代数
+    pub fn for_request(epoch: Option<u32>) -> Self {
+        Self {
+            epoch: epoch.unwrap_or_else(current_epoch),
+        }
+    }
+
+    /// 代数已变化，搜索应当提前结束
+    pub fn is_cancelled(&self) -> bool {
+        self.epoch != current_epoch()
+    }
+}
@@ -0,0 +1,60 @@
+// 结果分组 - 按匹配所在的顶级章节（文章中没有上级的标题，通常为 h1/h2）对结果分组，
+// 文档类的长文章中，宽泛的查询（如“部署”）会匹配许多文章中的同名章节，
+// 界面可以按“安装”、“配置”、“部署”等分组展示，方便浏览。
+// 分组依据结果中的标题树，精简结果没有标题树，不产生分组。
+
+use std::collections::HashMap;
+use crate::models::{ClusterItem, HeadingNode, ResultCluster, SearchResultItem};
+
+/// 最多返回的分组数量
+const MAX_CLUSTERS: usize = 10;
+
+/// 分组最少包含的文章数量，只有一篇文章的章节不算作分组
+const MIN_CLUSTER_SIZE: usize = 2;
+
+/// 章节或其下级章节中是否有匹配
+fn has_matches(node: &HeadingNode) -> bool {
+    node.matched_terms.as_ref().is_some_and(|terms| !terms.is_empty())
+        || node.children.iter().any(has_matches)
+}
+
+/// 按顶级章节对结果分组，分组按文章数量降序，数量相同时按其中排名最高的文章排序
+pub fn cluster_results(items: &[SearchResultItem]) -> Vec<ResultCluster> {
+    // 分组键（小写的章节标题）-> (排名最高的文章的位置, 分组)
+    let mut clusters: HashMap<String, (usize, ResultCluster)> = HashMap::new();
+
+    for (rank, item) in items.iter().enumerate() {
+        let Some(tree) = &item.heading_tree else {
+            continue;
+        };
+        for section in tree.children.iter().filter(|section| has_matches(section)) {
+            let label = section.text.trim();
+            if label.is_empty() {
+                continue;
+            }
+            let (_, cluster) = clusters.entry(label.to_lowercase()).or_insert_with(|| {
+                (rank, ResultCluster { label: label.to_string(), items: Vec::new() })
+            });
+            // 同一篇文章中的同名章节只计一次
+            if cluster.items.last().is_some_and(|last| last.id == item.id && last.source == item.source) {
+                continue;
+            }
+            cluster.items.push(ClusterItem {
+                id: item.id.clone(),
+                title: item.title.clone(),
+                url: item.url.clone(),
+                source: item.source.clone(),
+                heading_id: section.id.clone(),
+            });
+        }
+    }
+
+    let mut clusters: Vec<(usize, ResultCluster)> = clusters.into_values()
+        .filter(|(_, cluster)| cluster.items.len() >= MIN_CLUSTER_SIZE)
+        .collect();
+    clusters.sort_by(|a, b| b.1.items.len().cmp(&a.1.items.len()).then_with(|| a.0.cmp(&b.0)));
+    clusters.into_iter()
+        .take(MAX_CLUSTERS)
+        .map(|(_, cluster)| cluster)
+        .collect()
+}
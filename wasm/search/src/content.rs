@@ -0,0 +1,128 @@
+// 正文存储 - 把文章正文从搜索索引中拆分出来，按需加载
+// 拆分后索引只保留词典、位置索引和元数据，正文依次拼接写入单独的内容文件，
+// 每篇文章记录其在内容文件中的字节区间。查询时只有需要正文的地方（结果摘要、
+// 标题树）才通过加载函数请求正文，JS端可以用HTTP Range请求只下载对应区间。
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use utils_common::models::ArticleMetadata;
+use crate::models::ArticleSearchIndex;
+
+/// 内容文件名，与搜索索引文件放在同一目录
+pub const CONTENT_FILE_NAME: &str = "search_content.bin";
+
+/// 正文区间 - 文章正文在内容文件中的字节区间
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ContentRange {
+    /// 起始字节
+    pub offset: u64,
+    /// 字节长度，为0表示正文内嵌在索引中（或正文为空）
+    pub len: u32,
+}
+
+/// 正文加载函数 - 参数为文章ID和正文区间，无法立即提供正文时返回None
+pub type ContentLoader = Box<dyn Fn(&str, ContentRange) -> Option<String>>;
+
+thread_local! {
+    // WASM是单线程的，加载函数可能持有JS对象，不能放入全局的Mutex
+    static LOADER: RefCell<Option<ContentLoader>> = const { RefCell::new(None) };
+    // 已加载的正文（文章ID -> 正文）
+    static CACHE: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// 设置正文加载函数，同时清空已加载的正文
+pub fn set_loader(loader: Option<ContentLoader>) {
+    LOADER.with(|cell| *cell.borrow_mut() = loader);
+    clear();
+}
+
+/// 直接提供文章正文，适合JS端异步下载正文后写入
+pub fn provide(article_id: &str, content: String) {
+    CACHE.with(|cache| cache.borrow_mut().insert(article_id.to_string(), content));
+}
+
+/// 清空已加载的正文
+pub fn clear() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// 将索引中的正文移出，依次拼接为内容文件，并记录每篇文章的正文区间
+pub fn split_content(index: &mut ArticleSearchIndex) -> Result<Vec<u8>, String> {
+    let mut store = Vec::new();
+    index.content_ranges = index.articles.iter_mut()
+        .map(|article| {
+            let content = std::mem::take(&mut article.content);
+            let len = u32::try_from(content.len())
+                .map_err(|_| format!("文章正文过长: {}", article.id))?;
+            let range = ContentRange { offset: store.len() as u64, len };
+            store.extend_from_slice(content.as_bytes());
+            Ok(range)
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(store)
+}
+
+/// 从内容文件中恢复各篇文章的正文，恢复后索引不再依赖内容文件
+pub fn restore_content(index: &mut ArticleSearchIndex, store: &[u8]) -> Result<(), String> {
+    for (article, range) in index.articles.iter_mut().zip(&index.content_ranges) {
+        if range.len == 0 {
+            continue;
+        }
+        let start = usize::try_from(range.offset).map_err(|_| format!("正文区间无效: {}", article.id))?;
+        let bytes = store.get(start..start + range.len as usize)
+            .ok_or_else(|| format!("内容文件不完整: {}", article.id))?;
+        article.content = String::from_utf8(bytes.to_vec())
+            .map_err(|_| format!("正文不是有效的UTF-8: {}", article.id))?;
+    }
+    index.content_ranges.clear();
+    Ok(())
+}
+
+/// 文章正文是否存放在内容文件中
+pub fn is_external(index: &ArticleSearchIndex, article_id: usize) -> bool {
+    external_range(index, article_id).is_some()
+}
+
+/// 获取已经可用的正文（内嵌或已加载），不调用加载函数
+///
+/// 用于需要遍历大量文章的场景（宽松匹配、会话候选），避免一次请求所有正文。
+pub fn cached(index: &ArticleSearchIndex, article_id: usize) -> Option<Cow<'_, str>> {
+    let article = index.articles.get(article_id)?;
+    if !is_external(index, article_id) {
+        return Some(Cow::Borrowed(article.content.as_str()));
+    }
+    CACHE.with(|cache| cache.borrow().get(&article.id).cloned()).map(Cow::Owned)
+}
+
+/// 获取文章正文，正文尚未加载时调用加载函数
+pub fn load(index: &ArticleSearchIndex, article_id: usize) -> Option<Cow<'_, str>> {
+    if let Some(content) = cached(index, article_id) {
+        return Some(content);
+    }
+
+    let article = index.articles.get(article_id)?;
+    let range = external_range(index, article_id)?;
+    let content = LOADER.with(|loader| loader.borrow().as_ref().and_then(|load| load(&article.id, range)))?;
+    provide(&article.id, content.clone());
+    Some(Cow::Owned(content))
+}
+
+/// 获取带正文的文章元数据，正文无法加载时返回None
+pub fn with_content(index: &ArticleSearchIndex, article_id: usize) -> Option<Cow<'_, ArticleMetadata>> {
+    let article = index.articles.get(article_id)?;
+    if !is_external(index, article_id) {
+        return Some(Cow::Borrowed(article));
+    }
+
+    let content = load(index, article_id)?;
+    let mut article = article.clone();
+    article.content = content.into_owned();
+    Some(Cow::Owned(article))
+}
+
+// 正文存放在内容文件中时返回其区间
+fn external_range(index: &ArticleSearchIndex, article_id: usize) -> Option<ContentRange> {
+    index.content_ranges.get(article_id).copied().filter(|range| range.len > 0)
+}
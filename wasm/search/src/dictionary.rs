@@ -0,0 +1,181 @@
+use fst::automaton::{Levenshtein, Str};
+use fst::{Automaton, IntoStreamer, Map, MapBuilder, Streamer};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 词典 - 词汇存放在有限状态转换器(FST)中，值为倒排列表的序号
+///
+/// 所有倒排列表首尾相接存放在同一个字节数组中，`offsets[i]..offsets[i + 1]`
+/// 为第 i 个词汇对应的文章位置。文章位置升序排列，除第一项外只保存与前一项的差值，
+/// 并使用变长编码（每字节7位，最高位表示后面还有字节），查询时按需解码。
+/// 加载索引后直接在FST字节上查询，无需重建哈希表，同时支持按前缀和编辑距离扩展词汇。
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TermDictionary {
+    /// FST字节，词汇按字节序排列
+    fst: Vec<u8>,
+    /// 每个倒排列表在 `postings` 中的起始字节位置，最后一项为总长度
+    offsets: Vec<u32>,
+    /// 差值和变长编码后的倒排列表
+    postings: Vec<u8>,
+}
+
+impl Default for TermDictionary {
+    fn default() -> Self {
+        Self::from_postings(BTreeMap::new())
+    }
+}
+
+impl TermDictionary {
+    /// 从词汇到文章位置的映射构建词典，没有文章的词汇会被忽略
+    pub fn from_postings(postings: BTreeMap<String, BTreeSet<usize>>) -> Self {
+        let mut builder = MapBuilder::memory();
+        let mut offsets = vec![0];
+        let mut encoded = Vec::new();
+
+        for (term, article_ids) in postings.into_iter().filter(|(_, ids)| !ids.is_empty()) {
+            // BTreeMap按字节序遍历，插入顺序一定递增
+            builder.insert(&term, (offsets.len() - 1) as u64)
+                .expect("词汇按字节序插入");
+            // 集合有序且不重复，差值一定为正（第一项与0比较）
+            let mut previous = 0;
+            for article_id in article_ids {
+                let article_id = article_id as u32;
+                write_varint(&mut encoded, article_id - previous);
+                previous = article_id;
+            }
+            offsets.push(encoded.len() as u32);
+        }
+
+        TermDictionary {
+            fst: builder.into_inner().expect("写入内存不会失败"),
+            offsets,
+            postings: encoded,
+        }
+    }
+
+    /// 还原为词汇到文章位置的映射，用于修改后重新构建
+    pub fn to_postings(&self) -> BTreeMap<String, BTreeSet<usize>> {
+        let mut result = BTreeMap::new();
+        if let Some(map) = self.map() {
+            let mut stream = map.stream();
+            while let Some((term, slot)) = stream.next() {
+                let term = String::from_utf8_lossy(term).into_owned();
+                result.insert(term, self.postings_at(slot).collect());
+            }
+        }
+        result
+    }
+
+    /// 词汇数量
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// 词典是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 查找词汇对应的文章位置
+    pub fn get(&self, term: &str) -> Option<Postings<'_>> {
+        self.map()?.get(term).map(|slot| self.postings_at(slot))
+    }
+
+    /// 词典中是否包含该词汇
+    pub fn contains_key(&self, term: &str) -> bool {
+        self.map().is_some_and(|map| map.contains_key(term))
+    }
+
+    /// 查找以 `prefix` 开头的词汇，按字节序返回
+    pub fn prefix(&self, prefix: &str) -> Vec<(String, Postings<'_>)> {
+        self.search(Str::new(prefix).starts_with())
+    }
+
+    /// 查找与 `term` 编辑距离不超过 `distance` 的词汇，按字节序返回
+    ///
+    /// 词汇过长导致自动机过大时不做扩展，返回空列表。
+    pub fn fuzzy(&self, term: &str, distance: u32) -> Vec<(String, Postings<'_>)> {
+        match Levenshtein::new(term, distance) {
+            Ok(automaton) => self.search(automaton),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    // 打开FST，数据损坏时视为空词典
+    fn map(&self) -> Option<Map<&[u8]>> {
+        Map::new(self.fst.as_slice()).ok()
+    }
+
+    // 用自动机遍历词典
+    fn search<A: Automaton>(&self, automaton: A) -> Vec<(String, Postings<'_>)> {
+        let Some(map) = self.map() else {
+            return Vec::new();
+        };
+
+        let mut result = Vec::new();
+        let mut stream = map.search(automaton).into_stream();
+        while let Some((term, slot)) = stream.next() {
+            result.push((String::from_utf8_lossy(term).into_owned(), self.postings_at(slot)));
+        }
+        result
+    }
+
+    // 读取第 slot 个倒排列表
+    fn postings_at(&self, slot: u64) -> Postings<'_> {
+        let slot = slot as usize;
+        let start = self.offsets.get(slot).copied().unwrap_or(0) as usize;
+        let end = self.offsets.get(slot + 1).copied().unwrap_or(0) as usize;
+        Postings {
+            bytes: self.postings.get(start..end).unwrap_or(&[]),
+            previous: 0,
+        }
+    }
+}
+
+/// 写入变长编码的整数
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// 读取变长编码的整数，数据不完整或溢出时返回None
+pub(crate) fn read_varint(bytes: &mut &[u8]) -> Option<u32> {
+    let mut value: u32 = 0;
+    let mut shift = 0;
+    loop {
+        let (&byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        value |= ((byte & 0x7f) as u32).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+        shift += 7;
+    }
+}
+
+/// 倒排列表 - 按升序逐个解码一个词汇对应的文章位置
+#[derive(Clone, Debug)]
+pub struct Postings<'a> {
+    /// 尚未解码的字节
+    bytes: &'a [u8],
+    /// 上一个解码出的文章位置，第一项的差值相对于0
+    previous: u32,
+}
+
+impl Iterator for Postings<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let value = read_varint(&mut self.bytes)?;
+        self.previous = self.previous.checked_add(value)?;
+        Some(self.previous as usize)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // 每个文章位置至少占1字节
+        (0, Some(self.bytes.len()))
+    }
+}
@@ -0,0 +1,194 @@
+use once_cell::sync::OnceCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use utils_common::compression::from_compressed_with_version_range;
+use utils_common::models::ArticleMetadata;
+use crate::builder::SearchBuilder;
+use crate::models::ArticleSearchIndex;
+use crate::session::{self, SearchSession, MAX_SESSIONS};
+
+/// 构建器写入的索引版本，索引结构变化时递增主版本号
+pub const INDEX_VERSION: [u8; 2] = [15, 0];
+
+/// 支持的最大索引版本
+pub const MAX_INDEX_VERSION: u8 = INDEX_VERSION[0];
+
+/// 支持的最小索引版本 - 更早版本的索引结构不同，加载时返回要求重新生成索引的错误
+pub const MIN_INDEX_VERSION: u8 = 15;
+
+/// 默认索引名称 - `load_search_index` 和 `search_articles` 使用的索引
+pub const DEFAULT_INDEX: &str = "default";
+
+// 全局搜索引擎存储
+static ENGINE: OnceCell<Mutex<Option<SearchEngine>>> = OnceCell::new();
+
+/// 参与一次搜索的索引
+#[derive(Clone, Copy)]
+pub struct SearchSource<'a> {
+    /// 索引名称
+    pub name: &'a str,
+    /// 搜索索引
+    pub index: &'a ArticleSearchIndex,
+    /// 候选文章 - 只在这些文章中匹配，为空时匹配所有文章
+    pub candidates: Option<&'a HashSet<usize>>,
+}
+
+impl<'a> SearchSource<'a> {
+    /// 在整个索引中搜索
+    pub fn new(name: &'a str, index: &'a ArticleSearchIndex) -> Self {
+        Self { name, index, candidates: None }
+    }
+}
+
+/// 搜索引擎 - 持有已加载的搜索索引，避免每次查询都重新解压
+///
+/// 可以按名称加载多个索引（如按语言或栏目拆分的索引文件），一起搜索。
+#[derive(Default)]
+pub struct SearchEngine {
+    indexes: BTreeMap<String, ArticleSearchIndex>,
+    /// 边输入边搜索的会话，按客户端提供的会话ID保存
+    sessions: HashMap<String, SearchSession>,
+    /// 会话使用序号
+    session_clock: u64,
+}
+
+impl SearchEngine {
+    /// 使用已解码的索引创建搜索引擎，索引以默认名称保存
+    pub fn new(index: ArticleSearchIndex) -> Self {
+        let mut engine = Self::default();
+        engine.insert(DEFAULT_INDEX, index);
+        engine
+    }
+
+    /// 从压缩的二进制数据创建搜索引擎
+    pub fn from_compressed(data: &[u8]) -> Result<Self, String> {
+        decode_index(data).map(Self::new)
+    }
+
+    /// 添加或替换指定名称的索引
+    ///
+    /// 会话中保存的候选文章依赖索引内容，索引变化后全部作废。
+    pub fn insert(&mut self, name: &str, index: ArticleSearchIndex) {
+        self.indexes.insert(name.to_string(), index);
+        self.sessions.clear();
+    }
+
+    /// 在指定索引中添加或替换一篇文章，返回文章是否被索引（目录页面不会被索引）
+    pub fn upsert_article(&mut self, name: &str, article: ArticleMetadata) -> Result<bool, String> {
+        let index = self.indexes.get_mut(name)
+            .ok_or_else(|| format!("索引未加载: {}", name))?;
+        let indexed = SearchBuilder::upsert_into_index(index, article)?.is_some();
+        self.sessions.clear();
+        Ok(indexed)
+    }
+
+    /// 从指定索引中移除文章，返回是否找到该文章
+    pub fn remove_article(&mut self, name: &str, id: &str) -> Result<bool, String> {
+        let index = self.indexes.get_mut(name)
+            .ok_or_else(|| format!("索引未加载: {}", name))?;
+        let removed = SearchBuilder::remove_from_index(index, id)?;
+        if removed {
+            self.sessions.clear();
+        }
+        Ok(removed)
+    }
+
+    /// 按搜索范围选择索引，范围为空时返回所有索引
+    pub fn scoped(&self, scope: &[String]) -> Result<Vec<SearchSource<'_>>, String> {
+        if scope.is_empty() {
+            return Ok(self.indexes.iter().map(|(name, index)| SearchSource::new(name, index)).collect());
+        }
+
+        scope.iter()
+            .map(|name| {
+                self.indexes.get_key_value(name)
+                    .map(|(name, index)| SearchSource::new(name, index))
+                    .ok_or_else(|| format!("搜索范围中的索引未加载: {}", name))
+            })
+            .collect()
+    }
+
+    /// 按搜索范围选择索引，并使用会话缩小候选文章
+    ///
+    /// 查询在会话上一次查询后追加字符时，只在上一次的候选文章中筛选；
+    /// 否则重新扫描所有文章。筛选结果保存到会话中供下一次查询使用。
+    pub fn scoped_with_session(&mut self, scope: &[String], session_id: &str, query: &str) -> Result<Vec<SearchSource<'_>>, String> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            self.sessions.remove(session_id);
+            return self.scoped(scope);
+        }
+
+        self.session_clock += 1;
+        let previous = self.sessions.remove(session_id)
+            .filter(|session| session.can_extend(&query, scope));
+
+        let mut candidates = BTreeMap::new();
+        for source in self.scoped(scope)? {
+            let base = previous.as_ref().and_then(|session| session.candidates.get(source.name));
+            let found = session::collect_candidates(source.index, &query, base);
+            candidates.insert(source.name.to_string(), found);
+        }
+
+        // 淘汰最久未使用的会话
+        if self.sessions.len() >= MAX_SESSIONS {
+            let oldest = self.sessions.iter()
+                .min_by_key(|(_, session)| session.last_used)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                self.sessions.remove(&oldest);
+            }
+        }
+
+        self.sessions.insert(session_id.to_string(), SearchSession {
+            query,
+            scope: scope.to_vec(),
+            candidates,
+            last_used: self.session_clock,
+        });
+
+        let session = &self.sessions[session_id];
+        let mut sources = self.scoped(scope)?;
+        for source in &mut sources {
+            source.candidates = session.candidates.get(source.name);
+        }
+        Ok(sources)
+    }
+}
+
+/// 解压并反序列化搜索索引
+pub fn decode_index(data: &[u8]) -> Result<ArticleSearchIndex, String> {
+    let index = from_compressed_with_version_range::<ArticleSearchIndex>(data, MIN_INDEX_VERSION, MAX_INDEX_VERSION)
+        .map_err(|e| format!("解压搜索索引失败: {}", e))?;
+    index.validate_ordinals()
+        .map_err(|e| format!("搜索索引不一致: {}", e))?;
+    Ok(index)
+}
+
+/// 设置全局搜索引擎，替换之前加载的所有索引
+pub fn install(engine: SearchEngine) -> Result<(), String> {
+    let mut guard = ENGINE.get_or_init(|| Mutex::new(None))
+        .lock()
+        .map_err(|_| "获取搜索引擎锁失败".to_string())?;
+    *guard = Some(engine);
+    Ok(())
+}
+
+/// 向全局搜索引擎添加或替换一个命名索引，保留其他已加载的索引
+pub fn add_index(name: &str, index: ArticleSearchIndex) -> Result<(), String> {
+    let mut guard = ENGINE.get_or_init(|| Mutex::new(None))
+        .lock()
+        .map_err(|_| "获取搜索引擎锁失败".to_string())?;
+    guard.get_or_insert_with(SearchEngine::default).insert(name, index);
+    Ok(())
+}
+
+/// 使用全局搜索引擎执行操作
+pub fn with_engine<R>(f: impl FnOnce(&mut SearchEngine) -> R) -> Result<R, String> {
+    let mut guard = ENGINE.get()
+        .ok_or("搜索索引未加载")?
+        .lock()
+        .map_err(|_| "获取搜索引擎锁失败".to_string())?;
+    let engine = guard.as_mut().ok_or("搜索索引未加载")?;
+    Ok(f(engine))
+}
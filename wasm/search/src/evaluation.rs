@@ -0,0 +1,146 @@
+// 影子索引评估 - 仅在原生环境中使用
+// 用同一组查询分别搜索基准索引和候选索引（如调整分词器或权重后重新构建的索引），
+// 比较两边的结果差异，而不必在线上站点逐个查看搜索结果。
+
+use serde::Serialize;
+use crate::cancel::CancelToken;
+use crate::engine::{self, SearchSource};
+use crate::models::{ArticleSearchIndex, SearchRequest};
+
+/// 参与比较的结果数量
+pub const TOP_K: usize = 10;
+
+/// 同一篇文章在两个索引中的排名变化
+#[derive(Serialize, Debug, Clone)]
+pub struct RankShift {
+    /// 文章ID
+    pub id: String,
+    /// 基准索引中的排名（从1开始）
+    pub baseline_rank: usize,
+    /// 候选索引中的排名（从1开始）
+    pub candidate_rank: usize,
+}
+
+/// 单个查询的比较结果
+#[derive(Serialize, Debug, Clone)]
+pub struct QueryComparison {
+    /// 查询
+    pub query: String,
+    /// 基准索引的结果总数
+    pub baseline_total: usize,
+    /// 候选索引的结果总数
+    pub candidate_total: usize,
+    /// 前 `TOP_K` 条结果的重合比例，两边都没有结果时为1
+    pub overlap_at_k: f64,
+    /// 两边前 `TOP_K` 条结果中都出现的文章数量
+    pub shared: usize,
+    /// 两边前 `TOP_K` 条结果中都出现但排名不同的文章
+    pub rank_shifts: Vec<RankShift>,
+    /// 只在基准索引前 `TOP_K` 条结果中出现的文章
+    pub only_in_baseline: Vec<String>,
+    /// 只在候选索引前 `TOP_K` 条结果中出现的文章
+    pub only_in_candidate: Vec<String>,
+}
+
+/// 评估报告
+#[derive(Serialize, Debug, Clone)]
+pub struct EvaluationReport {
+    /// 各查询的比较结果
+    pub queries: Vec<QueryComparison>,
+    /// 平均重合比例
+    pub mean_overlap_at_k: f64,
+    /// 共同结果的平均排名变化（绝对值）
+    pub mean_rank_shift: f64,
+    /// 结果总数发生变化的查询数量
+    pub total_changed: usize,
+}
+
+/// 用同一组查询比较两个索引的搜索结果
+pub fn compare_indexes(baseline: &ArticleSearchIndex, candidate: &ArticleSearchIndex, queries: &[String]) -> Result<EvaluationReport, String> {
+    let comparisons = queries.iter()
+        .map(|query| compare_query(baseline, candidate, query))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let count = comparisons.len().max(1) as f64;
+    let mean_overlap_at_k = comparisons.iter().map(|c| c.overlap_at_k).sum::<f64>() / count;
+
+    // 排名不变的共同结果也计入分母
+    let total_shift: usize = comparisons.iter()
+        .flat_map(|c| c.rank_shifts.iter().map(|s| s.baseline_rank.abs_diff(s.candidate_rank)))
+        .sum();
+    let shared: usize = comparisons.iter().map(|c| c.shared).sum();
+    let mean_rank_shift = if shared == 0 { 0.0 } else { total_shift as f64 / shared as f64 };
+
+    let total_changed = comparisons.iter().filter(|c| c.baseline_total != c.candidate_total).count();
+
+    Ok(EvaluationReport {
+        queries: comparisons,
+        mean_overlap_at_k,
+        mean_rank_shift,
+        total_changed,
+    })
+}
+
+/// 从文件加载两个索引并比较搜索结果
+pub fn compare_index_files(baseline_path: &str, candidate_path: &str, queries: &[String]) -> Result<EvaluationReport, String> {
+    let load = |path: &str| {
+        std::fs::read(path)
+            .map_err(|e| format!("无法读取索引文件 {}: {}", path, e))
+            .and_then(|data| engine::decode_index(&data))
+    };
+
+    compare_indexes(&load(baseline_path)?, &load(candidate_path)?, queries)
+}
+
+// 比较单个查询的结果
+fn compare_query(baseline: &ArticleSearchIndex, candidate: &ArticleSearchIndex, query: &str) -> Result<QueryComparison, String> {
+    let (baseline_total, baseline_ids) = top_results(baseline, query)?;
+    let (candidate_total, candidate_ids) = top_results(candidate, query)?;
+
+    let rank_of = |ids: &[String], id: &str| ids.iter().position(|other| other == id).map(|rank| rank + 1);
+
+    let mut rank_shifts = Vec::new();
+    let mut only_in_baseline = Vec::new();
+    for (i, id) in baseline_ids.iter().enumerate() {
+        match rank_of(&candidate_ids, id) {
+            Some(candidate_rank) if candidate_rank != i + 1 => rank_shifts.push(RankShift {
+                id: id.clone(),
+                baseline_rank: i + 1,
+                candidate_rank,
+            }),
+            Some(_) => {}
+            None => only_in_baseline.push(id.clone()),
+        }
+    }
+    let only_in_candidate: Vec<String> = candidate_ids.iter()
+        .filter(|id| rank_of(&baseline_ids, id).is_none())
+        .cloned()
+        .collect();
+
+    let shared = baseline_ids.len() - only_in_baseline.len();
+    let overlap_at_k = match baseline_ids.len().max(candidate_ids.len()) {
+        0 => 1.0,
+        larger => shared as f64 / larger as f64,
+    };
+
+    Ok(QueryComparison {
+        query: query.to_string(),
+        baseline_total,
+        candidate_total,
+        overlap_at_k,
+        shared,
+        rank_shifts,
+        only_in_baseline,
+        only_in_candidate,
+    })
+}
+
+// 搜索并返回结果总数和前 `TOP_K` 条结果的文章ID
+fn top_results(index: &ArticleSearchIndex, query: &str) -> Result<(usize, Vec<String>), String> {
+    let mut req = SearchRequest::new(query);
+    req.page_size = TOP_K;
+
+    let token = CancelToken::for_request(None);
+    let result = crate::execute_search(&[SearchSource::new(engine::DEFAULT_INDEX, index)], &req, &token)?;
+    Ok((result.total, result.items.into_iter().map(|item| item.id).collect()))
+}
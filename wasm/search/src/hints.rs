@@ -0,0 +1,189 @@
+// 无结果提示 - 查询没有结果时，从索引词典中找出与查询最接近的词汇
+// 候选词汇来自查询中的各个单词、编辑距离相近的词汇和以查询开头的词汇，
+// 每个候选词汇都实际搜索一次，只返回确定有结果的词汇，界面可以直接作为"试试: ..."展示。
+// 同时生成完整的替代查询：把查询中的单词替换为编辑距离相近的词汇，或去掉多词查询中的
+// 某个单词，同样实际搜索一次，按结果数排序，界面可以作为"你是不是要找: ..."展示。
+
+use std::collections::{BTreeMap, HashMap};
+use crate::cancel::CancelToken;
+use crate::engine::SearchSource;
+use crate::models::{AlternativeQuery, NoResultsHints, QueryDebug, QueryOperator};
+use crate::{find_matched_articles, levenshtein_distance, split_query_to_terms, MatchOptions};
+
+/// 最多返回的提示词汇数量
+const MAX_HINT_TERMS: usize = 5;
+
+/// 最多实际搜索的候选词汇数量
+const MAX_CHECKED_CANDIDATES: usize = 20;
+
+/// 每个单词最多取的前缀匹配词汇数量
+const MAX_PREFIX_CANDIDATES: usize = 50;
+
+/// 最多返回的替代查询数量
+const MAX_ALTERNATIVES: usize = 5;
+
+/// 最多实际搜索的替代查询数量
+const MAX_CHECKED_ALTERNATIVES: usize = 20;
+
+/// 每个单词最多使用的纠正词汇数量
+const MAX_CORRECTIONS_PER_WORD: usize = 2;
+
+/// 支持的查询用法（语法，说明）
+const SUPPORTED_OPERATORS: &[(&str, &str)] = &[
+    ("rust wasm", "整个查询作为一个短语匹配，标题以查询开头的文章排在最前"),
+    ("Rust", "不区分大小写"),
+];
+
+/// 生成无结果提示，`terms` 为查询切分出的词条，第一个为完整查询
+pub(crate) fn no_results_hints(
+    sources: &[SearchSource],
+    terms: &[String],
+    page_type_boosts: &HashMap<String, f64>,
+    token: &CancelToken,
+) -> NoResultsHints {
+    // 候选词汇 -> (与查询单词的最小编辑距离, 所在文章数)
+    let mut candidates: BTreeMap<String, (i32, usize)> = BTreeMap::new();
+    let add = |candidates: &mut BTreeMap<String, (i32, usize)>, candidate: String, distance: i32, documents: usize| {
+        let entry = candidates.entry(candidate).or_insert((distance, documents));
+        entry.0 = entry.0.min(distance);
+        entry.1 = entry.1.max(documents);
+    };
+    // 查询中的各个单词，单个单词的查询即完整查询
+    let words = if terms.len() > 1 { &terms[1..] } else { terms };
+    // 单词 -> 编辑距离相近的纠正词汇
+    let mut corrections: BTreeMap<&str, BTreeMap<String, (i32, usize)>> = BTreeMap::new();
+
+    for source in sources {
+        let index = source.index;
+        // 多个单词的查询，先尝试各个单词
+        for word in terms.iter().skip(1) {
+            add(&mut candidates, word.clone(), 0, 0);
+        }
+
+        for word in terms {
+            let length = word.chars().count();
+            let distance = if length <= 4 { 1 } else { 2 };
+            for dictionary in [&index.title_term_index, &index.content_term_index] {
+                let fuzzy = dictionary.fuzzy(word, distance);
+                let prefixed = if length >= 2 {
+                    dictionary.prefix(word).into_iter().take(MAX_PREFIX_CANDIDATES).collect()
+                } else {
+                    Vec::new()
+                };
+                let fuzzy_count = fuzzy.len();
+                for (position, (candidate, postings)) in fuzzy.into_iter().chain(prefixed).enumerate() {
+                    let documents = postings.filter(|article_id| index.is_live(*article_id)).count();
+                    if documents > 0 {
+                        let distance = levenshtein_distance(word, &candidate);
+                        if position < fuzzy_count && words.contains(word) && candidate != *word {
+                            add(corrections.entry(word.as_str()).or_default(), candidate.clone(), distance, documents);
+                        }
+                        add(&mut candidates, candidate, distance, documents);
+                    }
+                }
+            }
+        }
+    }
+
+    // 编辑距离小的在前，同距离时文章数多的在前
+    let query = terms.first().map(String::as_str).unwrap_or_default();
+    let mut ranked: Vec<(String, (i32, usize))> = candidates.into_iter()
+        .filter(|(candidate, _)| candidate != query)
+        .collect();
+    ranked.sort_by(|a, b| a.1.0.cmp(&b.1.0).then_with(|| b.1.1.cmp(&a.1.1)).then_with(|| a.0.cmp(&b.0)));
+
+    let mut hint_terms = Vec::new();
+    for (candidate, _) in ranked.into_iter().take(MAX_CHECKED_CANDIDATES) {
+        if hint_terms.len() >= MAX_HINT_TERMS || token.is_cancelled() {
+            break;
+        }
+        let candidate_terms = [candidate.clone()];
+        let has_results = sources.iter().any(|source| {
+            let options = MatchOptions { page_type_boosts, candidates: None, token };
+            !find_matched_articles(source.index, &candidate_terms, &options, &mut QueryDebug::default()).is_empty()
+        });
+        if has_results {
+            hint_terms.push(candidate);
+        }
+    }
+
+    NoResultsHints {
+        terms: hint_terms,
+        did_you_mean: alternative_queries(sources, words, &corrections, page_type_boosts, token),
+        operators: SUPPORTED_OPERATORS.iter()
+            .map(|(syntax, description)| QueryOperator {
+                syntax: syntax.to_string(),
+                description: description.to_string(),
+            })
+            .collect(),
+    }
+}
+
+/// 生成替代查询并按结果数排序，只返回有结果的查询
+fn alternative_queries(
+    sources: &[SearchSource],
+    words: &[String],
+    corrections: &BTreeMap<&str, BTreeMap<String, (i32, usize)>>,
+    page_type_boosts: &HashMap<String, f64>,
+    token: &CancelToken,
+) -> Vec<AlternativeQuery> {
+    // 每个单词的纠正词汇，编辑距离小的在前，同距离时文章数多的在前
+    let best: Vec<Vec<&str>> = words.iter()
+        .map(|word| {
+            let mut ranked: Vec<(&String, &(i32, usize))> = corrections.get(word.as_str())
+                .map(|candidates| candidates.iter().collect())
+                .unwrap_or_default();
+            ranked.sort_by(|a, b| a.1.0.cmp(&b.1.0).then_with(|| b.1.1.cmp(&a.1.1)).then_with(|| a.0.cmp(b.0)));
+            ranked.into_iter().take(MAX_CORRECTIONS_PER_WORD).map(|(candidate, _)| candidate.as_str()).collect()
+        })
+        .collect();
+
+    let mut queries: Vec<String> = Vec::new();
+    let mut push = |parts: Vec<&str>| {
+        let query = parts.join(" ");
+        if !query.is_empty() && !queries.contains(&query) {
+            queries.push(query);
+        }
+    };
+    // 纠正所有有纠正词汇的单词
+    if best.iter().filter(|options| !options.is_empty()).count() > 1 {
+        push(words.iter().zip(&best).map(|(word, options)| options.first().copied().unwrap_or(word)).collect());
+    }
+    // 纠正其中一个单词
+    for (index, options) in best.iter().enumerate() {
+        for option in options {
+            let mut parts: Vec<&str> = words.iter().map(String::as_str).collect();
+            parts[index] = option;
+            push(parts);
+        }
+    }
+    // 去掉其中一个单词
+    if words.len() > 1 {
+        for index in 0..words.len() {
+            push(words.iter().enumerate().filter(|(i, _)| *i != index).map(|(_, word)| word.as_str()).collect());
+        }
+    }
+
+    let original = words.join(" ");
+    let mut alternatives = Vec::new();
+    for query in queries.into_iter().filter(|query| *query != original).take(MAX_CHECKED_ALTERNATIVES) {
+        if token.is_cancelled() {
+            break;
+        }
+        let terms = split_query_to_terms(&query);
+        let total: usize = sources.iter()
+            .map(|source| {
+                let options = MatchOptions { page_type_boosts, candidates: None, token };
+                find_matched_articles(source.index, &terms, &options, &mut QueryDebug::default()).len()
+            })
+            .sum();
+        if total > 0 {
+            alternatives.push(AlternativeQuery { query, total });
+        }
+    }
+
+    // 结果多的在前，同样多时保持生成顺序（纠正优先于去掉单词）
+    alternatives.sort_by_key(|alternative| std::cmp::Reverse(alternative.total));
+    alternatives.truncate(MAX_ALTERNATIVES);
+    alternatives
+}
@@ -1,57 +1,507 @@
 use wasm_bindgen::prelude::*;
-use utils_common::compression::from_compressed_with_max_version;
-use crate::models::{ArticleSearchIndex, SearchRequest, SearchResult, SearchResultItem, HeadingNode, HeadingIndexEntry, SuggestionCandidate, SearchSuggestion, SuggestionType};
+use crate::models::{ArticleSearchIndex, SearchRequest, SearchResult, SearchResultItem, Diagnostics, HeadingNode, HeadingIndexEntry, SuggestionCandidate, SearchSuggestion, CommonTerm, TermField, SuggestionPage, SuggestionType, MatchSpan, SummaryMode, ResultDetail, QueryDebug, QueryRewrite, RewriteKind, AppliedFilter, TermStats, IndexTermStats};
 use std::collections::{HashMap, HashSet};
-use web_sys;
+use utils_common::models::Visibility;
+use utils_common::text::detect_language;
+use cancel::CancelToken;
+use engine::SearchSource;
+use utils_common::compression::{from_binary, to_binary};
 pub mod models;
 pub mod builder;
+pub mod runtime;
+pub mod engine;
+pub mod cancel;
+pub mod session;
+pub mod dictionary;
+pub mod positions;
+pub mod content;
+pub mod related;
+pub mod analyzer;
+pub mod query_log;
+mod hints;
+mod clusters;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod evaluation;
+
+// 浏览器中的 wasm 模块没有 rayon 可用的线程池，并行只用于原生环境
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+compile_error!("`parallel` 特性只用于原生环境，构建 wasm 模块时不要启用");
+
+/// 初始化函数 - 设置错误处理
+#[wasm_bindgen(start)]
+pub fn start() {
+    // 捕获Rust panic并转换为JS错误，只需在模块加载时安装一次
+    console_error_panic_hook::set_once();
+}
 
 /// WASM入口点 - 搜索文章
 #[wasm_bindgen]
 pub fn search_articles(index_data: &[u8], request_json: &str) -> Result<String, JsValue> {
-    // 捕获Rust panic并转换为JS错误
-    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
-    
-    let start_time = web_sys::window()
-        .and_then(|w| w.performance())
-        .map(|p| p.now())
-        .unwrap_or(0.0);
-    
-    // 解析搜索请求
-    let req: SearchRequest = match serde_json::from_str(request_json) {
-        Ok(r) => r,
-        Err(e) => {
-            return Err(JsValue::from_str(&format!("解析搜索请求失败: {}", e)));
-        }
-    };
+    run_search(index_data, request_json).map_err(|e| {
+        runtime::log(&e);
+        JsValue::from_str(&e)
+    })
+}
+
+/// 执行搜索并返回JSON格式的结果
+fn run_search(index_data: &[u8], request_json: &str) -> Result<String, String> {
+    let start_time = runtime::now_ms();
+    let req = parse_search_request(request_json)?;
+    let result = search_index_data(index_data, &req)?;
+    finish_search(&req, result, start_time)
+}
+
+/// WASM入口点 - 使用二进制协议搜索文章
+///
+/// 请求和响应使用与索引文件相同的bincode标准配置编码，省去输入时高频调用中
+/// JSON解析和生成的开销。编码规则（供JS端编解码参考）：
+///
+/// - 结构体按字段定义顺序依次编码，没有字段名，`SearchRequest` 的所有字段都必须提供
+/// - 整数（`usize`、`u32`）使用变长编码：小于251时为1字节；否则前缀251/252/253后跟
+///   小端序的u16/u32/u64
+/// - `f64` 为8字节小端序，`bool` 为1字节
+/// - 字符串为变长长度前缀加UTF-8字节，`Vec` 和 `HashMap` 为变长元素个数加各元素
+/// - `Option` 为1字节标记（0表示空，1表示有值）加值，枚举为变长的变体序号
+#[wasm_bindgen]
+pub fn search_articles_bin(index_data: &[u8], request: &[u8]) -> Result<Vec<u8>, JsValue> {
+    run_search_bin(index_data, request).map_err(|e| {
+        runtime::log(&e);
+        JsValue::from_str(&e)
+    })
+}
+
+/// 执行搜索并返回二进制编码的结果
+fn run_search_bin(index_data: &[u8], request: &[u8]) -> Result<Vec<u8>, String> {
+    let start_time = runtime::now_ms();
+    
+    let req: SearchRequest = from_binary(request)
+        .map_err(|e| format!("解析二进制搜索请求失败: {}", e))?;
+    validate_search_request(&req)?;
+    
+    let mut result = search_index_data(index_data, &req)?;
+    record_search(&req, &mut result, start_time);
+    
+    to_binary(&result)
+        .map_err(|e| format!("序列化搜索结果失败: {}", e))
+}
+
+/// 解压索引数据并执行搜索
+fn search_index_data(index_data: &[u8], req: &SearchRequest) -> Result<SearchResult, String> {
+    let token = CancelToken::for_request(req.epoch);
+    
+    // 已取消的请求无需解压索引
+    if token.is_cancelled() {
+        return Ok(cancelled_result(&[], req));
+    }
     
     // 解压缩搜索索引
-    let search_index = match from_compressed_with_max_version::<ArticleSearchIndex>(index_data, 9) {
-        Ok(idx) => idx,
-        Err(e) => {
-            return Err(JsValue::from_str(&format!("解压搜索索引失败: {}", e)));
-        }
-    };
+    let search_index = engine::decode_index(index_data)?;
     
     // 执行搜索
-    let mut result = match req.search_type.as_str() {
-        "autocomplete" => perform_autocomplete(&search_index, &req),
-        _ => perform_search(&search_index, &req),
-    };
+    execute_search(&[SearchSource::new(engine::DEFAULT_INDEX, &search_index)], req, &token)
+}
+
+/// WASM入口点 - 在已加载的索引中搜索文章
+///
+/// 通过请求中的 `scope` 字段选择要搜索的索引，为空时搜索所有已加载的索引，
+/// 各索引的结果合并后统一排序，每个结果项带有所属索引的名称。
+#[wasm_bindgen]
+pub fn search_loaded_indexes(request_json: &str) -> Result<String, JsValue> {
+    run_loaded_search(request_json).map_err(|e| {
+        runtime::log(&e);
+        JsValue::from_str(&e)
+    })
+}
+
+/// 在已加载的索引中执行搜索并返回JSON格式的结果
+fn run_loaded_search(request_json: &str) -> Result<String, String> {
+    let start_time = runtime::now_ms();
+    let req = parse_search_request(request_json)?;
+    let token = CancelToken::for_request(req.epoch);
+    
+    let result = engine::with_engine(|engine| {
+        let sources = match &req.session_id {
+            Some(session_id) => engine.scoped_with_session(&req.scope, session_id, &req.query_scope().text)?,
+            None => engine.scoped(&req.scope)?,
+        };
+        execute_search(&sources, &req, &token)
+    })??;
+    finish_search(&req, result, start_time)
+}
+
+/// 解析并校验搜索请求
+fn parse_search_request(request_json: &str) -> Result<SearchRequest, String> {
+    let req: SearchRequest = serde_json::from_str(request_json)
+        .map_err(|e| format!("解析搜索请求失败: {}", e))?;
+    validate_search_request(&req)?;
+    Ok(req)
+}
+
+/// 校验搜索请求
+fn validate_search_request(req: &SearchRequest) -> Result<(), String> {
+    // 页码和每页条数参与除法和减法运算，必须大于0
+    if req.page == 0 || req.page_size == 0 {
+        return Err(format!("无效的分页参数: page={}, page_size={}", req.page, req.page_size));
+    }
     
-    // 计算执行时间
-    let end_time = web_sys::window()
-        .and_then(|w| w.performance())
-        .map(|p| p.now())
-        .unwrap_or(0.0);
+    Ok(())
+}
+
+/// 按搜索类型在给定的索引中执行搜索
+fn execute_search(sources: &[SearchSource], req: &SearchRequest, token: &CancelToken) -> Result<SearchResult, String> {
+    if token.is_cancelled() {
+        return Ok(cancelled_result(sources, req));
+    }
+    check_tokenizer(sources, req)?;
     
-    let time_ms = (end_time - start_time) as usize;
-    result.time_ms = time_ms;
+    Ok(match req.search_type.as_str() {
+        "autocomplete" => perform_autocomplete(sources, req),
+        _ => perform_search(sources, req, token),
+    })
+}
+
+/// 检查请求声明的分词器与索引构建时使用的分词器是否一致
+///
+/// 查询按请求方的分词方式切分，与索引不一致时词汇无法对应，直接报错而不是返回空结果。
+fn check_tokenizer(sources: &[SearchSource], req: &SearchRequest) -> Result<(), String> {
+    let Some(tokenizer) = &req.tokenizer else {
+        return Ok(());
+    };
+    
+    match sources.iter().find(|source| source.index.tokenizer != *tokenizer) {
+        Some(source) => Err(format!(
+            "分词器不一致: 请求使用 {}, 索引 {} 使用 {}",
+            tokenizer, source.name, source.index.tokenizer
+        )),
+        None => Ok(()),
+    }
+}
+
+/// 记录搜索耗时（关闭计时后不写入结果）并写入查询日志
+fn record_search(req: &SearchRequest, result: &mut SearchResult, start_time: f64) {
+    let elapsed = runtime::now_ms() - start_time;
+    if runtime::timing_enabled() {
+        result.diagnostics = Some(Diagnostics { time_ms: elapsed as usize });
+    }
+    query_log::record(req, result, elapsed);
+}
+
+/// 已取消搜索的结果
+fn cancelled_result(sources: &[SearchSource], req: &SearchRequest) -> SearchResult {
+    SearchResult {
+        items: Vec::new(),
+        total: 0,
+        page: req.page,
+        page_size: req.page_size,
+        total_pages: 0,
+        query: req.query.to_lowercase(),
+        suggestions: Vec::new(),
+        cancelled: true,
+        query_debug: query_debug(sources, req),
+        hints: None,
+        diagnostics: None,
+        clusters: None,
+    }
+}
+
+/// 填充搜索耗时并序列化结果
+fn finish_search(req: &SearchRequest, mut result: SearchResult, start_time: f64) -> Result<String, String> {
+    record_search(req, &mut result, start_time);
     
     // 序列化结果
-    match serde_json::to_string(&result) {
-        Ok(json) => Ok(json),
-        Err(e) => Err(JsValue::from_str(&format!("序列化搜索结果失败: {}", e))),
+    serde_json::to_string(&result)
+        .map_err(|e| format!("序列化搜索结果失败: {}", e))
+}
+
+/// 加载搜索索引并常驻内存，供 `get_suggestions` 等接口使用
+///
+/// 替换之前加载的所有索引，新索引使用默认名称。`max_visibility` 为最高可见级别
+/// （public 或 members），默认只搜索公开文章，unlisted 文章始终不出现在结果中。
+#[wasm_bindgen]
+pub fn load_search_index(index_data: &[u8], max_visibility: Option<String>) -> Result<(), JsValue> {
+    decode_visible_index(index_data, max_visibility.as_deref())
+        .map(engine::SearchEngine::new)
+        .and_then(engine::install)
+        .map_err(|e| {
+            runtime::log(&e);
+            JsValue::from_str(&e)
+        })
+}
+
+/// 加载一个命名索引，与已加载的其他索引一起参与搜索
+///
+/// 同名索引会被替换。`max_visibility` 与 `load_search_index` 相同。
+#[wasm_bindgen]
+pub fn add_index(name: &str, index_data: &[u8], max_visibility: Option<String>) -> Result<(), JsValue> {
+    decode_visible_index(index_data, max_visibility.as_deref())
+        .and_then(|index| engine::add_index(name, index))
+        .map_err(|e| {
+            runtime::log(&e);
+            JsValue::from_str(&e)
+        })
+}
+
+/// 在已加载的索引中添加或替换一篇文章（按ID匹配），无需重新构建和加载索引
+///
+/// 适合预览环境实时反映草稿的修改。`index_name` 为空时使用默认索引，
+/// 返回文章是否被索引（目录页面不会被索引）。
+#[wasm_bindgen]
+pub fn upsert_article(article_json: &str, index_name: Option<String>) -> Result<bool, JsValue> {
+    let name = index_name.as_deref().unwrap_or(engine::DEFAULT_INDEX);
+    serde_json::from_str::<utils_common::models::ArticleMetadata>(article_json)
+        .map_err(|e| format!("解析文章失败: {}", e))
+        .and_then(|article| engine::with_engine(|engine| engine.upsert_article(name, article))?)
+        .map_err(|e| {
+            runtime::log(&e);
+            JsValue::from_str(&e)
+        })
+}
+
+/// 从已加载的索引中移除文章，返回是否找到该文章
+///
+/// `index_name` 为空时使用默认索引。
+#[wasm_bindgen]
+pub fn remove_article(id: &str, index_name: Option<String>) -> Result<bool, JsValue> {
+    let name = index_name.as_deref().unwrap_or(engine::DEFAULT_INDEX);
+    engine::with_engine(|engine| engine.remove_article(name, id))
+        .and_then(|removed| removed)
+        .map_err(|e| {
+            runtime::log(&e);
+            JsValue::from_str(&e)
+        })
+}
+
+/// 设置正文加载函数，正文拆分到单独内容文件的索引在需要正文时调用
+///
+/// 加载函数的参数为文章ID、正文在内容文件中的起始字节和字节长度，返回正文字符串；
+/// 正文尚未下载时返回 `undefined`，该文章的结果使用存储的摘要。JS端可以在返回
+/// `undefined` 后用HTTP Range请求下载正文，通过 `provide_content` 写入后重新搜索。
+/// 传入空值时移除加载函数。设置或移除加载函数都会清空已加载的正文。
+#[wasm_bindgen]
+pub fn set_content_loader(loader: Option<js_sys::Function>) {
+    content::set_loader(loader.map(|loader| {
+        Box::new(move |id: &str, range: content::ContentRange| {
+            loader.call3(
+                &JsValue::NULL,
+                &JsValue::from_str(id),
+                &JsValue::from_f64(range.offset as f64),
+                &JsValue::from_f64(range.len as f64),
+            )
+            .ok()
+            .and_then(|value| value.as_string())
+        }) as content::ContentLoader
+    }));
+}
+
+/// 写入文章正文，供正文拆分的索引生成摘要和标题树
+#[wasm_bindgen]
+pub fn provide_content(article_id: &str, content: String) {
+    content::provide(article_id, content);
+}
+
+/// 开启或关闭搜索计时（默认开启）
+///
+/// 关闭后搜索结果的 `diagnostics` 为空，相同的请求总是得到相同的结果，便于快照测试。
+#[wasm_bindgen]
+pub fn set_timing(enabled: bool) {
+    runtime::set_timing_enabled(enabled);
+}
+
+/// 读取索引文件头部的索引元数据（文章数、标签数、构建时间等），不解压索引
+///
+/// 返回JSON字符串，没有头部的旧索引返回 `null`。
+#[wasm_bindgen]
+pub fn get_index_metadata(index_data: &[u8]) -> Result<String, JsValue> {
+    utils_common::compression::read_index_metadata(index_data, engine::MAX_INDEX_VERSION)
+        .map_err(|e| format!("读取索引元数据失败: {}", e))
+        .and_then(|metadata| serde_json::to_string(&metadata).map_err(|e| format!("序列化索引元数据失败: {}", e)))
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// 解压索引并设置最高可见级别
+fn decode_visible_index(index_data: &[u8], max_visibility: Option<&str>) -> Result<ArticleSearchIndex, String> {
+    let max_visibility = max_visibility.map_or(Ok(Visibility::Public), str::parse)?;
+    let mut index = engine::decode_index(index_data)?;
+    index.max_visibility = max_visibility;
+    Ok(index)
+}
+
+/// 获取文章的相关文章 - 返回构建索引时预先计算的相关文章（JSON数组），按相似度降序
+///
+/// 在所有已加载的索引中查找该文章，找不到时返回空数组。
+#[wasm_bindgen]
+pub fn get_related(article_id: &str) -> Result<String, JsValue> {
+    let items = engine::with_engine(|engine| {
+        engine.scoped(&[]).map(|sources| {
+            sources.iter()
+                .find_map(|source| related::related_items(source.index, article_id))
+                .unwrap_or_default()
+        })
+    })
+    .and_then(|items| items)
+    .map_err(|e| JsValue::from_str(&e))?;
+    
+    serde_json::to_string(&items)
+        .map_err(|e| JsValue::from_str(&format!("序列化相关文章失败: {}", e)))
+}
+
+/// 获取词条统计 - 返回词条在各个已加载索引的标题、章节标题、正文和媒体说明中的文档频率，
+/// 以及是否为停用词或常用词条（JSON格式的 `TermStats`），供调试搜索相关性使用
+#[wasm_bindgen]
+pub fn get_term_stats(term: &str) -> Result<String, JsValue> {
+    let indexes = engine::with_engine(|engine| {
+        engine.scoped(&[]).map(|sources| {
+            sources.iter()
+                .map(|source| index_term_stats(source.name, source.index, term))
+                .collect::<Vec<_>>()
+        })
+    })
+    .and_then(|indexes| indexes)
+    .map_err(|e| JsValue::from_str(&e))?;
+
+    let stats = TermStats { term: term.to_string(), indexes };
+    serde_json::to_string(&stats)
+        .map_err(|e| JsValue::from_str(&format!("序列化词条统计失败: {}", e)))
+}
+
+/// 统计词条在一个索引中的文档频率，查询按各字段的分析器规范化
+fn index_term_stats(name: &str, index: &ArticleSearchIndex, term: &str) -> IndexTermStats {
+    let config = &index.config;
+    let field_query = |analyzer_id: &str| analyzer::query_term(analyzer_id, config, term);
+    let count = |postings: Option<dictionary::Postings>| {
+        postings.map_or(0, |postings| postings.filter(|article_id| index.is_live(*article_id)).count())
+    };
+
+    let title = count(field_query(&config.analyzers.title).and_then(|term| index.title_term_index.get(&term)));
+    let content = count(field_query(&config.analyzers.content).and_then(|term| index.content_term_index.get(&term)));
+    let media = count(field_query(&config.analyzers.media).and_then(|term| index.media_term_index.get(&term)));
+    // 同一篇文章的多个章节标题只计一次
+    let heading = field_query(&config.analyzers.heading)
+        .and_then(|term| index.heading_term_index.get(&term))
+        .map_or(0, |heading_ids| {
+            heading_ids.iter()
+                .filter_map(|heading_id| index.heading_ordinal(heading_id))
+                .filter(|article_id| index.is_live(*article_id))
+                .collect::<HashSet<_>>()
+                .len()
+        });
+
+    // 分析器不接受停用词和过短的词条，按原样的小写形式判断
+    let normalized = term.trim().to_lowercase();
+    IndexTermStats {
+        index: name.to_string(),
+        articles: index.live_articles().count(),
+        title,
+        heading,
+        content,
+        media,
+        stop_word: config.stop_words.contains(&normalized),
+        too_short: normalized.len() < config.min_term_length,
+        common_term: index.common_terms.get(&normalized).cloned(),
+    }
+}
+
+/// 获取搜索建议 - 只返回建议列表，供输入框自动补全使用
+#[wasm_bindgen]
+pub fn get_suggestions(query: &str, limit: usize) -> Result<String, JsValue> {
+    suggest(query, limit).map_err(|e| JsValue::from_str(&e))
+}
+
+/// 在已加载的索引中获取搜索建议并返回JSON格式的结果
+fn suggest(query: &str, limit: usize) -> Result<String, String> {
+    let suggestions = engine::with_engine(|engine| {
+        engine.scoped(&[]).map(|sources| get_merged_suggestions(&sources, query, 0, limit))
+    })??;
+    
+    serde_json::to_string(&suggestions)
+        .map_err(|e| format!("序列化搜索建议失败: {}", e))
+}
+
+/// 每批返回的搜索建议数量
+const SUGGESTION_BATCH_SIZE: usize = 10;
+
+/// 获取下一批搜索建议 - 从 `offset` 开始返回一批建议，供建议下拉框的"更多"按钮使用
+#[wasm_bindgen]
+pub fn get_more_suggestions(query: &str, offset: usize) -> Result<String, JsValue> {
+    suggest_batch(query, offset).map_err(|e| JsValue::from_str(&e))
+}
+
+/// 在已加载的索引中获取一批搜索建议并返回JSON格式的结果
+fn suggest_batch(query: &str, offset: usize) -> Result<String, String> {
+    // 多取一条用于判断是否还有更多建议
+    let mut suggestions = engine::with_engine(|engine| {
+        engine.scoped(&[]).map(|sources| {
+            get_merged_suggestions(&sources, query, offset, SUGGESTION_BATCH_SIZE + 1)
+        })
+    })??;
+    
+    let has_more = suggestions.len() > SUGGESTION_BATCH_SIZE;
+    suggestions.truncate(SUGGESTION_BATCH_SIZE);
+    
+    let page = SuggestionPage {
+        suggestions,
+        next_offset: has_more.then_some(offset + SUGGESTION_BATCH_SIZE),
+    };
+    
+    serde_json::to_string(&page)
+        .map_err(|e| format!("序列化搜索建议失败: {}", e))
+}
+
+/// 生成查询处理信息，宽松匹配的改写在匹配过程中补充
+fn query_debug(sources: &[SearchSource], req: &SearchRequest) -> QueryDebug {
+    let scope = req.query_scope();
+    let normalized = scope.text.trim().to_lowercase();
+    let tokens = split_query_to_terms(&scope.text);
+    
+    let mut rewrites = Vec::new();
+    if normalized != req.query {
+        rewrites.push(QueryRewrite {
+            kind: RewriteKind::Normalize,
+            from: req.query.clone(),
+            to: vec![normalized.clone()],
+        });
+    }
+    if tokens.len() > 1 {
+        rewrites.push(QueryRewrite {
+            kind: RewriteKind::Split,
+            from: normalized.clone(),
+            to: tokens[1..].to_vec(),
+        });
+    }
+    
+    let mut filters = Vec::new();
+    let mut add_filter = |field: &str, values: Vec<String>| {
+        if !values.is_empty() {
+            filters.push(AppliedFilter { field: field.to_string(), values });
+        }
+    };
+    if !req.scope.is_empty() {
+        add_filter("scope", sources.iter().map(|source| source.name.to_string()).collect());
+    }
+    let mut visibility: Vec<String> = sources.iter()
+        .map(|source| source.index.max_visibility)
+        .filter(|visibility| *visibility != Visibility::Public)
+        .map(|visibility| visibility.as_str().to_string())
+        .collect();
+    visibility.dedup();
+    add_filter("visibility", visibility);
+    let mut boosts: Vec<String> = req.page_type_boosts.iter()
+        .map(|(page_type, boost)| format!("{}={}", page_type, boost))
+        .collect();
+    boosts.sort();
+    add_filter("page_type_boost", boosts);
+    if sources.iter().any(|source| source.candidates.is_some()) {
+        add_filter("session", req.session_id.iter().cloned().collect());
+    }
+    add_filter("tag", scope.tags);
+    add_filter("category", scope.categories);
+    
+    QueryDebug {
+        original: req.query.clone(),
+        normalized,
+        tokens,
+        rewrites,
+        filters,
     }
 }
 
@@ -65,31 +515,62 @@ fn split_query_to_terms(query: &str) -> Vec<String> {
         terms.push(clean_query.clone());
     }
     
-    // 不再分割查询为多个词条，简化搜索逻辑
-    // 如果需要，可以在这里添加分词逻辑
+    // 多个词组成的查询，追加每个独立的词条
+    for word in clean_query.split_whitespace() {
+        if !terms.iter().any(|term| term == word) {
+            terms.push(word.to_string());
+        }
+    }
     
     terms
 }
 
-/// 获取搜索建议
-fn get_search_suggestions(search_index: &ArticleSearchIndex, query: &str) -> Vec<SearchSuggestion> {
+/// 合并多个索引的搜索建议
+///
+/// 按各索引建议的排名轮流取出，去除重复的建议文本，再跳过前 `offset` 条。
+fn get_merged_suggestions(sources: &[SearchSource], query: &str, offset: usize, limit: usize) -> Vec<SearchSuggestion> {
+    if let [source] = sources {
+        return get_search_suggestions(source.index, query, offset, limit);
+    }
+    
+    // 合并结果的前 offset + limit 条只来自各索引的前 offset + limit 条
+    let per_source: Vec<Vec<SearchSuggestion>> = sources.iter()
+        .map(|source| get_search_suggestions(source.index, query, 0, offset + limit))
+        .collect();
+    let max_len = per_source.iter().map(|list| list.len()).max().unwrap_or(0);
+    
+    let mut merged: Vec<SearchSuggestion> = Vec::new();
+    for rank in 0..max_len {
+        for list in &per_source {
+            if let Some(suggestion) = list.get(rank) {
+                if !merged.iter().any(|existing| existing.text == suggestion.text) {
+                    merged.push(suggestion.clone());
+                }
+            }
+        }
+    }
+    
+    merged.into_iter().skip(offset).take(limit).collect()
+}
+
+/// 获取搜索建议 - 跳过前 `offset` 条，最多返回 `limit` 条
+fn get_search_suggestions(search_index: &ArticleSearchIndex, query: &str, offset: usize, limit: usize) -> Vec<SearchSuggestion> {
     let query = query.trim().to_lowercase();
     
     // 如果查询为空，返回热门词汇
     if query.is_empty() {
-        let mut common_terms: Vec<(String, usize)> = search_index.common_terms
-            .iter()
-            .map(|(term, freq)| (term.clone(), *freq))
-            .collect();
+        let mut common_terms: Vec<(&String, &CommonTerm)> = search_index.common_terms.iter().collect();
         
-        common_terms.sort_by(|a, b| b.1.cmp(&a.1)); // 按频率降序排序
+        // 按频率降序排序，频率相同时按词汇排序，保证分批获取时顺序稳定
+        common_terms.sort_by(|a, b| b.1.frequency.cmp(&a.1.frequency).then_with(|| a.0.cmp(b.0)));
         
-        return common_terms.iter().take(10).map(|(term, _)| {
+        return common_terms.iter().skip(offset).take(limit).map(|(_, common_term)| {
             SearchSuggestion {
-                text: term.clone(),
+                text: common_term.display.clone(),
                 suggestion_type: SuggestionType::Completion,
                 matched_text: String::new(),
-                suggestion_text: term.clone(),
+                suggestion_text: common_term.display.clone(),
+                field: common_term.field,
             }
         }).collect();
     }
@@ -98,7 +579,7 @@ fn get_search_suggestions(search_index: &ArticleSearchIndex, query: &str) -> Vec
     let mut candidates: Vec<SuggestionCandidate> = Vec::new();
     
     // 第1步: 标题完全匹配
-    for (_, article) in search_index.articles.iter().enumerate() {
+    for (_, article) in search_index.live_articles() {
         let title_lower = article.title.to_lowercase();
         
         if title_lower == query {
@@ -110,7 +591,8 @@ fn get_search_suggestions(search_index: &ArticleSearchIndex, query: &str) -> Vec
                 text: article.title.clone(),
                 score: 100,
                 suggestion_type: SuggestionType::Completion,
-                frequency: 100
+                frequency: 100,
+                field: TermField::Title,
             });
         } else if title_lower.contains(&query) {
             // 标题包含查询，作为纠正建议
@@ -118,13 +600,14 @@ fn get_search_suggestions(search_index: &ArticleSearchIndex, query: &str) -> Vec
                 text: article.title.clone(),
                 score: 90,
                 suggestion_type: SuggestionType::Correction,
-                frequency: 90
+                frequency: 90,
+                field: TermField::Title,
             });
         }
     }
     
     // 第2步: 独立词汇匹配
-    for (term, freq) in &search_index.common_terms {
+    for (term, common_term) in &search_index.common_terms {
         let term_lower = term.to_lowercase();
         
         // 跳过与查询完全相同的词汇
@@ -135,25 +618,48 @@ fn get_search_suggestions(search_index: &ArticleSearchIndex, query: &str) -> Vec
         if term_lower.starts_with(&query) {
             // 前缀匹配，作为补全建议
             candidates.push(SuggestionCandidate {
-                text: term.clone(),
+                text: common_term.display.clone(),
                 score: 95,
                 suggestion_type: SuggestionType::Completion,
-                frequency: *freq
+                frequency: common_term.frequency,
+                field: common_term.field,
             });
         } else if term_lower.contains(&query) {
             // 包含关系，作为纠正建议
             candidates.push(SuggestionCandidate {
-                text: term.clone(),
+                text: common_term.display.clone(),
                 score: 85,
                 suggestion_type: SuggestionType::Correction,
-                frequency: *freq
+                frequency: common_term.frequency,
+                field: common_term.field,
             });
         }
     }
     
+    // 标题词典前缀补全 - 不在常用词汇中的标题词汇也能作为补全建议，
+    // 出现在大多数文章标题中的词汇按文档频率降权
+    let live_count = search_index.live_articles().count();
+    for (term, article_ids) in search_index.title_term_index.prefix(&query) {
+        let documents = article_ids.filter(|&article_id| search_index.is_live(article_id)).count();
+        if documents == 0 || term == query || candidates.iter().any(|s| s.text.to_lowercase() == term) {
+            continue;
+        }
+        
+        let weight = builder::document_frequency_weight(documents, live_count, search_index.config.common_term_max_df);
+        // 常用词汇中记录了展示形式时按展示形式显示
+        let text = search_index.common_terms.get(&term).map_or(term, |common_term| common_term.display.clone());
+        candidates.push(SuggestionCandidate {
+            text,
+            score: 90,
+            suggestion_type: SuggestionType::Completion,
+            frequency: (documents as f64 * weight).round() as usize,
+            field: TermField::Title,
+        });
+    }
+    
     // 第3步: 编辑距离匹配
     if candidates.len() < 5 {
-        for (term, freq) in &search_index.common_terms {
+        for (term, common_term) in &search_index.common_terms {
             let term_lower = term.to_lowercase();
             
             // 跳过已添加的词汇和完全相同的词汇
@@ -171,26 +677,28 @@ fn get_search_suggestions(search_index: &ArticleSearchIndex, query: &str) -> Vec
                 let edit_score = 80 - distance * 5;
                 
                 candidates.push(SuggestionCandidate {
-                    text: term.clone(),
+                    text: common_term.display.clone(),
                     score: edit_score,
                     suggestion_type: SuggestionType::Correction,
-                    frequency: *freq
+                    frequency: common_term.frequency,
+                    field: common_term.field,
                 });
             }
         }
     }
     
-    // 首先按分数和频率排序
+    // 首先按分数和频率排序，最后按文本排序，保证分批获取时顺序稳定
     candidates.sort_by(|a, b| {
         match b.score.cmp(&a.score) {
-            std::cmp::Ordering::Equal => b.frequency.cmp(&a.frequency),
+            std::cmp::Ordering::Equal => b.frequency.cmp(&a.frequency).then_with(|| a.text.cmp(&b.text)),
             other => other
         }
     });
     
-    // 转换为SearchSuggestion格式并截取前10个结果
+    // 转换为SearchSuggestion格式并截取指定范围的结果
     candidates.iter()
-        .take(10)
+        .skip(offset)
+        .take(limit)
         .map(|candidate| {
             let text_lower = candidate.text.to_lowercase();
             
@@ -212,6 +720,7 @@ fn get_search_suggestions(search_index: &ArticleSearchIndex, query: &str) -> Vec
                 suggestion_type: candidate.suggestion_type.clone(),
                 matched_text,
                 suggestion_text,
+                field: candidate.field,
             }
         })
         .collect()
@@ -233,11 +742,11 @@ fn levenshtein_distance(s1: &str, s2: &str) -> i32 {
     let mut matrix = vec![vec![0; n + 1]; m + 1];
     
     // 初始化第一行和第一列
-    for i in 0..=m {
-        matrix[i][0] = i as i32;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i as i32;
     }
-    for j in 0..=n {
-        matrix[0][j] = j as i32;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j as i32;
     }
     
     // 填充剩余的矩阵
@@ -260,7 +769,7 @@ fn levenshtein_distance(s1: &str, s2: &str) -> i32 {
 }
 
 /// 执行自动补全
-fn perform_autocomplete(search_index: &ArticleSearchIndex, req: &SearchRequest) -> SearchResult {
+fn perform_autocomplete(sources: &[SearchSource], req: &SearchRequest) -> SearchResult {
     let query = req.query.to_lowercase();
     
     // 如果查询为空，返回空结果
@@ -271,14 +780,18 @@ fn perform_autocomplete(search_index: &ArticleSearchIndex, req: &SearchRequest)
             page: 1,
             page_size: 10,
             total_pages: 0,
-            time_ms: 0,
             query: query.clone(),
             suggestions: Vec::new(),
+            cancelled: false,
+            query_debug: query_debug(sources, req),
+            hints: None,
+            diagnostics: None,
+            clusters: None,
         };
     }
     
     // 使用与普通搜索相同的建议生成逻辑
-    let suggestions = get_search_suggestions(search_index, &query);
+    let suggestions = get_merged_suggestions(sources, &query, 0, SUGGESTION_BATCH_SIZE);
     
     SearchResult {
         items: Vec::new(), // 自动补全不需要返回结果项
@@ -286,15 +799,23 @@ fn perform_autocomplete(search_index: &ArticleSearchIndex, req: &SearchRequest)
         page: 1,
         page_size: suggestions.len(),
         total_pages: 1,
-        time_ms: 0, // 由外部函数填充
         query: query.clone(),
         suggestions,
+        cancelled: false,
+        query_debug: query_debug(sources, req),
+        hints: None,
+        diagnostics: None,
+        clusters: None,
     }
 }
 
 /// 执行搜索
-fn perform_search(search_index: &ArticleSearchIndex, req: &SearchRequest) -> SearchResult {
-    let query = req.query.to_lowercase();
+///
+/// 查询中的 `tag:`、`category:` 限定和请求中的标签、分类筛选通过标签和分类索引
+/// 缩小参与匹配的文章，其余文本作为查询。
+fn perform_search(sources: &[SearchSource], req: &SearchRequest, token: &CancelToken) -> SearchResult {
+    let scope = req.query_scope();
+    let query = scope.text.to_lowercase();
     
     // 如果查询为空，返回空结果
     if query.is_empty() {
@@ -304,9 +825,13 @@ fn perform_search(search_index: &ArticleSearchIndex, req: &SearchRequest) -> Sea
             page: req.page,
             page_size: req.page_size,
             total_pages: 0,
-            time_ms: 0,
             query: query.clone(),
             suggestions: Vec::new(),
+            cancelled: false,
+            query_debug: query_debug(sources, req),
+            hints: None,
+            diagnostics: None,
+            clusters: None,
         };
     }
     
@@ -319,52 +844,57 @@ fn perform_search(search_index: &ArticleSearchIndex, req: &SearchRequest) -> Sea
             page: req.page,
             page_size: req.page_size,
             total_pages: 0,
-            time_ms: 0,
             query: query.clone(),
             suggestions: Vec::new(),
+            cancelled: false,
+            query_debug: query_debug(sources, req),
+            hints: None,
+            diagnostics: None,
+            clusters: None,
         };
     }
     
-    // 找到匹配的文章ID及其得分 - 已按匹配优先级排序
-    let matched_articles = find_matched_articles(search_index, &terms);
-    
-    // 处理每个匹配的文章
+    // 在每个索引中查找匹配的文章
     let mut all_items = Vec::new();
-    
-    for (article_id, base_score) in matched_articles {
-        if article_id >= search_index.articles.len() {
-            continue;
-        }
-        
-        let article = &search_index.articles[article_id];
-        
-        // 构建标题树和匹配内容
-        let heading_tree = build_heading_tree_with_matches(article, &terms, search_index);
-        
-        // 高亮处理文章标题
-        let highlighted_title = if !terms.is_empty() {
-            highlight_title(&article.title, &terms[0])
-        } else {
-            article.title.clone()
+    let mut debug = query_debug(sources, req);
+    
+    for source in sources {
+        let search_index = source.index;
+        // 标签和分类筛选与会话的候选文章取交集
+        let scoped = scope.articles(search_index).map(|articles| match source.candidates {
+            Some(candidates) => &articles & candidates,
+            None => articles,
+        });
+        let options = MatchOptions {
+            page_type_boosts: &req.page_type_boosts,
+            candidates: scoped.as_ref().or(source.candidates),
+            token,
         };
         
-        // 创建搜索结果项
-        let result_item = SearchResultItem {
-            id: article.id.clone(),
-            title: highlighted_title,
-            summary: article.summary.clone(),
-            url: article.url.clone(),
-            score: base_score,
-            heading_tree,
-            page_type: article.page_type.clone(),
-        };
+        // 找到匹配的文章ID及其得分 - 已按匹配优先级排序
+        let matched_articles = find_matched_articles(search_index, &terms, &options, &mut debug);
+        if token.is_cancelled() {
+            return cancelled_result(sources, req);
+        }
         
-        all_items.push(result_item);
+        // 处理每个匹配的文章
+        match build_result_items(search_index, &matched_articles, &terms, req, source.name, token) {
+            Some(items) => all_items.extend(items),
+            None => return cancelled_result(sources, req),
+        }
     }
     
+    // 合并多个索引的结果后按分数统一排序，同分时保持索引内原有的顺序
+    if sources.len() > 1 {
+        all_items.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    }
+    
+    // 在分页之前按顶级章节对全部结果分组
+    let clusters = req.cluster_results.then(|| clusters::cluster_results(&all_items));
+    
     // 分页处理
     let total = all_items.len();
-    let total_pages = (total + req.page_size - 1) / req.page_size;
+    let total_pages = total.div_ceil(req.page_size);
     let start_idx = (req.page - 1) * req.page_size;
     let end_idx = std::cmp::min(start_idx + req.page_size, total);
     
@@ -375,7 +905,10 @@ fn perform_search(search_index: &ArticleSearchIndex, req: &SearchRequest) -> Sea
     };
     
     // 生成搜索建议
-    let suggestions = get_search_suggestions(search_index, &query);
+    let suggestions = get_merged_suggestions(sources, &query, 0, SUGGESTION_BATCH_SIZE);
+    
+    // 没有结果时从索引中找出确定有结果的相近词汇
+    let hints = (total == 0).then(|| hints::no_results_hints(sources, &terms, &req.page_type_boosts, token));
     
     SearchResult {
         items: paged_results,
@@ -383,92 +916,378 @@ fn perform_search(search_index: &ArticleSearchIndex, req: &SearchRequest) -> Sea
         page: req.page,
         page_size: req.page_size,
         total_pages,
-        time_ms: 0, // 由外部函数填充
         query: query.clone(),
         suggestions,
+        cancelled: false,
+        query_debug: debug,
+        hints,
+        diagnostics: None,
+        clusters,
     }
 }
 
-/// 高亮处理标题文本
-fn highlight_title(title: &str, query: &str) -> String {
-    if title.is_empty() || query.is_empty() {
-        return title.to_string();
+/// 并行构建结果项的最少文章数，结果较少时线程调度的开销大于收益
+#[cfg(feature = "parallel")]
+const PARALLEL_RESULT_THRESHOLD: usize = 32;
+
+/// 依次为匹配的文章构建结果项，顺序与匹配顺序相同，搜索已取消时返回None
+fn build_result_items_sequential(
+    search_index: &ArticleSearchIndex,
+    matched_articles: &[(usize, f64)],
+    terms: &[String],
+    req: &SearchRequest,
+    source: &str,
+    token: &CancelToken,
+) -> Option<Vec<SearchResultItem>> {
+    let mut items = Vec::with_capacity(matched_articles.len());
+    for &(article_id, base_score) in matched_articles {
+        // 构建标题树之前检查是否已取消
+        if token.is_cancelled() {
+            return None;
+        }
+        
+        if let Some(article) = search_index.articles.get(article_id) {
+            items.push(build_result_item(search_index, article_id, article, base_score, terms, req, source));
+        }
     }
+    Some(items)
+}
 
-    let title_lower = title.to_lowercase();
-    let query_lower = query.to_lowercase();
-    
-    // 查找所有匹配位置
-    let mut term_positions = Vec::new();
-    let mut start_idx = 0;
+/// 为匹配的文章构建结果项，顺序与匹配顺序相同，搜索已取消时返回None
+#[cfg(not(feature = "parallel"))]
+fn build_result_items(
+    search_index: &ArticleSearchIndex,
+    matched_articles: &[(usize, f64)],
+    terms: &[String],
+    req: &SearchRequest,
+    source: &str,
+    token: &CancelToken,
+) -> Option<Vec<SearchResultItem>> {
+    build_result_items_sequential(search_index, matched_articles, terms, req, source, token)
+}
+
+/// 为匹配的文章构建结果项，顺序与匹配顺序相同，搜索已取消时返回None
+///
+/// 摘要和标题树按文章并行构建。精简模式没有需要并行的工作；正文拆分到内容文件时，
+/// 加载函数和已加载的正文只在当前线程可用，这两种情况以及结果较少时依次构建。
+#[cfg(feature = "parallel")]
+fn build_result_items(
+    search_index: &ArticleSearchIndex,
+    matched_articles: &[(usize, f64)],
+    terms: &[String],
+    req: &SearchRequest,
+    source: &str,
+    token: &CancelToken,
+) -> Option<Vec<SearchResultItem>> {
+    use rayon::prelude::*;
+    
+    let has_external_content = search_index.content_ranges.iter().any(|range| range.len > 0);
+    if matched_articles.len() < PARALLEL_RESULT_THRESHOLD
+        || req.result_detail == ResultDetail::Minimal
+        || has_external_content
+    {
+        return build_result_items_sequential(search_index, matched_articles, terms, req, source, token);
+    }
     
-    while start_idx < title_lower.len() {
-        if let Some(found_idx) = title_lower[start_idx..].find(&query_lower) {
-            let abs_idx = start_idx + found_idx;
-            let match_end = abs_idx + query_lower.len();
-            
-            // 确保索引位于字符边界上
-            let valid_abs_idx = find_char_boundary(title, abs_idx);
-            let valid_match_end = find_char_boundary(title, match_end);
-            
-            // 添加匹配位置
-            if valid_match_end > valid_abs_idx {
-                term_positions.push((valid_abs_idx, valid_match_end));
+    // 任一文章构建前发现已取消时整体返回None，未取消的文章缺失时跳过
+    let items: Option<Vec<Option<SearchResultItem>>> = matched_articles.par_iter()
+        .map(|&(article_id, base_score)| {
+            if token.is_cancelled() {
+                return None;
             }
-            
-            start_idx = if valid_match_end > start_idx { valid_match_end } else { start_idx + 1 };
-        } else {
-            break;
-        }
+            Some(search_index.articles.get(article_id)
+                .map(|article| build_result_item(search_index, article_id, article, base_score, terms, req, source)))
+        })
+        .collect();
+    items.map(|items| items.into_iter().flatten().collect())
+}
+
+/// 相关度换算的分数尺度 - 原始分数等于该值时相关度约为63
+const RELEVANCE_SCALE: f64 = 40.0;
+
+/// 把原始分数换算为0到100的相关度：`100 × (1 − e^(−分数 / 40))`
+///
+/// 换算单调且饱和，分数越高越接近100但不会超过：标题以查询开头（115）约为94，
+/// 标题包含查询（90）约为89，正文匹配（75）约为85，宽松匹配（50）约为71。
+/// 原始分数为0、负数或非有限值时相关度为0。
+fn normalize_score(score: f64) -> f64 {
+    if !score.is_finite() || score <= 0.0 {
+        return 0.0;
     }
-    
-    // 如果没有找到匹配，返回原始标题
-    if term_positions.is_empty() {
-        return title.to_string();
+    100.0 * (1.0 - (-score / RELEVANCE_SCALE).exp())
+}
+
+/// 为匹配的文章构建搜索结果项
+fn build_result_item(
+    search_index: &ArticleSearchIndex,
+    article_id: usize,
+    article: &utils_common::models::ArticleMetadata,
+    base_score: f64,
+    terms: &[String],
+    req: &SearchRequest,
+    source: &str,
+) -> SearchResultItem {
+    // 精简模式跳过摘要和标题树的构建
+    if req.result_detail == ResultDetail::Minimal {
+        return SearchResultItem {
+            id: article.id.clone(),
+            title: highlight_title(&article.title, terms),
+            summary: String::new(),
+            summary_matches: Vec::new(),
+            url: article.url.clone(),
+            score: base_score,
+            heading_tree: None,
+            page_type: String::new(),
+            lang: String::new(),
+            source: source.to_string(),
+            matched_terms: Vec::new(),
+            relevance: normalize_score(base_score),
+        };
     }
     
-    // 按位置排序
-    term_positions.sort_by_key(|&(start, _)| start);
+    // 正文拆分到内容文件且无法加载时，不构建标题树，摘要使用存储的摘要
+    let article = content::with_content(search_index, article_id)
+        .unwrap_or(std::borrow::Cow::Borrowed(article));
+    let article = article.as_ref();
+    
+    // 构建标题树和匹配内容
+    let heading_tree = build_heading_tree_with_matches(article, terms, search_index);
+    let matched_terms = heading_tree.as_ref().map(collect_matched_terms).unwrap_or_default();
+    
+    // 高亮处理文章标题
+    let highlighted_title = highlight_title(&article.title, terms);
     
-    // 构建高亮标题
+    // 选择摘要文本
+    let summary = match req.summary_mode {
+        SummaryMode::Snippet => best_snippet(&article.content, terms)
+            .unwrap_or_else(|| article.summary.clone()),
+        SummaryMode::Stored => article.summary.clone(),
+    };
+    
+    // 高亮处理文章摘要，同时记录匹配区间
+    let (highlighted_summary, summary_spans) = highlight_text(&summary, terms);
+    let summary_matches = summary_spans.into_iter()
+        .map(|(start, end)| MatchSpan {
+            start: utf16_offset(&summary, start),
+            end: utf16_offset(&summary, end),
+            text: summary[start..end].to_string(),
+        })
+        .collect();
+    
+    SearchResultItem {
+        id: article.id.clone(),
+        title: highlighted_title,
+        summary: highlighted_summary,
+        summary_matches,
+        url: article.url.clone(),
+        score: base_score,
+        heading_tree,
+        page_type: article.page_type.clone(),
+        lang: article.lang.clone(),
+        source: source.to_string(),
+        matched_terms,
+        relevance: normalize_score(base_score),
+    }
+}
+
+/// 汇总标题树中所有节点匹配的关键词，按匹配的节点数降序，数量相同时按关键词排序
+fn collect_matched_terms(tree: &HeadingNode) -> Vec<String> {
+    fn count<'a>(node: &'a HeadingNode, counts: &mut HashMap<&'a str, usize>) {
+        for term in node.matched_terms.iter().flatten() {
+            *counts.entry(term.as_str()).or_insert(0) += 1;
+        }
+        for child in &node.children {
+            count(child, counts);
+        }
+    }
+
+    let mut counts = HashMap::new();
+    count(tree, &mut counts);
+    let mut terms: Vec<(&str, usize)> = counts.into_iter().collect();
+    terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    terms.into_iter().map(|(term, _)| term.to_string()).collect()
+}
+
+/// 高亮处理标题文本
+fn highlight_title(title: &str, terms: &[String]) -> String {
+    highlight_text(title, terms).0
+}
+
+/// 高亮文本中出现的所有查询词，返回高亮后的文本和匹配区间（字节偏移）
+///
+/// 每个查询词的所有出现位置都会被标记，相互重叠或相邻的区间合并为一个高亮块。
+fn highlight_text(text: &str, terms: &[String]) -> (String, Vec<(usize, usize)>) {
+    let spans = find_term_spans(text, terms);
+    
+    // 如果没有找到匹配，返回原始文本
+    if spans.is_empty() {
+        return (text.to_string(), spans);
+    }
+    
+    // 构建高亮文本
     let mut highlighted = String::new();
     let mut last_pos = 0;
     
-    for (start, end) in term_positions {
+    for &(start, end) in &spans {
         // 添加匹配前的文本
         if start > last_pos {
-            highlighted.push_str(&title[last_pos..start]);
+            highlighted.push_str(&text[last_pos..start]);
         }
         
         // 添加高亮标记
         highlighted.push_str("<mark>");
-        highlighted.push_str(&title[start..end]);
+        highlighted.push_str(&text[start..end]);
         highlighted.push_str("</mark>");
         
         last_pos = end;
     }
     
     // 添加最后一部分
-    if last_pos < title.len() {
-        highlighted.push_str(&title[last_pos..]);
+    if last_pos < text.len() {
+        highlighted.push_str(&text[last_pos..]);
+    }
+    
+    (highlighted, spans)
+}
+
+/// 查找所有查询词在文本中的位置（忽略大小写），返回按位置排序并合并后的字节区间
+fn find_term_spans(text: &str, terms: &[String]) -> Vec<(usize, usize)> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    
+    // 逐字符转为小写，并记录小写文本每个字节对应的原文字节偏移，
+    // 避免大小写转换改变字节长度时高亮位置错位
+    let mut lower = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    for (pos, c) in text.char_indices() {
+        for lc in c.to_lowercase() {
+            lower.push(lc);
+            offsets.extend(std::iter::repeat_n(pos, lc.len_utf8()));
+        }
+    }
+    offsets.push(text.len());
+    
+    // 收集每个查询词的所有匹配位置
+    let mut spans = Vec::new();
+    for term in terms {
+        let term_lower = term.to_lowercase();
+        if term_lower.is_empty() {
+            continue;
+        }
+        
+        for (found_idx, matched) in lower.match_indices(&term_lower) {
+            let start = offsets[found_idx];
+            let end = offsets[found_idx + matched.len()];
+            if end > start {
+                spans.push((start, end));
+            }
+        }
+    }
+    
+    // 按位置排序并合并重叠或相邻的区间
+    spans.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    
+    merged
+}
+
+/// 摘要片段的长度（字符数）
+const SNIPPET_CHARS: usize = 120;
+/// 片段中第一个匹配之前保留的上下文长度（字符数）
+const SNIPPET_CONTEXT_CHARS: usize = 30;
+
+/// 从正文中选出匹配最集中的片段作为摘要，正文中没有匹配时返回None
+///
+/// 以每个匹配位置为起点的窗口中，命中文本总长度最大的窗口胜出（同分取靠前的），
+/// 片段前后被截断时添加省略号。
+fn best_snippet(content: &str, terms: &[String]) -> Option<String> {
+    let spans = find_term_spans(content, terms);
+    if spans.is_empty() {
+        return None;
+    }
+    
+    let mut best_anchor = spans[0].0;
+    let mut best_score = 0;
+    for (i, &(anchor, _)) in spans.iter().enumerate() {
+        let window_end = advance_chars(content, anchor, SNIPPET_CHARS - SNIPPET_CONTEXT_CHARS);
+        let score: usize = spans[i..].iter()
+            .take_while(|&&(_, end)| end <= window_end)
+            .map(|&(start, end)| end - start)
+            .sum();
+        if score > best_score {
+            best_score = score;
+            best_anchor = anchor;
+        }
     }
     
-    highlighted
+    // 在第一个匹配前保留少量上下文
+    let start = content[..best_anchor].char_indices()
+        .rev()
+        .take(SNIPPET_CONTEXT_CHARS)
+        .last()
+        .map(|(pos, _)| pos)
+        .unwrap_or(best_anchor);
+    let end = advance_chars(content, start, SNIPPET_CHARS);
+    
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str("...");
+    }
+    snippet.push_str(content[start..end].trim());
+    if end < content.len() {
+        snippet.push_str("...");
+    }
+    
+    Some(snippet)
+}
+
+/// 从字节偏移 `from` 向后移动 `count` 个字符，返回新的字节偏移
+fn advance_chars(text: &str, from: usize, count: usize) -> usize {
+    text[from..].char_indices()
+        .nth(count)
+        .map(|(pos, _)| from + pos)
+        .unwrap_or(text.len())
+}
+
+/// 将字节偏移转换为UTF-16偏移，与JavaScript字符串下标一致
+fn utf16_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].encode_utf16().count()
 }
 
 /// 查找匹配的文章ID并按优先级排序
-fn find_matched_articles(search_index: &ArticleSearchIndex, terms: &[String]) -> Vec<(usize, f64)> {
+///
+/// 遍历文章的循环中会检查取消令牌，搜索被取消时返回空列表。
+fn find_matched_articles(search_index: &ArticleSearchIndex, terms: &[String], options: &MatchOptions, debug: &mut QueryDebug) -> Vec<(usize, f64)> {
     // 确保有搜索词
     if terms.is_empty() {
         return Vec::new();
     }
     
+    let token = options.token;
     let query = &terms[0].to_lowercase();
     let mut result_with_scores: Vec<(usize, f64)> = Vec::new();
+    
+    // 不在候选文章中的文章视为已处理
     let mut seen_articles = HashSet::new();
+    let articles = candidate_articles(search_index, options.candidates);
+    if options.candidates.is_some() {
+        let candidate_ids: HashSet<usize> = articles.iter().map(|&(article_id, _)| article_id).collect();
+        seen_articles.extend((0..search_index.articles.len()).filter(|id| !candidate_ids.contains(id)));
+    }
     
     // 第1步: 查找以查询开头的标题 (如"wasm入门指南")
-    for (article_id, article) in search_index.articles.iter().enumerate() {
+    for &(article_id, article) in &articles {
+        if token.is_cancelled() {
+            return Vec::new();
+        }
+        
         let title_lower = article.title.to_lowercase();
         
         if title_lower.starts_with(query) && title_lower != *query {
@@ -478,7 +1297,11 @@ fn find_matched_articles(search_index: &ArticleSearchIndex, terms: &[String]) ->
     }
     
     // 第2步: 查找包含查询的标题 (如"使用wasm")
-    for (article_id, article) in search_index.articles.iter().enumerate() {
+    for &(article_id, article) in &articles {
+        if token.is_cancelled() {
+            return Vec::new();
+        }
+        
         if seen_articles.contains(&article_id) {
             continue;
         }
@@ -493,7 +1316,11 @@ fn find_matched_articles(search_index: &ArticleSearchIndex, terms: &[String]) ->
     }
     
     // 第3步: 查找标题与查询完全匹配的文章 (如只有"wasm")
-    for (article_id, article) in search_index.articles.iter().enumerate() {
+    for &(article_id, article) in &articles {
+        if token.is_cancelled() {
+            return Vec::new();
+        }
+        
         if seen_articles.contains(&article_id) {
             continue;
         }
@@ -506,71 +1333,184 @@ fn find_matched_articles(search_index: &ArticleSearchIndex, terms: &[String]) ->
         }
     }
     
-    // 第4步: 从索引中查找匹配
-    if let Some(article_ids) = search_index.title_term_index.get(query) {
-        for &article_id in article_ids {
-            if seen_articles.contains(&article_id) {
+    // 第4步: 从索引中查找匹配，各字段的分数按构建配置中的系数调整，
+    // 查询按构建索引时各字段的分析器规范化
+    let config = &search_index.config;
+    let weights = &config.field_weights;
+    let field_query = |analyzer_id: &str| analyzer::query_term(analyzer_id, config, query);
+    if let Some(article_ids) = field_query(&config.analyzers.title).and_then(|term| search_index.title_term_index.get(&term)) {
+        for article_id in article_ids {
+            if seen_articles.contains(&article_id) || !search_index.is_live(article_id) {
                 continue;
             }
             
-            result_with_scores.push((article_id, 85.0));
+            result_with_scores.push((article_id, 85.0 * weights.title));
             seen_articles.insert(article_id);
         }
     }
     
     // 第5步: 从标题关键词索引中查找
-    if let Some(heading_ids) = search_index.heading_term_index.get(query) {
+    if let Some(heading_ids) = field_query(&config.analyzers.heading).and_then(|term| search_index.heading_term_index.get(&term)) {
         for heading_id in heading_ids {
-            if let Some(article_id) = extract_article_id_from_heading(heading_id) {
-                if seen_articles.contains(&article_id) || article_id >= search_index.articles.len() {
+            if let Some(article_id) = search_index.heading_ordinal(heading_id) {
+                if seen_articles.contains(&article_id) || !search_index.is_live(article_id) {
                     continue;
                 }
                 
-                result_with_scores.push((article_id, 80.0));
+                result_with_scores.push((article_id, 80.0 * weights.heading));
                 seen_articles.insert(article_id);
             }
         }
     }
     
     // 第6步: 从内容索引中查找
-    if let Some(article_ids) = search_index.content_term_index.get(query) {
-        for &article_id in article_ids {
-            if seen_articles.contains(&article_id) || article_id >= search_index.articles.len() {
+    if let Some(article_ids) = field_query(&config.analyzers.content).and_then(|term| search_index.content_term_index.get(&term)) {
+        for article_id in article_ids {
+            if seen_articles.contains(&article_id) || !search_index.is_live(article_id) {
+                continue;
+            }
+            
+            result_with_scores.push((article_id, 75.0 * weights.content));
+            seen_articles.insert(article_id);
+        }
+    }
+    
+    // 第7步: 从媒体说明索引中查找（图片alt、figcaption等），权重低于正文
+    if let Some(article_ids) = field_query(&config.analyzers.media).and_then(|term| search_index.media_term_index.get(&term)) {
+        for article_id in article_ids {
+            if seen_articles.contains(&article_id) || !search_index.is_live(article_id) {
                 continue;
             }
             
-            result_with_scores.push((article_id, 75.0));
+            result_with_scores.push((article_id, 60.0 * weights.media));
             seen_articles.insert(article_id);
         }
     }
     
-    // 第7步: 如果没有找到任何匹配，尝试更宽松的匹配
+    // 第8步: 如果没有找到任何匹配，尝试更宽松的匹配
     if result_with_scores.is_empty() {
         // 对所有文章内容进行更宽松的搜索
-        for (article_id, article) in search_index.articles.iter().enumerate() {
-            let content_lower = article.content.to_lowercase();
+        for &(article_id, article) in &articles {
+            if token.is_cancelled() {
+                return Vec::new();
+            }
+            
+            // 只检查已经可用的正文，不为宽松匹配加载所有文章的正文
+            let content_lower = content::cached(search_index, article_id)
+                .map(|content| content.to_lowercase())
+                .unwrap_or_default();
             
             if content_lower.contains(query) {
                 result_with_scores.push((article_id, 50.0));
+            } else if article.media_text.to_lowercase().contains(query) {
+                // 只在媒体说明中出现，分数更低
+                result_with_scores.push((article_id, 40.0));
             }
         }
+        
+        let rewrite = QueryRewrite {
+            kind: RewriteKind::LooseMatch,
+            from: query.clone(),
+            to: vec![query.clone()],
+        };
+        if !result_with_scores.is_empty() && !debug.rewrites.contains(&rewrite) {
+            debug.rewrites.push(rewrite);
+        }
     }
     
+    // 按语言调整分数
+    apply_language_weights(search_index, query, &mut result_with_scores);
+    
+    // 按页面类型调整分数
+    apply_page_type_boosts(search_index, options.page_type_boosts, &mut result_with_scores);
+    
     // 按分数降序排序
     result_with_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
     
     result_with_scores
 }
 
-/// 从标题ID中提取文章ID
-fn extract_article_id_from_heading(heading_id: &str) -> Option<usize> {
-    // 标题ID的格式为 "article_id:heading_index"
-    if let Some(colon_pos) = heading_id.find(':') {
-        if let Some(article_id_str) = heading_id.get(0..colon_pos) {
-            return article_id_str.parse::<usize>().ok();
+/// 文章匹配选项
+struct MatchOptions<'a> {
+    /// 覆盖索引默认值的页面类型分数系数
+    page_type_boosts: &'a HashMap<String, f64>,
+    /// 候选文章，为空时匹配所有文章
+    candidates: Option<&'a HashSet<usize>>,
+    /// 取消令牌
+    token: &'a CancelToken,
+}
+
+/// 参与匹配的文章，有候选文章时只包含候选文章，按ID升序
+fn candidate_articles<'a>(search_index: &'a ArticleSearchIndex, candidates: Option<&HashSet<usize>>) -> Vec<(usize, &'a utils_common::models::ArticleMetadata)> {
+    match candidates {
+        Some(candidates) => {
+            let mut ids: Vec<usize> = candidates.iter().copied().collect();
+            ids.sort_unstable();
+            ids.into_iter()
+                .filter(|&article_id| search_index.is_live(article_id))
+                .map(|article_id| (article_id, &search_index.articles[article_id]))
+                .collect()
+        }
+        None => search_index.live_articles().collect(),
+    }
+}
+
+/// 按页面类型调整分数
+///
+/// 请求中指定的系数优先，其次使用索引中保存的默认系数，都没有时系数为1。
+fn apply_page_type_boosts(search_index: &ArticleSearchIndex, overrides: &HashMap<String, f64>, results: &mut [(usize, f64)]) {
+    if search_index.page_type_boosts.is_empty() && overrides.is_empty() {
+        return;
+    }
+    
+    for (article_id, score) in results.iter_mut() {
+        let Some(article) = search_index.articles.get(*article_id) else {
+            continue;
+        };
+        
+        let boost = overrides.get(&article.page_type)
+            .or_else(|| search_index.page_type_boosts.get(&article.page_type))
+            .copied()
+            .unwrap_or(1.0);
+        *score *= boost;
+    }
+}
+
+/// 同语言结果的分数系数
+const SAME_LANGUAGE_BOOST: f64 = 1.1;
+/// 跨语言结果的分数系数
+const CROSS_LANGUAGE_PENALTY: f64 = 0.6;
+
+/// 按查询语言与文章语言调整分数
+///
+/// 只有索引中包含多种语言时才生效：同语言的文章加权，跨语言的文章降权，
+/// 避免中文查询与英文文章的无意义部分匹配排在前面。
+fn apply_language_weights(search_index: &ArticleSearchIndex, query: &str, results: &mut [(usize, f64)]) {
+    let query_lang = detect_language(query);
+    if query_lang == "und" {
+        return;
+    }
+    
+    // 单一语言的索引无需调整
+    let languages: HashSet<&str> = search_index.live_articles()
+        .map(|(_, article)| article.lang.as_str())
+        .filter(|lang| !lang.is_empty() && *lang != "und")
+        .collect();
+    if languages.len() < 2 {
+        return;
+    }
+    
+    for (article_id, score) in results.iter_mut() {
+        let article_lang = search_index.articles.get(*article_id)
+            .map(|article| article.lang.as_str())
+            .unwrap_or("");
+        
+        if article_lang == query_lang {
+            *score *= SAME_LANGUAGE_BOOST;
+        } else if !article_lang.is_empty() && article_lang != "und" {
+            *score *= CROSS_LANGUAGE_PENALTY;
         }
     }
-    None
 }
 
 /// 在文章内容中查找匹配词，并提取段落上下文
@@ -681,7 +1621,7 @@ fn format_matched_content(content: &str, term_positions: &[(usize, usize, i32)])
         };
         
         // 安全地计算上下文起始位置，确保位于字符边界上
-        let mut ctx_start = if first_start > 150 { first_start - 150 } else { 0 };
+        let mut ctx_start = first_start.saturating_sub(150);
         ctx_start = find_char_boundary(content, ctx_start); // 确保在字符边界上
         
         // 安全地计算上下文结束位置，确保位于字符边界上
@@ -831,9 +1771,8 @@ fn build_heading_tree_with_matches(
     }
 
     // 获取与文章相关的所有标题
-    let article_id_str = article.id.to_string();
     let heading_map: HashMap<String, &HeadingIndexEntry> = search_index.heading_index.iter()
-        .filter(|(id, _)| id.starts_with(&format!("{}:", article_id_str)))
+        .filter(|(id, _)| id.rsplit_once(':').is_some_and(|(article_id, _)| article_id == article.id))
         .map(|(id, entry)| (id.clone(), entry))
         .collect();
     
@@ -1,7 +1,31 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use bincode::{Encode, Decode};
-use utils_common::models::ArticleMetadata;
+use ts_rs::TS;
+use utils_common::models::{ArticleMetadata, Visibility};
+use crate::dictionary::TermDictionary;
+use crate::positions::PositionIndex;
+use crate::content::ContentRange;
+use crate::builder::SearchBuilderConfig;
+use crate::related::RelatedArticle;
+
+/// 文章列表的序列化 - 写入时转换为紧凑文章表，读取时检查后还原
+mod article_table {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use utils_common::arena::ArticleTable;
+    use utils_common::models::ArticleMetadata;
+
+    pub fn serialize<S: Serializer>(articles: &[ArticleMetadata], serializer: S) -> Result<S::Ok, S::Error> {
+        ArticleTable::from_articles(articles).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ArticleMetadata>, D::Error> {
+        let table = ArticleTable::deserialize(deserializer)?;
+        table.validate().map_err(D::Error::custom)?;
+        Ok(table.to_articles())
+    }
+}
 
 /// 标题索引项
 #[derive(Serialize, Deserialize, Clone, Debug, Encode, Decode)]
@@ -23,7 +47,7 @@ pub struct HeadingIndexEntry {
 }
 
 /// 带有匹配内容的标题节点
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, TS)]
 pub struct HeadingNode {
     /// 标题ID
     pub id: String,
@@ -40,40 +64,379 @@ pub struct HeadingNode {
 }
 
 /// 搜索索引 - 简化版本
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ArticleSearchIndex {
-    /// 关键词到文章ID的映射（标题）
-    pub title_term_index: HashMap<String, HashSet<usize>>,
+    /// 关键词到文章位置的词典（标题）
+    pub title_term_index: TermDictionary,
     /// 文章的元数据列表
+    ///
+    /// 索引文件中以紧凑文章表（`ArticleTable`）保存，加载时检查各区间后还原为文章列表。
+    #[serde(with = "article_table")]
     pub articles: Vec<ArticleMetadata>,
+    /// 文章ID到文章位置的映射，只包含未移除的文章
+    ///
+    /// 文章位置只在索引内部使用，对外一律使用文章ID；构建索引时文章按ID排序，
+    /// 与输入文件的顺序无关。
+    pub ordinals: BTreeMap<String, usize>,
     /// 标题索引 - 标题ID到标题信息的映射
     pub heading_index: HashMap<String, HeadingIndexEntry>,
     /// 关键词到标题ID的映射
     pub heading_term_index: HashMap<String, HashSet<String>>,
-    /// 常用词汇及其频率
-    pub common_terms: HashMap<String, usize>,
-    /// 内容关键词到文章ID的映射
-    pub content_term_index: HashMap<String, HashSet<usize>>,
+    /// 常用词汇（小写）到建议词典条目的映射
+    pub common_terms: HashMap<String, CommonTerm>,
+    /// 内容关键词到文章位置的词典
+    pub content_term_index: TermDictionary,
+    /// 媒体说明文本（图片alt、figcaption等）关键词到文章位置的词典，匹配权重低于正文
+    pub media_term_index: TermDictionary,
+    /// 标题关键词的位置索引
+    pub title_position_index: PositionIndex,
+    /// 正文关键词的位置索引
+    pub content_position_index: PositionIndex,
+    /// 媒体说明关键词的位置索引
+    pub media_position_index: PositionIndex,
+    /// 页面类型的默认分数系数，未列出的类型系数为1
+    pub page_type_boosts: HashMap<String, f64>,
+    /// 已移除文章的位置 - 在已加载的索引中删除文章时标记，构建索引时为空
+    pub tombstones: HashSet<usize>,
+    /// 构建索引时使用的分词器标识，内置分词器为 `builtin`
+    pub tokenizer: String,
+    /// 每篇文章的正文在内容文件中的区间，与 `articles` 一一对应；正文内嵌在索引中时为空
+    pub content_ranges: Vec<ContentRange>,
+    /// 构建索引时使用的配置
+    pub config: SearchBuilderConfig,
+    /// 每篇文章的相关文章，与 `articles` 一一对应，按相似度降序
+    pub related: Vec<Vec<RelatedArticle>>,
+    /// 每篇文章各字段的词条数量，与 `articles` 一一对应，已移除的文章均为0
+    ///
+    /// 与 `average_field_lengths` 一起为按文档长度归一化匹配分数（BM25）预先保存，
+    /// 目前只写入索引和构建统计，计算匹配分数时尚未使用。
+    pub field_lengths: Vec<FieldLengths>,
+    /// 未移除文章各字段的平均词条数量，尚未参与排序（见 `field_lengths`）
+    pub average_field_lengths: FieldAverages,
+    /// 标签到文章位置的映射，只包含未移除的文章
+    pub tag_index: BTreeMap<String, BTreeSet<usize>>,
+    /// 分类到文章位置的映射，只包含未移除的文章
+    ///
+    /// 分类为文章ID中的目录部分，文章同时属于所在目录和各级上级目录，
+    /// 如 `articles/web/部署/nginx` 属于 `articles`、`articles/web` 和 `articles/web/部署`。
+    pub category_index: BTreeMap<String, BTreeSet<usize>>,
+    /// 最高可见级别 - 加载索引时指定，不写入索引文件，默认只列出公开文章
+    #[serde(skip)]
+    pub max_visibility: Visibility,
+}
+
+impl ArticleSearchIndex {
+    /// 文章是否可以出现在搜索结果中（未被移除且在最高可见级别下列出）
+    pub fn is_live(&self, article_id: usize) -> bool {
+        self.articles.get(article_id).is_some_and(|article| {
+            !self.tombstones.contains(&article_id) && article.visibility.is_listed(self.max_visibility)
+        })
+    }
+
+    /// 按文章ID查找未移除文章的位置（包括当前可见级别下不列出的文章）
+    pub fn ordinal(&self, id: &str) -> Option<usize> {
+        self.ordinals.get(id).copied()
+    }
+
+    /// 从标题ID（文章ID:标题序号）中解析出文章位置
+    pub fn heading_ordinal(&self, heading_id: &str) -> Option<usize> {
+        // 文章ID本身可能包含冒号，标题序号在最后一个冒号之后
+        let (article_id, _) = heading_id.rsplit_once(':')?;
+        self.ordinal(article_id)
+    }
+
+    /// 按文章列表重新生成文章ID到位置的映射，文章ID重复时返回错误
+    pub fn rebuild_ordinals(&mut self) -> Result<(), String> {
+        let mut ordinals = BTreeMap::new();
+        for (article_id, article) in self.articles.iter().enumerate() {
+            if self.tombstones.contains(&article_id) {
+                continue;
+            }
+            if ordinals.insert(article.id.clone(), article_id).is_some() {
+                return Err(format!("文章ID重复: {}", article.id));
+            }
+        }
+        self.ordinals = ordinals;
+        Ok(())
+    }
+
+    /// 检查文章ID映射与文章列表、标题索引是否一致，加载索引时调用
+    pub fn validate_ordinals(&self) -> Result<(), String> {
+        for (id, article_id) in &self.ordinals {
+            match self.articles.get(*article_id) {
+                Some(article) if article.id == *id && !self.tombstones.contains(article_id) => {}
+                Some(_) => return Err(format!("文章ID映射不一致: {} -> {}", id, article_id)),
+                None => return Err(format!("文章ID映射超出文章列表: {} -> {}", id, article_id)),
+            }
+        }
+        let live = self.articles.len() - self.tombstones.iter().filter(|article_id| **article_id < self.articles.len()).count();
+        if self.ordinals.len() != live {
+            return Err(format!("文章ID映射缺少文章: 映射 {} 篇，未移除的文章 {} 篇", self.ordinals.len(), live));
+        }
+        if let Some(heading_id) = self.heading_index.keys().find(|heading_id| self.heading_ordinal(heading_id).is_none()) {
+            return Err(format!("标题不属于索引中的文章: {}", heading_id));
+        }
+        Ok(())
+    }
+
+    /// 按未移除文章的字段长度重新计算平均字段长度
+    pub fn update_field_averages(&mut self) {
+        let mut total = FieldAverages::default();
+        let mut count = 0;
+        for (article_id, lengths) in self.field_lengths.iter().enumerate() {
+            if self.tombstones.contains(&article_id) {
+                continue;
+            }
+            total.title += lengths.title as f64;
+            total.heading += lengths.heading as f64;
+            total.content += lengths.content as f64;
+            total.media += lengths.media as f64;
+            count += 1;
+        }
+        if count > 0 {
+            let count = count as f64;
+            total.title /= count;
+            total.heading /= count;
+            total.content /= count;
+            total.media /= count;
+        }
+        self.average_field_lengths = total;
+    }
+
+    /// 按未移除的文章重新生成标签和分类索引
+    pub fn rebuild_taxonomy(&mut self) {
+        let mut tag_index: BTreeMap<String, BTreeSet<usize>> = BTreeMap::new();
+        let mut category_index: BTreeMap<String, BTreeSet<usize>> = BTreeMap::new();
+        for (article_id, article) in self.articles.iter().enumerate() {
+            if self.tombstones.contains(&article_id) {
+                continue;
+            }
+            for tag in &article.tags {
+                tag_index.entry(tag.clone()).or_default().insert(article_id);
+            }
+            for category in article_categories(&article.id) {
+                category_index.entry(category.to_string()).or_default().insert(article_id);
+            }
+        }
+        self.tag_index = tag_index;
+        self.category_index = category_index;
+    }
+
+    /// 带有该标签、可以出现在搜索结果中的文章位置
+    pub fn articles_with_tag(&self, tag: &str) -> impl Iterator<Item = usize> + '_ {
+        self.tag_index.get(tag)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|article_id| self.is_live(*article_id))
+    }
+
+    /// 属于该分类（包括其下级分类）、可以出现在搜索结果中的文章位置
+    pub fn articles_in_category(&self, category: &str) -> impl Iterator<Item = usize> + '_ {
+        self.category_index.get(category.trim_matches('/'))
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|article_id| self.is_live(*article_id))
+    }
+
+    /// 遍历可以出现在搜索结果中的文章及其位置
+    pub fn live_articles(&self) -> impl Iterator<Item = (usize, &ArticleMetadata)> {
+        self.articles.iter()
+            .enumerate()
+            .filter(|(article_id, _)| self.is_live(*article_id))
+    }
+}
+
+/// 文章所属的各级分类 - 文章ID中最后一个 `/` 之前的各级目录，由上到下
+pub fn article_categories(id: &str) -> impl Iterator<Item = &str> {
+    let id = id.trim_matches('/');
+    id.match_indices('/').map(move |(end, _)| &id[..end])
+}
+
+/// 文章各字段的词条数量（词条在字段中每出现一次计一次）
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FieldLengths {
+    /// 标题
+    pub title: u32,
+    /// 所有章节标题
+    pub heading: u32,
+    /// 正文
+    pub content: u32,
+    /// 媒体说明
+    pub media: u32,
+}
+
+/// 各字段的平均词条数量
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FieldAverages {
+    /// 标题
+    pub title: f64,
+    /// 所有章节标题
+    pub heading: f64,
+    /// 正文
+    pub content: f64,
+    /// 媒体说明
+    pub media: f64,
 }
 
 /// 搜索请求结构
-#[derive(Deserialize)]
+///
+/// 二进制协议（`search_articles_bin`）按字段定义顺序编码，新增字段只能追加在末尾。
+/// 除查询外的字段都有默认值，TypeScript类型中为可选字段。
+#[derive(Serialize, Deserialize, TS)]
+#[ts(optional_fields)]
 pub struct SearchRequest {
     /// 搜索查询
     pub query: String,
     /// 搜索类型 (normal或autocomplete)
     #[serde(default)]
+    #[ts(as = "Option<String>")]
     pub search_type: String,
     /// 当前页码
     #[serde(default = "default_page")]
+    #[ts(as = "Option<usize>")]
     pub page: usize,
     /// 每页条数
     #[serde(default = "default_page_size")]
+    #[ts(as = "Option<usize>")]
     pub page_size: usize,
+    /// 摘要来源
+    #[serde(default)]
+    #[ts(as = "Option<SummaryMode>")]
+    pub summary_mode: SummaryMode,
+    /// 搜索范围 - 要搜索的已加载索引名称，为空时搜索所有索引
+    #[serde(default)]
+    #[ts(as = "Option<Vec<String>>")]
+    pub scope: Vec<String>,
+    /// 请求所属的代数，与当前代数不一致时请求被视为已取消
+    #[serde(default)]
+    pub epoch: Option<u32>,
+    /// 覆盖索引中的页面类型分数系数
+    #[serde(default)]
+    #[ts(as = "Option<HashMap<String, f64>>")]
+    pub page_type_boosts: HashMap<String, f64>,
+    /// 结果详细程度
+    #[serde(default)]
+    #[ts(as = "Option<ResultDetail>")]
+    pub result_detail: ResultDetail,
+    /// 边输入边搜索的会话ID，同一输入框的连续查询使用相同的ID以复用上一次的候选文章
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// 请求方使用的分词器标识，指定时必须与索引构建时使用的分词器一致
+    #[serde(default)]
+    pub tokenizer: Option<String>,
+    /// 按匹配的顶级章节对结果分组，结果中返回 `clusters`
+    #[serde(default)]
+    #[ts(as = "Option<bool>")]
+    pub cluster_results: bool,
+    /// 标签筛选 - 只返回带有全部指定标签的文章，与查询中的 `tag:标签` 合并
+    #[serde(default)]
+    #[ts(as = "Option<Vec<String>>")]
+    pub tags: Vec<String>,
+    /// 分类筛选 - 只返回属于该分类（文章ID中的目录，包括下级目录）的文章，
+    /// 与查询中的 `category:分类` 合并
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+impl SearchRequest {
+    /// 创建使用默认参数的搜索请求
+    pub fn new(query: &str) -> Self {
+        SearchRequest {
+            query: query.to_string(),
+            search_type: String::new(),
+            page: default_page(),
+            page_size: default_page_size(),
+            summary_mode: SummaryMode::default(),
+            scope: Vec::new(),
+            epoch: None,
+            page_type_boosts: HashMap::new(),
+            result_detail: ResultDetail::default(),
+            session_id: None,
+            tokenizer: None,
+            cluster_results: false,
+            tags: Vec::new(),
+            category: None,
+        }
+    }
+
+    /// 拆分查询中的范围限定（`tag:标签`、`category:分类`），与请求中的标签和分类筛选合并
+    pub fn query_scope(&self) -> QueryScope {
+        let mut scope = QueryScope {
+            text: String::new(),
+            tags: self.tags.clone(),
+            categories: self.category.iter().cloned().collect(),
+        };
+        let mut words = Vec::new();
+        for word in self.query.split_whitespace() {
+            if let Some(tag) = word.strip_prefix("tag:").filter(|tag| !tag.is_empty()) {
+                scope.tags.push(tag.to_string());
+            } else if let Some(category) = word.strip_prefix("category:").filter(|category| !category.is_empty()) {
+                scope.categories.push(category.to_string());
+            } else {
+                words.push(word);
+            }
+        }
+        // 没有范围限定时保留原样的查询
+        scope.text = if words.len() == self.query.split_whitespace().count() {
+            self.query.clone()
+        } else {
+            words.join(" ")
+        };
+        scope
+    }
+}
+
+/// 搜索范围限定 - 查询文本与标签、分类筛选
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryScope {
+    /// 去除范围限定后的查询
+    pub text: String,
+    /// 文章需要带有的全部标签
+    pub tags: Vec<String>,
+    /// 文章需要属于的全部分类
+    pub categories: Vec<String>,
+}
+
+impl QueryScope {
+    /// 索引中符合标签和分类筛选、可以出现在搜索结果中的文章位置，没有筛选时返回None
+    ///
+    /// 通过标签和分类索引查找，不需要遍历所有文章。
+    pub fn articles(&self, index: &ArticleSearchIndex) -> Option<HashSet<usize>> {
+        let mut sets = self.tags.iter()
+            .map(|tag| index.articles_with_tag(tag).collect::<HashSet<usize>>())
+            .chain(self.categories.iter().map(|category| index.articles_in_category(category).collect()));
+        let first = sets.next()?;
+        Some(sets.fold(first, |matched, set| &matched & &set))
+    }
+}
+
+/// 搜索结果的详细程度
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultDetail {
+    /// 完整结果，包含摘要和标题树
+    #[default]
+    Full,
+    /// 精简结果，只填充ID、标题、URL、分数和来源索引，不构建摘要和标题树，
+    /// 适合快速结果下拉框
+    Minimal,
+}
+
+/// 搜索结果摘要的来源
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryMode {
+    /// 使用文章自带的摘要
+    #[default]
+    Stored,
+    /// 使用正文中匹配最集中的片段，正文没有匹配时回退到文章摘要
+    Snippet,
 }
 
 /// 搜索建议类型
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum SuggestionType {
     /// 补全建议 - 前缀匹配
@@ -82,6 +445,63 @@ pub enum SuggestionType {
     Correction
 }
 
+/// 词条的来源字段，按标题、章节标题、正文的顺序优先
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum TermField {
+    /// 文章标题
+    Title,
+    /// 章节标题
+    Heading,
+    /// 正文
+    Content,
+}
+
+/// 建议词典条目 - 常用词汇的频率、展示形式和来源字段
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CommonTerm {
+    /// 按文档频率降权后的频率
+    pub frequency: usize,
+    /// 展示形式 - 原文中出现最多的大小写形式（如 `JavaScript`），建议按此显示
+    pub display: String,
+    /// 词条出现的最重要的字段
+    pub field: TermField,
+}
+
+/// 词条在各个已加载索引中的统计 - 供调试相关性使用（`get_term_stats`）
+#[derive(Serialize, Debug)]
+pub struct TermStats {
+    /// 查询的词条（原样）
+    pub term: String,
+    /// 各索引中的统计，按索引加载顺序排列
+    pub indexes: Vec<IndexTermStats>,
+}
+
+/// 词条在一个索引中的统计
+///
+/// 文档频率只统计可以出现在搜索结果中的文章，词条按各字段的分析器规范化后查找。
+#[derive(Serialize, Debug)]
+pub struct IndexTermStats {
+    /// 索引名称
+    pub index: String,
+    /// 可以出现在搜索结果中的文章数
+    pub articles: usize,
+    /// 标题中包含该词条的文章数
+    pub title: usize,
+    /// 章节标题中包含该词条的文章数
+    pub heading: usize,
+    /// 正文中包含该词条的文章数
+    pub content: usize,
+    /// 媒体说明中包含该词条的文章数
+    pub media: usize,
+    /// 是否为构建配置中的停用词（停用词不写入索引）
+    pub stop_word: bool,
+    /// 是否短于构建配置中的最短词条长度（不写入索引）
+    pub too_short: bool,
+    /// 常用词条中的记录，不是常用词条时为空
+    pub common_term: Option<CommonTerm>,
+}
+
 /// 搜索建议分数和类型（内部使用）
 #[derive(Debug, Clone)]
 pub struct SuggestionCandidate {
@@ -93,10 +513,12 @@ pub struct SuggestionCandidate {
     pub suggestion_type: SuggestionType,
     /// 原始关键词频率
     pub frequency: usize,
+    /// 来源字段
+    pub field: TermField,
 }
 
 /// 搜索建议结构（对外输出）
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, TS)]
 pub struct SearchSuggestion {
     /// 建议文本
     pub text: String,
@@ -106,10 +528,23 @@ pub struct SearchSuggestion {
     pub matched_text: String,
     /// 建议补全部分
     pub suggestion_text: String,
+    /// 建议的来源字段
+    pub field: TermField,
+}
+
+/// 一批搜索建议 - 供建议下拉框逐批加载
+#[derive(Serialize, Debug)]
+pub struct SuggestionPage {
+    /// 本批建议
+    pub suggestions: Vec<SearchSuggestion>,
+    /// 下一批的起始位置，没有更多建议时为空
+    pub next_offset: Option<usize>,
 }
 
 /// 搜索结果
-#[derive(Serialize)]
+///
+/// 二进制协议（`search_articles_bin`）按字段定义顺序编码。
+#[derive(Serialize, Deserialize, TS)]
 pub struct SearchResult {
     /// 搜索结果条目
     pub items: Vec<SearchResultItem>,
@@ -121,31 +556,178 @@ pub struct SearchResult {
     pub page_size: usize,
     /// 总页数
     pub total_pages: usize,
-    /// 搜索耗时(毫秒)
-    pub time_ms: usize,
     /// 搜索查询
     pub query: String,
     /// 搜索建议
     pub suggestions: Vec<SearchSuggestion>,
+    /// 搜索是否被取消
+    pub cancelled: bool,
+    /// 查询的处理过程，供调试和界面显示实际生效的查询与筛选条件
+    pub query_debug: QueryDebug,
+    /// 无结果提示 - 查询没有结果时提供，有结果时为空
+    pub hints: Option<NoResultsHints>,
+    /// 诊断信息（耗时等每次搜索都会变化的数据），关闭计时后为空，结果中的其他字段保持确定
+    pub diagnostics: Option<Diagnostics>,
+    /// 按顶级章节分组的结果 - 请求 `cluster_results` 时提供
+    pub clusters: Option<Vec<ResultCluster>>,
+}
+
+/// 搜索诊断信息
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+pub struct Diagnostics {
+    /// 搜索耗时(毫秒)
+    pub time_ms: usize,
+}
+
+/// 结果分组 - 所有结果中匹配出现在同名顶级章节（如“部署”）下的文章
+///
+/// 一篇文章可以属于多个分组。分组覆盖全部结果而不只是当前页。
+#[derive(Serialize, Deserialize, Debug, Clone, TS)]
+pub struct ResultCluster {
+    /// 分组名称 - 章节标题，同名（不区分大小写）的章节合为一组，按排名最高的文章中的写法显示
+    pub label: String,
+    /// 分组中的文章，按结果顺序排列
+    pub items: Vec<ClusterItem>,
+}
+
+/// 分组中的文章
+#[derive(Serialize, Deserialize, Debug, Clone, TS)]
+pub struct ClusterItem {
+    /// 文章ID
+    pub id: String,
+    /// 文章标题
+    pub title: String,
+    /// 文章URL
+    pub url: String,
+    /// 结果所属的索引名称
+    pub source: String,
+    /// 匹配所在的顶级章节ID，与 `heading_tree` 中节点的 `id` 相同
+    pub heading_id: String,
+}
+
+/// 无结果提示 - 界面可以据此显示"试试: wasm-bindgen, webassembly"，而不是固定的文案
+#[derive(Serialize, Deserialize, Debug, Clone, Default, TS)]
+pub struct NoResultsHints {
+    /// 与查询最接近的索引词汇，每个词汇单独搜索都有结果
+    pub terms: Vec<String>,
+    /// 支持的查询用法
+    pub operators: Vec<QueryOperator>,
+    /// 替代查询 - 纠正拼写或去掉部分单词后的完整查询，按结果数从多到少排列
+    pub did_you_mean: Vec<AlternativeQuery>,
+}
+
+/// 替代查询
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+pub struct AlternativeQuery {
+    /// 查询文本
+    pub query: String,
+    /// 按该查询搜索的结果数
+    pub total: usize,
+}
+
+/// 查询用法说明
+#[derive(Serialize, Deserialize, Debug, Clone, TS)]
+pub struct QueryOperator {
+    /// 示例语法
+    pub syntax: String,
+    /// 说明
+    pub description: String,
+}
+
+/// 查询处理信息 - 说明查询如何被规范化、切分和改写，以及哪些筛选条件生效
+///
+/// 界面可以据此显示“rust wasm 的结果（范围: blog）”之类的标题。
+#[derive(Serialize, Deserialize, Debug, Clone, Default, TS)]
+pub struct QueryDebug {
+    /// 原始查询
+    pub original: String,
+    /// 规范化后的查询（去除首尾空白并转为小写）
+    pub normalized: String,
+    /// 参与匹配和高亮的词条，第一个为完整查询
+    pub tokens: Vec<String>,
+    /// 对查询做的改写
+    pub rewrites: Vec<QueryRewrite>,
+    /// 生效的筛选条件
+    pub filters: Vec<AppliedFilter>,
+}
+
+/// 查询改写
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+pub struct QueryRewrite {
+    /// 改写类型
+    pub kind: RewriteKind,
+    /// 改写前的文本
+    pub from: String,
+    /// 改写后的文本
+    pub to: Vec<String>,
+}
+
+/// 查询改写类型
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum RewriteKind {
+    /// 去除首尾空白并转为小写
+    Normalize,
+    /// 多个词组成的查询切分为独立的词条
+    Split,
+    /// 词汇索引没有匹配，改为在正文和媒体说明中查找包含查询的文章
+    LooseMatch,
+}
+
+/// 生效的筛选条件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+pub struct AppliedFilter {
+    /// 筛选字段：scope（搜索范围）、visibility（最高可见级别）、
+    /// page_type_boost（页面类型分数系数）、session（复用会话候选文章）、
+    /// tag（标签筛选）、category（分类筛选）
+    pub field: String,
+    /// 筛选值
+    pub values: Vec<String>,
 }
 
 /// 搜索结果条目
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, TS)]
 pub struct SearchResultItem {
     /// 文章ID
     pub id: String,
     /// 文章标题
     pub title: String,
-    /// 文章摘要
+    /// 文章摘要（查询词已高亮）
     pub summary: String,
+    /// 摘要中的匹配区间
+    pub summary_matches: Vec<MatchSpan>,
     /// 文章URL
     pub url: String,
-    /// 匹配分数
+    /// 原始匹配分数 - 各匹配规则的基础分数乘以各项系数，不同查询之间不可比较；
+    /// 界面应改用 `relevance`，该字段在过渡期后移除
     pub score: f64,
     /// 结构化的标题和内容层级
     pub heading_tree: Option<HeadingNode>,
     /// 页面类型
     pub page_type: String,
+    /// 文章语言，用于界面显示语言标记
+    pub lang: String,
+    /// 结果所属的索引名称
+    pub source: String,
+    /// 文章中匹配的关键词，合并标题树各节点的匹配并去重，按匹配的章节数降序；
+    /// 精简模式下为空
+    pub matched_terms: Vec<String>,
+    /// 相关度 - 原始分数换算到0到100的值，分数越高越接近100
+    pub relevance: f64,
+}
+
+/// 匹配区间 - 文本中命中查询词的位置
+///
+/// 偏移量以UTF-16编码单元计算，与JavaScript字符串下标一致，
+/// 并且相对于未插入高亮标记的原始文本。
+#[derive(Serialize, Deserialize, Debug, Clone, TS)]
+pub struct MatchSpan {
+    /// 起始偏移
+    pub start: usize,
+    /// 结束偏移（不包含）
+    pub end: usize,
+    /// 匹配到的原文
+    pub text: String,
 }
 
 /// 默认页码
@@ -0,0 +1,149 @@
+use fst::{Map, MapBuilder, Streamer};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::dictionary::{read_varint, write_varint};
+
+/// 词汇位置 - 每篇文章中词汇出现的字节偏移（升序）
+pub type TermPositions = BTreeMap<String, BTreeMap<usize, Vec<u32>>>;
+
+/// 位置倒排索引 - 记录词汇在某个字段（标题、正文等）中出现的位置
+///
+/// 位置为词汇在小写化后的字段文本中的字节偏移，供短语搜索、邻近度排序和
+/// 精确高亮使用，查询时无需重新扫描文章内容。词汇存放在FST中，值为位置列表的序号；
+/// 每个词汇的位置列表依次保存各篇文章的位置差值、位置数量和位置差值，均为变长编码。
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PositionIndex {
+    /// FST字节，词汇按字节序排列
+    fst: Vec<u8>,
+    /// 每个位置列表在 `data` 中的起始字节位置，最后一项为总长度
+    offsets: Vec<u32>,
+    /// 编码后的位置列表
+    data: Vec<u8>,
+}
+
+impl Default for PositionIndex {
+    fn default() -> Self {
+        Self::from_positions(BTreeMap::new())
+    }
+}
+
+impl PositionIndex {
+    /// 从词汇位置构建位置索引
+    pub fn from_positions(positions: TermPositions) -> Self {
+        let mut builder = MapBuilder::memory();
+        let mut offsets = vec![0];
+        let mut data = Vec::new();
+
+        for (term, articles) in positions.into_iter().filter(|(_, articles)| !articles.is_empty()) {
+            builder.insert(&term, (offsets.len() - 1) as u64)
+                .expect("词汇按字节序插入");
+
+            write_varint(&mut data, articles.len() as u32);
+            let mut previous_article = 0;
+            for (article_id, mut article_positions) in articles {
+                let article_id = article_id as u32;
+                write_varint(&mut data, article_id - previous_article);
+                previous_article = article_id;
+
+                article_positions.sort_unstable();
+                article_positions.dedup();
+                write_varint(&mut data, article_positions.len() as u32);
+                let mut previous = 0;
+                for position in article_positions {
+                    write_varint(&mut data, position - previous);
+                    previous = position;
+                }
+            }
+            offsets.push(data.len() as u32);
+        }
+
+        PositionIndex {
+            fst: builder.into_inner().expect("写入内存不会失败"),
+            offsets,
+            data,
+        }
+    }
+
+    /// 还原为词汇位置，用于修改后重新构建
+    pub fn to_positions(&self) -> TermPositions {
+        let mut result = BTreeMap::new();
+        if let Some(map) = self.map() {
+            let mut stream = map.stream();
+            while let Some((term, slot)) = stream.next() {
+                let term = String::from_utf8_lossy(term).into_owned();
+                result.insert(term, self.decode(slot).into_iter().collect());
+            }
+        }
+        result
+    }
+
+    /// 词汇数量
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// 位置索引是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 获取词汇在所有文章中的位置，按文章位置升序
+    pub fn get(&self, term: &str) -> Vec<(usize, Vec<u32>)> {
+        self.map()
+            .and_then(|map| map.get(term))
+            .map(|slot| self.decode(slot))
+            .unwrap_or_default()
+    }
+
+    /// 获取词汇在指定文章中的位置
+    pub fn positions(&self, term: &str, article_id: usize) -> Vec<u32> {
+        self.get(term)
+            .into_iter()
+            .find(|(id, _)| *id == article_id)
+            .map(|(_, positions)| positions)
+            .unwrap_or_default()
+    }
+
+    // 打开FST，数据损坏时视为空索引
+    fn map(&self) -> Option<Map<&[u8]>> {
+        Map::new(self.fst.as_slice()).ok()
+    }
+
+    // 解码第 slot 个位置列表，数据损坏时返回已解码的部分
+    fn decode(&self, slot: u64) -> Vec<(usize, Vec<u32>)> {
+        let slot = slot as usize;
+        let start = self.offsets.get(slot).copied().unwrap_or(0) as usize;
+        let end = self.offsets.get(slot + 1).copied().unwrap_or(0) as usize;
+        let mut bytes = self.data.get(start..end).unwrap_or(&[]);
+
+        let mut result = Vec::new();
+        let article_count = read_varint(&mut bytes).unwrap_or(0);
+        let mut article_id: u32 = 0;
+        for _ in 0..article_count {
+            let (Some(delta), Some(count)) = (read_varint(&mut bytes), read_varint(&mut bytes)) else {
+                break;
+            };
+            article_id = article_id.saturating_add(delta);
+
+            let mut positions = Vec::with_capacity((count as usize).min(bytes.len()));
+            let mut position: u32 = 0;
+            for _ in 0..count {
+                let Some(delta) = read_varint(&mut bytes) else {
+                    break;
+                };
+                position = position.saturating_add(delta);
+                positions.push(position);
+            }
+            result.push((article_id as usize, positions));
+        }
+        result
+    }
+}
+
+/// 查找词汇在文本中出现的字节偏移，文本应已小写化
+pub fn find_positions(text: &str, term: &str) -> Vec<u32> {
+    if term.is_empty() {
+        return Vec::new();
+    }
+    text.match_indices(term).map(|(offset, _)| offset as u32).collect()
+}
@@ -0,0 +1,150 @@
+// 查询日志 - 在内存中记录访客的搜索（查询、时间、结果数、耗时），默认关闭
+// 站点可以定期调用 `export_query_log()` 取出日志并发送到自己的统计接口，
+// 了解访客在搜索什么、哪些查询没有结果。日志只保存在当前页面的WASM内存中，
+// 超出容量时丢弃最早的记录。
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use crate::models::{SearchRequest, SearchResult};
+
+/// 默认最多保留的记录数
+pub const DEFAULT_CAPACITY: usize = 1000;
+
+// 全局查询日志，未开启时为空
+static QUERY_LOG: OnceCell<Mutex<Option<QueryLog>>> = OnceCell::new();
+
+/// 一次搜索的记录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QueryLogEntry {
+    /// 查询文本（原样记录）
+    pub query: String,
+    /// 搜索类型（为空表示普通搜索）
+    pub search_type: String,
+    /// 搜索时间
+    pub timestamp: DateTime<Utc>,
+    /// 结果总数
+    pub result_count: usize,
+    /// 搜索耗时（毫秒）
+    pub latency_ms: f64,
+}
+
+/// 导出的查询日志
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QueryLogExport {
+    /// 记录，按时间从早到晚排列
+    pub entries: Vec<QueryLogEntry>,
+    /// 因超出容量而丢弃的记录数（自上次清空以来）
+    pub dropped: usize,
+}
+
+/// 固定容量的查询日志
+#[derive(Debug)]
+pub struct QueryLog {
+    capacity: usize,
+    entries: VecDeque<QueryLogEntry>,
+    dropped: usize,
+}
+
+impl QueryLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    /// 追加记录，超出容量时丢弃最早的记录
+    pub fn push(&mut self, entry: QueryLogEntry) {
+        while self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// 导出当前的记录，`clear` 为真时导出后清空
+    pub fn export(&mut self, clear: bool) -> QueryLogExport {
+        let export = QueryLogExport {
+            entries: self.entries.iter().cloned().collect(),
+            dropped: self.dropped,
+        };
+        if clear {
+            self.entries.clear();
+            self.dropped = 0;
+        }
+        export
+    }
+}
+
+// 使用全局查询日志执行操作
+fn with_log<R>(f: impl FnOnce(&mut Option<QueryLog>) -> R) -> Result<R, String> {
+    let mut guard = QUERY_LOG.get_or_init(|| Mutex::new(None))
+        .lock()
+        .map_err(|_| "获取查询日志锁失败".to_string())?;
+    Ok(f(&mut guard))
+}
+
+/// 记录一次搜索，未开启日志、空查询和已取消的搜索不记录
+pub fn record(req: &SearchRequest, result: &SearchResult, latency_ms: f64) {
+    if result.cancelled || req.query.trim().is_empty() {
+        return;
+    }
+    let entry = QueryLogEntry {
+        query: req.query.clone(),
+        search_type: req.search_type.clone(),
+        timestamp: Utc::now(),
+        result_count: result.total,
+        latency_ms,
+    };
+    // 日志不可用时不影响搜索本身
+    let _ = with_log(|log| {
+        if let Some(log) = log {
+            log.push(entry);
+        }
+    });
+}
+
+/// 开启查询日志，`capacity` 为最多保留的记录数（默认1000）
+///
+/// 已开启时只修改容量，保留已有的记录。
+#[wasm_bindgen]
+pub fn enable_query_log(capacity: Option<usize>) -> Result<(), JsValue> {
+    let capacity = capacity.unwrap_or(DEFAULT_CAPACITY);
+    if capacity == 0 {
+        return Err(JsValue::from_str("查询日志容量必须大于0"));
+    }
+    with_log(|log| {
+        let log = log.get_or_insert_with(|| QueryLog::new(capacity));
+        log.capacity = capacity;
+        while log.entries.len() > capacity {
+            log.entries.pop_front();
+            log.dropped += 1;
+        }
+    })
+    .map_err(|e| JsValue::from_str(&e))
+}
+
+/// 关闭查询日志并丢弃所有记录
+#[wasm_bindgen]
+pub fn disable_query_log() -> Result<(), JsValue> {
+    with_log(|log| *log = None).map_err(|e| JsValue::from_str(&e))
+}
+
+/// 导出查询日志，返回JSON字符串（`QueryLogExport`），`clear` 为真时导出后清空
+///
+/// 未开启日志时返回空的记录列表。
+#[wasm_bindgen]
+pub fn export_query_log(clear: Option<bool>) -> Result<String, JsValue> {
+    let export = with_log(|log| match log {
+        Some(log) => log.export(clear.unwrap_or(false)),
+        None => QueryLogExport { entries: Vec::new(), dropped: 0 },
+    })
+    .map_err(|e| JsValue::from_str(&e))?;
+    serde_json::to_string(&export)
+        .map_err(|e| JsValue::from_str(&format!("序列化查询日志失败: {}", e)))
+}
@@ -0,0 +1,179 @@
+// 相关文章 - 构建索引时预先计算每篇文章最相关的文章
+// 相似度为标题和正文词汇TF-IDF向量的余弦相似度，加上标签重合度（Jaccard系数）的加成。
+// 结果写入索引，文章页面显示"相关文章"时直接读取，无需在运行时搜索。
+// 运行时增删文章后重新计算，新增或修改的文章也会出现在其他文章的相关文章中。
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::ArticleSearchIndex;
+
+/// 标题中词汇的词频系数
+const TITLE_TERM_WEIGHT: f64 = 2.0;
+
+/// 标签重合度在相似度中的系数
+const TAG_OVERLAP_WEIGHT: f64 = 0.3;
+
+/// 相关文章 - 写入索引
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RelatedArticle {
+    /// 文章在索引中的位置
+    pub article: usize,
+    /// 相似度
+    pub score: f32,
+}
+
+/// 相关文章条目 - `get_related` 返回的结果
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RelatedItem {
+    /// 文章ID
+    pub id: String,
+    /// 文章标题
+    pub title: String,
+    /// 文章摘要
+    pub summary: String,
+    /// 文章URL
+    pub url: String,
+    /// 发布日期
+    pub date: DateTime<Utc>,
+    /// 相似度
+    pub score: f32,
+}
+
+/// 计算每篇文章最相关的 `count` 篇文章，结果与 `articles` 一一对应，已移除的文章为空
+///
+/// 出现在超过 `max_df` 比例文章中的词汇（模板词等）不参与计算，
+/// 既避免所有文章彼此相似，也避免词汇两两组合的开销过大。
+pub fn compute_related(index: &ArticleSearchIndex, count: usize, max_df: f64) -> Vec<Vec<RelatedArticle>> {
+    let article_count = index.articles.len();
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let live: Vec<bool> = (0..article_count).map(|article_id| !index.tombstones.contains(&article_id)).collect();
+    let live_count = live.iter().filter(|live| **live).count();
+
+    // 词汇 -> 文章位置 -> 词频（标题中的出现次数加权）
+    let mut frequencies: BTreeMap<String, BTreeMap<usize, f64>> = BTreeMap::new();
+    for (positions, weight) in [
+        (index.title_position_index.to_positions(), TITLE_TERM_WEIGHT),
+        (index.content_position_index.to_positions(), 1.0),
+    ] {
+        for (term, articles) in positions {
+            for (article_id, term_positions) in articles {
+                if live.get(article_id).copied().unwrap_or(false) {
+                    *frequencies.entry(term.clone()).or_default().entry(article_id).or_insert(0.0) +=
+                        term_positions.len() as f64 * weight;
+                }
+            }
+        }
+    }
+
+    // 计算TF-IDF权重和向量长度，只在两篇以上文章中出现的词汇才会产生相似度
+    let max_documents = ((live_count as f64 * max_df).floor() as usize).max(2);
+    let mut norms = vec![0.0f64; article_count];
+    let mut postings: Vec<Vec<(usize, f64)>> = Vec::new();
+    for articles in frequencies.into_values() {
+        let documents = articles.len();
+        let idf = (live_count as f64 / documents as f64).ln();
+        if idf <= 0.0 {
+            continue;
+        }
+        let weighted: Vec<(usize, f64)> = articles.into_iter()
+            .map(|(article_id, tf)| (article_id, (1.0 + tf.ln()) * idf))
+            .collect();
+        for (article_id, weight) in &weighted {
+            norms[*article_id] += weight * weight;
+        }
+        if documents > 1 && documents <= max_documents {
+            postings.push(weighted);
+        }
+    }
+
+    let mut dot_products: Vec<HashMap<usize, f64>> = vec![HashMap::new(); article_count];
+    for weighted in &postings {
+        for (a, weight_a) in weighted {
+            for (b, weight_b) in weighted {
+                if a != b {
+                    *dot_products[*a].entry(*b).or_insert(0.0) += weight_a * weight_b;
+                }
+            }
+        }
+    }
+
+    // 标签重合数量 - 文章的标签先去重，重复的标签不重复计数
+    let tag_sets: Vec<BTreeSet<&str>> = index.articles.iter()
+        .enumerate()
+        .map(|(article_id, article)| match live[article_id] {
+            true => article.tags.iter().map(String::as_str).collect(),
+            false => BTreeSet::new(),
+        })
+        .collect();
+    let mut tag_articles: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (article_id, tags) in tag_sets.iter().enumerate() {
+        for tag in tags {
+            tag_articles.entry(tag).or_default().push(article_id);
+        }
+    }
+    let mut shared_tags: Vec<HashMap<usize, usize>> = vec![HashMap::new(); article_count];
+    for articles in tag_articles.values() {
+        for a in articles {
+            for b in articles {
+                if a != b {
+                    *shared_tags[*a].entry(*b).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    (0..article_count)
+        .map(|a| {
+            let mut candidates: Vec<usize> = dot_products[a].keys().chain(shared_tags[a].keys()).copied().collect();
+            candidates.sort_unstable();
+            candidates.dedup();
+
+            let mut related: Vec<RelatedArticle> = candidates.into_iter()
+                .map(|b| {
+                    let cosine = match dot_products[a].get(&b) {
+                        Some(dot) if norms[a] > 0.0 && norms[b] > 0.0 => dot / (norms[a].sqrt() * norms[b].sqrt()),
+                        _ => 0.0,
+                    };
+                    let shared = shared_tags[a].get(&b).copied().unwrap_or(0);
+                    let union = tag_sets[a].len() + tag_sets[b].len() - shared;
+                    let jaccard = if union == 0 { 0.0 } else { shared as f64 / union as f64 };
+                    RelatedArticle { article: b, score: (cosine + TAG_OVERLAP_WEIGHT * jaccard) as f32 }
+                })
+                .filter(|related| related.score > 0.0)
+                .collect();
+            // 相似度相同时按文章位置排序，保证结果稳定
+            related.sort_by(|x, y| y.score.total_cmp(&x.score).then_with(|| x.article.cmp(&y.article)));
+            related.truncate(count);
+            related
+        })
+        .collect()
+}
+
+/// 获取文章的相关文章，只返回可以出现在搜索结果中的文章；索引中没有该文章时返回None
+pub fn related_items(index: &ArticleSearchIndex, article_id: &str) -> Option<Vec<RelatedItem>> {
+    let position = index.ordinal(article_id).filter(|position| index.is_live(*position))?;
+
+    let items = index.related.get(position)
+        .map(|related| {
+            related.iter()
+                .filter(|related| index.is_live(related.article))
+                .map(|related| {
+                    let article = &index.articles[related.article];
+                    RelatedItem {
+                        id: article.id.clone(),
+                        title: article.title.clone(),
+                        summary: article.summary.clone(),
+                        url: article.url.clone(),
+                        date: article.date,
+                        score: related.score,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(items)
+}
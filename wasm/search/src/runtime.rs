@@ -0,0 +1,65 @@
+// 运行时环境辅助 - 计时与日志
+// 搜索模块既运行在浏览器中，也运行在Node.js（SSR预渲染）和原生测试环境中，
+// 这里屏蔽不同目标平台的差异，避免依赖 `window` 全局对象。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::{JsCast, JsValue};
+
+// 是否在搜索结果中记录耗时
+static TIMING_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// 开启或关闭计时 - 关闭后搜索结果不包含诊断信息，相同的请求总是得到相同的结果，
+/// 供快照测试使用
+pub fn set_timing_enabled(enabled: bool) {
+    TIMING_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否记录耗时
+pub fn timing_enabled() -> bool {
+    TIMING_ENABLED.load(Ordering::Relaxed)
+}
+
+/// 获取当前时间戳（毫秒）
+///
+/// 优先使用高精度的 `performance.now()`，在没有 `performance` 的环境中
+/// 回退到 `Date.now()`；原生环境下使用系统时钟。
+pub fn now_ms() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        performance_now().unwrap_or_else(js_sys::Date::now)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+}
+
+/// 从全局对象读取 `performance.now()`，兼容浏览器、Web Worker 和 Node.js
+#[cfg(target_arch = "wasm32")]
+fn performance_now() -> Option<f64> {
+    let performance = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("performance")).ok()?;
+    if performance.is_undefined() || performance.is_null() {
+        return None;
+    }
+    Some(performance.unchecked_into::<web_sys::Performance>().now())
+}
+
+/// 输出日志信息
+///
+/// WASM环境下写入 `console.log`（Node.js 同样提供），原生环境下写入标准错误输出。
+pub fn log(message: &str) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::console::log_1(&JsValue::from_str(message));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        eprintln!("{}", message);
+    }
+}
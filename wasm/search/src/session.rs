@@ -0,0 +1,47 @@
+use std::collections::{BTreeMap, HashSet};
+use crate::content;
+use crate::models::ArticleSearchIndex;
+
+/// 最多保留的搜索会话数量，超出时淘汰最久未使用的会话
+pub const MAX_SESSIONS: usize = 32;
+
+/// 搜索会话 - 记录同一输入框上一次查询的候选文章，供边输入边搜索时复用
+///
+/// 候选文章是标题、正文或媒体说明中包含查询的文章。新查询在上一次查询后追加字符时
+/// （如 "was" → "wasm"），任何能匹配新查询的文章都包含上一次的查询，
+/// 因此只需在上一次的候选文章中继续筛选。
+#[derive(Debug, Default)]
+pub struct SearchSession {
+    /// 上一次的查询（已转为小写）
+    pub query: String,
+    /// 上一次的搜索范围
+    pub scope: Vec<String>,
+    /// 每个索引的候选文章
+    pub candidates: BTreeMap<String, HashSet<usize>>,
+    /// 最近一次使用的序号，用于淘汰旧会话
+    pub last_used: u64,
+}
+
+impl SearchSession {
+    /// 新查询能否复用本会话的候选文章
+    pub fn can_extend(&self, query: &str, scope: &[String]) -> bool {
+        !self.query.is_empty() && self.scope == scope && query.starts_with(&self.query)
+    }
+}
+
+/// 从给定的文章中筛选出包含查询的候选文章，`base` 为空时检查所有文章
+pub fn collect_candidates(index: &ArticleSearchIndex, query: &str, base: Option<&HashSet<usize>>) -> HashSet<usize> {
+    let contains_query = |article_id: usize| {
+        index.is_live(article_id) && index.articles.get(article_id).is_some_and(|article| {
+            // 正文尚未加载的文章无法判断，保留为候选
+            article.title.to_lowercase().contains(query)
+                || content::cached(index, article_id).is_none_or(|content| content.to_lowercase().contains(query))
+                || article.media_text.to_lowercase().contains(query)
+        })
+    };
+
+    match base {
+        Some(base) => base.iter().copied().filter(|&id| contains_query(id)).collect(),
+        None => (0..index.articles.len()).filter(|&id| contains_query(id)).collect(),
+    }
+}
@@ -0,0 +1,237 @@
+//! 搜索索引格式兼容性测试 - 解码检入的搜索索引样本，确保修改模型或压缩格式后
+//! 已部署的索引文件仍能读取。
+//!
+//! 样本文件与 utils-common 的兼容性测试共用 `utils-common/tests/fixtures`。支持的格式版本为
+//! `MIN_INDEX_VERSION` 到 `MAX_INDEX_VERSION`，每个版本保留一个样本 `search-v{版本}.bin`，
+//! 以及该样本对一组固定查询的预期搜索结果 `search-v{版本}.results.json`。更早版本的索引
+//! 加载时返回要求重新生成索引的错误。
+//!
+//! 修改索引结构时需要提升写入的格式版本，并设置环境变量 `UPDATE_GOLDEN=1` 运行测试生成
+//! 新版本的样本和各版本的预期结果。新版本不保留旧版本的解码方式时，同时把
+//! `MIN_INDEX_VERSION` 提升到新版本，并删除旧版本的样本和预期结果。
+
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use search_wasm::builder::SearchBuilder;
+use search_wasm::engine::{decode_index, MAX_INDEX_VERSION, MIN_INDEX_VERSION};
+use search_wasm::models::ArticleSearchIndex;
+use serde_json::Value;
+use utils_common::compression::{
+    from_compressed_with_version_range, read_format, read_index_metadata, validate_compressed_data_with_max_version,
+    SerializationFormat,
+};
+use utils_common::models::{ArticleMetadata, IndexType};
+
+/// 预期结果覆盖的查询：标题和正文命中、多个结果、带筛选和分页的请求、无结果
+const GOLDEN_QUERIES: &[&str] = &[
+    r#"{"query":"安装"}"#,
+    r#"{"query":"rust"}"#,
+    r#"{"query":"docker 部署"}"#,
+    r#"{"query":"rust","page_size":1,"page":2}"#,
+    r#"{"query":"不存在的词"}"#,
+];
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../utils-common/tests/fixtures")
+}
+
+/// 重新生成样本文件，多个测试共用同一样本，只生成一次
+static GENERATE: Once = Once::new();
+
+fn fixture_path(version: u8) -> PathBuf {
+    fixtures_dir().join(format!("search-v{}.bin", version))
+}
+
+/// 添加了样本文章的构建器
+fn fixture_builder() -> SearchBuilder {
+    let articles = std::fs::read_to_string(fixtures_dir().join("articles.json")).unwrap();
+    let articles: Vec<ArticleMetadata> = serde_json::from_str(&articles).unwrap();
+    let mut builder = SearchBuilder::new();
+    for article in articles {
+        builder.add_article(article);
+    }
+    builder
+}
+
+/// 设置 `UPDATE_GOLDEN` 时用样本文章重新生成当前写入版本的样本
+fn generate_current_fixture() {
+    GENERATE.call_once(|| {
+        if std::env::var_os("UPDATE_GOLDEN").is_none() {
+            return;
+        }
+        let builder = fixture_builder();
+        // 先写入临时文件，按文件头中的版本命名
+        let temp_path = fixtures_dir().join("search-current.tmp");
+        builder.save_search_index(temp_path.to_str().unwrap()).unwrap();
+        let data = std::fs::read(&temp_path).unwrap();
+        let [version, _] = validate_compressed_data_with_max_version(&data, MAX_INDEX_VERSION).unwrap();
+        std::fs::rename(&temp_path, fixture_path(version)).unwrap();
+    });
+}
+
+/// 读取当前版本的搜索索引样本
+fn search_fixture() -> Vec<u8> {
+    generate_current_fixture();
+    let path = fixture_path(MAX_INDEX_VERSION);
+    std::fs::read(&path).unwrap_or_else(|e| panic!("无法读取样本文件 {}: {}", path.display(), e))
+}
+
+/// 读取支持范围内每个版本的搜索索引样本，按版本升序，缺少任一版本的样本时失败
+fn search_fixtures() -> Vec<(u8, Vec<u8>)> {
+    generate_current_fixture();
+    (MIN_INDEX_VERSION..=MAX_INDEX_VERSION)
+        .map(|version| {
+            let path = fixture_path(version);
+            let data = std::fs::read(&path)
+                .unwrap_or_else(|e| panic!("缺少支持版本 v{} 的样本 {}: {}", version, path.display(), e));
+            (version, data)
+        })
+        .collect()
+}
+
+/// 检入的样本文件对应的版本
+fn fixture_versions() -> Vec<u8> {
+    let mut versions: Vec<u8> = std::fs::read_dir(fixtures_dir()).unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix("search-v")?.strip_suffix(".bin")?.parse().ok()
+        })
+        .collect();
+    versions.sort_unstable();
+    versions
+}
+
+
+#[test]
+fn decodes_search_index_v15() {
+    let data = search_fixture();
+
+    assert_eq!(validate_compressed_data_with_max_version(&data, MAX_INDEX_VERSION).unwrap(), [15, 0]);
+    let metadata = read_index_metadata(&data, MAX_INDEX_VERSION).unwrap().expect("缺少索引元数据头部");
+    assert_eq!(metadata.index_type, IndexType::Search);
+    assert_eq!(metadata.article_count, 3);
+
+    let index = decode_index(&data).unwrap();
+    assert_eq!(index.ordinals.len(), 3);
+    assert!(index.ordinal("articles/docker").is_some());
+    // 文章以紧凑文章表写入，还原后与构建时的文章相同
+    assert_eq!(index.articles, fixture_builder().build_search_index().unwrap().articles);
+}
+
+#[test]
+fn searches_search_index_v15() {
+    let data = search_fixture();
+
+    let result = search_wasm::search_articles(&data, r#"{"query":"安装"}"#).unwrap();
+    let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let mut ids: Vec<&str> = result["items"].as_array().unwrap()
+        .iter()
+        .map(|item| item["id"].as_str().unwrap())
+        .collect();
+    ids.sort();
+    assert_eq!(ids, ["articles/rust", "articles/wasm"]);
+}
+
+#[test]
+fn rejects_versions_below_minimum() {
+    let mut data = search_fixture();
+    // 版本号紧跟在魔数之后
+    data[utils_common::compression::MAGIC_BYTES.len()] = MIN_INDEX_VERSION - 1;
+
+    let error = decode_index(&data).unwrap_err();
+    assert!(error.contains("请重新生成索引"), "{}", error);
+}
+
+#[test]
+fn supported_versions_match_golden_results() {
+    // 关闭计时，结果中不包含每次都会变化的耗时
+    search_wasm::set_timing(false);
+    for (version, data) in search_fixtures() {
+        assert_eq!(validate_compressed_data_with_max_version(&data, MAX_INDEX_VERSION).unwrap(), [version, 0]);
+        let index = from_compressed_with_version_range::<ArticleSearchIndex>(&data, MIN_INDEX_VERSION, MAX_INDEX_VERSION)
+            .unwrap_or_else(|e| panic!("无法解码 v{} 样本: {}", version, e));
+        index.validate_ordinals().unwrap();
+
+        let results: serde_json::Map<String, Value> = GOLDEN_QUERIES.iter()
+            .map(|request| {
+                let result = search_wasm::search_articles(&data, request)
+                    .unwrap_or_else(|_| panic!("v{} 样本搜索失败: {}", version, request));
+                (request.to_string(), serde_json::from_str::<Value>(&result).unwrap())
+            })
+            .collect();
+
+        let path = fixtures_dir().join(format!("search-v{}.results.json", version));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, serde_json::to_string_pretty(&results).unwrap() + "\n").unwrap();
+        }
+        let expected: serde_json::Map<String, Value> = serde_json::from_str(
+            &std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("无法读取预期结果 {}: {}", path.display(), e)),
+        ).unwrap();
+        for request in GOLDEN_QUERIES {
+            assert_eq!(results[*request], expected[*request], "v{} 样本的搜索结果与预期不同: {}", version, request);
+        }
+    }
+}
+
+#[test]
+fn fixtures_cover_supported_versions() {
+    generate_current_fixture();
+    let supported: Vec<u8> = (MIN_INDEX_VERSION..=MAX_INDEX_VERSION).collect();
+    assert_eq!(fixture_versions(), supported, "样本版本与支持的版本范围不一致，新增缺少的样本或删除过旧的样本");
+    for version in supported {
+        let path = fixtures_dir().join(format!("search-v{}.results.json", version));
+        assert!(path.exists(), "缺少 v{} 样本的预期结果 {}", version, path.display());
+    }
+}
+
+/// 转换为与集合迭代顺序无关的 JSON 值，便于比较两个索引的内容
+fn normalized(index: &ArticleSearchIndex) -> Value {
+    fn sort_arrays(value: &mut Value) {
+        match value {
+            Value::Array(items) => {
+                items.iter_mut().for_each(sort_arrays);
+                items.sort_by_key(|item| item.to_string());
+            }
+            Value::Object(fields) => fields.values_mut().for_each(sort_arrays),
+            _ => {}
+        }
+    }
+    let mut value = serde_json::to_value(index).unwrap();
+    sort_arrays(&mut value);
+    value
+}
+
+#[test]
+fn current_layout_matches_current_version_fixture() {
+    let decoded = decode_index(&search_fixture()).unwrap();
+    let built = fixture_builder().build_search_index().unwrap();
+
+    // 修改索引结构后旧样本仍能解码时，解码结果会与新构建的索引不同
+    assert_eq!(
+        normalized(&decoded), normalized(&built),
+        "搜索索引结构已变化，请提升 INDEX_VERSION 并设置 UPDATE_GOLDEN=1 生成新版本的样本",
+    );
+}
+
+#[test]
+fn round_trips_through_messagepack() {
+    search_wasm::set_timing(false);
+    let mut builder = fixture_builder();
+    let built = builder.build_search_index().unwrap();
+    builder.set_format(SerializationFormat::MessagePack);
+    let path = std::env::temp_dir().join(format!("search-msgpack-{}.bin", std::process::id()));
+    builder.save_search_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(read_format(&data, MAX_INDEX_VERSION).unwrap(), SerializationFormat::MessagePack);
+    assert_eq!(normalized(&decode_index(&data).unwrap()), normalized(&built));
+
+    // 与检入的默认格式样本搜索结果相同
+    let fixture = search_fixture();
+    for request in GOLDEN_QUERIES {
+        let result: Value = serde_json::from_str(&search_wasm::search_articles(&data, request).unwrap()).unwrap();
+        let expected: Value = serde_json::from_str(&search_wasm::search_articles(&fixture, request).unwrap()).unwrap();
+        assert_eq!(result, expected, "MessagePack 索引的搜索结果与预期不同: {}", request);
+    }
+}
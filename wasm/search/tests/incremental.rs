@@ -0,0 +1,104 @@
+//! 增量更新测试 - 在已有索引上修改、删除和新增文章后，搜索结果与用同样的文章
+//! 完整重建的索引相同，且已删除文章的位置在保存时被压缩。
+
+use std::path::PathBuf;
+use search_wasm::builder::SearchBuilder;
+use search_wasm::engine::decode_index;
+use serde_json::{json, Value};
+use utils_common::models::ArticleMetadata;
+
+/// 覆盖修改、删除和新增文章的查询
+const QUERIES: &[&str] = &[
+    r#"{"query":"rust"}"#,
+    r#"{"query":"部署"}"#,
+    r#"{"query":"nginx"}"#,
+    r#"{"query":"缓存"}"#,
+    r#"{"query":"docker"}"#,
+];
+
+fn article(id: &str, title: &str, content: &str, tags: &[&str]) -> ArticleMetadata {
+    serde_json::from_value(json!({
+        "id": id,
+        "title": title,
+        "summary": "",
+        "date": "2024-01-10T08:00:00Z",
+        "tags": tags,
+        "url": format!("/{}", id),
+        "content": content,
+        "lang": "zh",
+    }))
+    .unwrap()
+}
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("search-incremental-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn save(builder: &SearchBuilder, path: &PathBuf) -> Vec<u8> {
+    builder.save_search_index(path.to_str().unwrap()).unwrap();
+    std::fs::read(path).unwrap()
+}
+
+/// 搜索结果中的文章，按文章ID排序，不受文章位置的影响
+fn results(data: &[u8], request: &str) -> Vec<Value> {
+    let result = search_wasm::search_articles(data, request).unwrap_or_else(|_| panic!("搜索失败: {}", request));
+    let result: Value = serde_json::from_str(&result).unwrap();
+    let mut items = result["items"].as_array().unwrap().clone();
+    items.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+    items
+}
+
+#[test]
+fn incremental_build_matches_full_rebuild() {
+    search_wasm::set_timing(false);
+    let dir = temp_dir();
+
+    let rust = article("notes/rust", "Rust 入门", "使用 rustup 安装 Rust 工具链，rust 编译器。", &["rust"]);
+    let docker = article("notes/docker", "Docker 部署", "使用 docker compose 部署服务，docker 镜像。", &["docker"]);
+    let nginx = article("notes/nginx", "Nginx 配置", "nginx 反向代理 部署 网站，nginx 缓存。", &["nginx"]);
+    let redis = article("notes/redis", "Redis 缓存", "redis 缓存 数据，缓存 过期。", &["redis"]);
+    let docker_changed = article("notes/docker", "Docker 部署 Rust 服务", "用 docker 部署 rust 服务，docker 多阶段构建。", &["docker", "rust"]);
+    let wasm = article("notes/wasm", "Rust 与 WebAssembly", "rust 编译 为 wasm，在浏览器 运行 rust。", &["rust", "wasm"]);
+
+    let mut builder = SearchBuilder::new();
+    for article in [&rust, &docker, &nginx, &redis] {
+        builder.add_article(article.clone());
+    }
+    let base_path = dir.join("base.bin");
+    save(&builder, &base_path);
+
+    // 在已有索引上修改、删除和新增文章，未变化的文章不重新索引
+    let mut builder = SearchBuilder::load_existing(base_path.to_str().unwrap()).unwrap();
+    assert!(!builder.update_article(rust.clone()).unwrap());
+    assert!(builder.update_article(docker_changed.clone()).unwrap());
+    assert!(builder.remove_article("notes/nginx").unwrap());
+    assert!(!builder.remove_article("notes/nginx").unwrap());
+    builder.add_article(wasm.clone());
+
+    let mut ids = builder.article_ids();
+    ids.sort();
+    assert_eq!(ids, ["notes/docker", "notes/redis", "notes/rust", "notes/wasm"]);
+
+    // 已删除和被替换的文章不留下位置
+    let incremental = save(&builder, &dir.join("incremental.bin"));
+    let index = decode_index(&incremental).unwrap();
+    assert!(index.tombstones.is_empty());
+    assert_eq!(index.articles.len(), 4);
+    index.validate_ordinals().unwrap();
+
+    let mut builder = SearchBuilder::new();
+    for article in [&rust, &docker_changed, &redis, &wasm] {
+        builder.add_article(article.clone());
+    }
+    let full = save(&builder, &dir.join("full.bin"));
+
+    for request in QUERIES {
+        assert_eq!(results(&incremental, request), results(&full, request), "增量更新与完整重建的搜索结果不同: {}", request);
+    }
+    assert_eq!(results(&incremental, r#"{"query":"rust"}"#).len(), 3);
+    assert!(results(&incremental, r#"{"query":"nginx"}"#).is_empty());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,77 @@
+//! 相关文章测试 - 标签重复的文章不影响标签重合度的计算；运行时增删文章后
+//! `get_related` 的结果随之更新。
+
+use search_wasm::builder::SearchBuilder;
+use serde_json::{json, Value};
+use utils_common::models::ArticleMetadata;
+
+fn article(id: &str, title: &str, tags: &[&str]) -> ArticleMetadata {
+    serde_json::from_value(json!({
+        "id": id,
+        "title": title,
+        "summary": "",
+        "date": "2024-01-10T08:00:00Z",
+        "tags": tags,
+        "url": format!("/{}", id),
+        "content": "",
+        "lang": "zh",
+    }))
+    .unwrap()
+}
+
+#[test]
+fn duplicate_tags_count_once() {
+    let mut builder = SearchBuilder::new();
+    builder.add_article(article("notes/a", "甲", &["rust", "rust", "rust"]));
+    builder.add_article(article("notes/b", "乙", &["rust", "rust", "rust"]));
+    builder.add_article(article("notes/c", "丙", &["rust", "wasm"]));
+    let index = builder.build_search_index().unwrap();
+
+    // 去重后 a 与 b 的标签完全相同，比只共享一半标签的 c 更相关
+    let a = index.ordinal("notes/a").unwrap();
+    let related: Vec<&str> = index.related[a].iter()
+        .map(|related| index.articles[related.article].id.as_str())
+        .collect();
+    assert_eq!(related, ["notes/b", "notes/c"]);
+    assert!(index.related[a][0].score > index.related[a][1].score);
+}
+
+/// `get_related` 返回的文章ID
+fn related_ids(article_id: &str) -> Vec<String> {
+    let items: Value = serde_json::from_str(&search_wasm::get_related(article_id).unwrap()).unwrap();
+    items.as_array().unwrap().iter()
+        .map(|item| item["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn runtime_changes_update_related() {
+    search_wasm::set_timing(false);
+    let mut builder = SearchBuilder::new();
+    builder.add_article(article("notes/a", "甲", &["rust", "wasm"]));
+    builder.add_article(article("notes/b", "乙", &["docker"]));
+    let path = std::env::temp_dir().join(format!("search-related-{}.bin", std::process::id()));
+    builder.save_search_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    search_wasm::load_search_index(&data, None).unwrap();
+    assert!(related_ids("notes/a").is_empty());
+
+    // 新增的文章出现在已有文章的相关文章中，新文章也有相关文章
+    let added = article("notes/c", "丙", &["rust", "wasm"]);
+    assert!(search_wasm::upsert_article(&serde_json::to_string(&added).unwrap(), None).unwrap());
+    assert_eq!(related_ids("notes/a"), ["notes/c"]);
+    assert_eq!(related_ids("notes/c"), ["notes/a"]);
+
+    // 修改标签后按新的标签计算
+    let changed = article("notes/c", "丙", &["docker"]);
+    assert!(search_wasm::upsert_article(&serde_json::to_string(&changed).unwrap(), None).unwrap());
+    assert!(related_ids("notes/a").is_empty());
+    assert_eq!(related_ids("notes/b"), ["notes/c"]);
+    assert_eq!(related_ids("notes/c"), ["notes/b"]);
+
+    assert!(search_wasm::remove_article("notes/c", None).unwrap());
+    assert!(related_ids("notes/b").is_empty());
+    assert!(related_ids("notes/c").is_empty());
+}
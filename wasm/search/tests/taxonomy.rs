@@ -0,0 +1,81 @@
+//! 标签和分类筛选测试 - 请求中的标签、分类和查询中的 `tag:`、`category:` 只保留
+//! 对应的文章，不指定时搜索结果不变。
+
+use search_wasm::builder::SearchBuilder;
+use serde_json::{json, Value};
+use utils_common::models::ArticleMetadata;
+
+fn article(id: &str, title: &str, content: &str, tags: &[&str]) -> ArticleMetadata {
+    serde_json::from_value(json!({
+        "id": id,
+        "title": title,
+        "summary": "",
+        "date": "2024-01-10T08:00:00Z",
+        "tags": tags,
+        "url": format!("/{}", id),
+        "content": content,
+        "lang": "zh",
+    }))
+    .unwrap()
+}
+
+fn index() -> Vec<u8> {
+    let mut builder = SearchBuilder::new();
+    builder.add_article(article("articles/web/nginx", "Nginx 部署", "nginx 部署 网站。", &["nginx", "运维"]));
+    builder.add_article(article("articles/web/docker", "Docker 部署", "docker 部署 服务。", &["docker", "运维"]));
+    builder.add_article(article("articles/rust/wasm", "Rust 部署到浏览器", "rust 编译 为 wasm 后 部署。", &["rust", "wasm"]));
+    builder.add_article(article("notes/deploy", "部署笔记", "部署 清单。", &["运维"]));
+
+    let path = std::env::temp_dir().join(format!("search-taxonomy-{}.bin", std::process::id()));
+    builder.save_search_index(path.to_str().unwrap()).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    data
+}
+
+/// 搜索结果中的文章ID，按ID排序
+fn result_ids(data: &[u8], request: Value) -> Vec<String> {
+    let result = search_wasm::search_articles(data, &request.to_string()).unwrap();
+    let result: Value = serde_json::from_str(&result).unwrap();
+    let mut ids: Vec<String> = result["items"].as_array().unwrap().iter()
+        .map(|item| item["id"].as_str().unwrap().to_string())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn filters_results_by_tag_and_category() {
+    search_wasm::set_timing(false);
+    let data = index();
+
+    let all = result_ids(&data, json!({"query": "部署"}));
+    assert_eq!(all, ["articles/rust/wasm", "articles/web/docker", "articles/web/nginx", "notes/deploy"]);
+    // 空的标签列表不筛选
+    assert_eq!(result_ids(&data, json!({"query": "部署", "tags": []})), all);
+
+    assert_eq!(result_ids(&data, json!({"query": "部署", "tags": ["运维"]})),
+        ["articles/web/docker", "articles/web/nginx", "notes/deploy"]);
+    // 多个标签需要同时带有
+    assert_eq!(result_ids(&data, json!({"query": "部署", "tags": ["运维", "docker"]})), ["articles/web/docker"]);
+    assert!(result_ids(&data, json!({"query": "部署", "tags": ["不存在"]})).is_empty());
+
+    // 分类包括下级目录
+    assert_eq!(result_ids(&data, json!({"query": "部署", "category": "articles"})),
+        ["articles/rust/wasm", "articles/web/docker", "articles/web/nginx"]);
+    assert_eq!(result_ids(&data, json!({"query": "部署", "category": "articles/web", "tags": ["nginx"]})),
+        ["articles/web/nginx"]);
+}
+
+#[test]
+fn parses_scope_from_query() {
+    search_wasm::set_timing(false);
+    let data = index();
+
+    assert_eq!(result_ids(&data, json!({"query": "tag:运维 部署"})),
+        result_ids(&data, json!({"query": "部署", "tags": ["运维"]})));
+    assert_eq!(result_ids(&data, json!({"query": "部署 category:articles/web"})),
+        ["articles/web/docker", "articles/web/nginx"]);
+    // 查询中的条件与请求中的条件同时生效
+    assert_eq!(result_ids(&data, json!({"query": "tag:运维 部署", "category": "notes"})), ["notes/deploy"]);
+}
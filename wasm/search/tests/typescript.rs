@@ -0,0 +1,40 @@
+//! TypeScript类型定义测试 - 检查 `src/types/wasm` 中检入的类型定义与Rust模型一致
+//!
+//! 类型定义由 ts-rs 根据模型生成，前端直接引用，修改请求或结果结构后前端类型随之更新。
+//! 修改模型后设置环境变量 `UPDATE_GOLDEN=1` 运行测试重新生成类型定义，再检入生成的文件。
+
+use std::path::{Path, PathBuf};
+use search_wasm::models::{SearchRequest, SearchResult, SearchSuggestion};
+use ts_rs::TS;
+
+fn types_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../src/types/wasm")
+}
+
+/// 把类型及其依赖的类型导出到目录
+fn export_all(dir: &Path) {
+    SearchRequest::export_all_to(dir).unwrap();
+    SearchResult::export_all_to(dir).unwrap();
+    SearchSuggestion::export_all_to(dir).unwrap();
+}
+
+#[test]
+fn typescript_definitions_are_up_to_date() {
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        export_all(&types_dir());
+    }
+
+    let generated = std::env::temp_dir().join(format!("search-wasm-types-{}", std::process::id()));
+    export_all(&generated);
+    for entry in std::fs::read_dir(&generated).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap();
+        let expected = std::fs::read_to_string(&path).unwrap();
+        let checked_in = std::fs::read_to_string(types_dir().join(name)).unwrap_or_default();
+        assert_eq!(
+            checked_in, expected,
+            "{:?} 与模型不一致，设置 UPDATE_GOLDEN=1 运行测试重新生成", name
+        );
+    }
+    let _ = std::fs::remove_dir_all(&generated);
+}
@@ -0,0 +1,347 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use crate::models::{ArticleMetadata, Heading, Visibility};
+
+/// 文本片段 - 指向文本块中的一段字节区间
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct TextSpan {
+    /// 起始字节偏移
+    start: u32,
+    /// 字节长度
+    len: u32,
+}
+
+/// 标题记录 - 文本存放在文章表的文本块中
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+struct HeadingRecord {
+    level: u32,
+    text: TextSpan,
+    position: u32,
+    end_position: Option<u32>,
+    raw_text: Option<TextSpan>,
+}
+
+/// 文章记录 - 只保存偏移量和定长字段
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ArticleRecord {
+    id: TextSpan,
+    title: TextSpan,
+    summary: TextSpan,
+    date: DateTime<Utc>,
+    /// 标签在 `tags` 列表中的起始位置和数量
+    tags: (u32, u32),
+    url: TextSpan,
+    content: TextSpan,
+    page_type: TextSpan,
+    /// 标题在 `headings` 列表中的起始位置和数量
+    headings: (u32, u32),
+    lang: TextSpan,
+    media_text: TextSpan,
+    visibility: Visibility,
+    featured: bool,
+    series: Option<TextSpan>,
+    author: Option<TextSpan>,
+    word_count: u32,
+}
+
+/// 紧凑文章表 - 所有字符串字段存放在同一个文本块中
+///
+/// 反序列化时只需分配一个大字符串和几个定长数组，而不是为每篇文章的
+/// 每个字段单独分配字符串，适合在移动端快速解码大量文章。
+/// 重复出现的短文本（标签、页面类型、系列名称、作者）在文本块中只保存一份。
+/// 从文件解码后应先调用 `validate` 检查各区间，访问器不会因越界的区间而崩溃，
+/// 但越界的字段读取为空。
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ArticleTable {
+    /// 文本块
+    text: String,
+    /// 文章记录
+    records: Vec<ArticleRecord>,
+    /// 所有文章的标签片段
+    tags: Vec<TextSpan>,
+    /// 所有文章的标题记录
+    headings: Vec<HeadingRecord>,
+}
+
+impl ArticleTable {
+    /// 从文章列表构建紧凑文章表
+    pub fn from_articles(articles: &[ArticleMetadata]) -> Self {
+        let mut table = ArticleTable::default();
+        let mut interned: HashMap<String, TextSpan> = HashMap::new();
+
+        for article in articles {
+            let id = table.push_text(&article.id);
+            let title = table.push_text(&article.title);
+            let summary = table.push_text(&article.summary);
+            let url = table.push_text(&article.url);
+            let content = table.push_text(&article.content);
+            let page_type = table.intern_text(&article.page_type, &mut interned);
+            let lang = table.intern_text(&article.lang, &mut interned);
+            let media_text = table.push_text(&article.media_text);
+            let series = article.series.as_deref().map(|series| table.intern_text(series, &mut interned));
+            let author = article.author.as_deref().map(|author| table.intern_text(author, &mut interned));
+
+            let tag_start = table.tags.len() as u32;
+            for tag in &article.tags {
+                let span = table.intern_text(tag, &mut interned);
+                table.tags.push(span);
+            }
+
+            let heading_start = table.headings.len() as u32;
+            for heading in &article.headings {
+                let text = table.push_text(&heading.text);
+                let raw_text = heading.raw_text.as_deref().map(|raw| table.push_text(raw));
+                table.headings.push(HeadingRecord {
+                    level: heading.level as u32,
+                    text,
+                    position: heading.position as u32,
+                    end_position: heading.end_position.map(|p| p as u32),
+                    raw_text,
+                });
+            }
+
+            table.records.push(ArticleRecord {
+                id,
+                title,
+                summary,
+                date: article.date,
+                tags: (tag_start, article.tags.len() as u32),
+                url,
+                content,
+                page_type,
+                headings: (heading_start, article.headings.len() as u32),
+                lang,
+                media_text,
+                visibility: article.visibility,
+                featured: article.featured,
+                series,
+                author,
+                word_count: article.word_count as u32,
+            });
+        }
+
+        table
+    }
+
+    /// 文章数量
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// 文章表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// 按位置获取文章视图
+    pub fn get(&self, index: usize) -> Option<ArticleRef<'_>> {
+        self.records.get(index).map(|record| ArticleRef { table: self, record })
+    }
+
+    /// 遍历所有文章视图
+    pub fn iter(&self) -> impl Iterator<Item = ArticleRef<'_>> {
+        self.records.iter().map(move |record| ArticleRef { table: self, record })
+    }
+
+    /// 检查所有文本区间、标签和标题区间都在范围内，文本区间落在字符边界上
+    pub fn validate(&self) -> Result<(), io::Error> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let check_text = |span: TextSpan| -> bool {
+            let start = span.start as usize;
+            start.checked_add(span.len as usize)
+                .is_some_and(|end| self.text.get(start..end).is_some())
+        };
+        if !self.tags.iter().all(|span| check_text(*span)) {
+            return Err(invalid("文章表中的标签超出文本范围".to_string()));
+        }
+        for heading in &self.headings {
+            if !check_text(heading.text) || !heading.raw_text.is_none_or(check_text) {
+                return Err(invalid("文章表中的标题超出文本范围".to_string()));
+            }
+        }
+
+        for (position, record) in self.records.iter().enumerate() {
+            let spans = [record.id, record.title, record.summary, record.url, record.content, record.page_type, record.lang, record.media_text];
+            let valid = spans.into_iter().chain(record.series).chain(record.author).all(check_text)
+                && range(record.tags, self.tags.len()).is_some()
+                && range(record.headings, self.headings.len()).is_some();
+            if !valid {
+                return Err(invalid(format!("文章表中第 {} 篇文章的记录超出范围", position)));
+            }
+        }
+        Ok(())
+    }
+
+    /// 还原为普通的文章元数据列表
+    pub fn to_articles(&self) -> Vec<ArticleMetadata> {
+        self.iter().map(|article| article.to_metadata()).collect()
+    }
+
+    // 追加文本到文本块
+    fn push_text(&mut self, text: &str) -> TextSpan {
+        let span = TextSpan {
+            start: self.text.len() as u32,
+            len: text.len() as u32,
+        };
+        self.text.push_str(text);
+        span
+    }
+
+    // 追加文本到文本块，相同文本只保存一份
+    fn intern_text(&mut self, text: &str, interned: &mut HashMap<String, TextSpan>) -> TextSpan {
+        if let Some(span) = interned.get(text) {
+            return *span;
+        }
+        let span = self.push_text(text);
+        interned.insert(text.to_string(), span);
+        span
+    }
+
+    // 读取文本片段，超出范围或不在字符边界上时为空
+    fn slice(&self, span: TextSpan) -> &str {
+        let start = span.start as usize;
+        self.text.get(start..start + span.len as usize).unwrap_or("")
+    }
+}
+
+// 起始位置和数量表示的区间，超出 `len` 时返回None
+fn range((start, count): (u32, u32), len: usize) -> Option<std::ops::Range<usize>> {
+    let start = start as usize;
+    let end = start.checked_add(count as usize)?;
+    (end <= len).then_some(start..end)
+}
+
+impl From<&[ArticleMetadata]> for ArticleTable {
+    fn from(articles: &[ArticleMetadata]) -> Self {
+        Self::from_articles(articles)
+    }
+}
+
+/// 文章视图 - 借用文章表中的数据，字段访问方式与 `ArticleMetadata` 一致
+#[derive(Clone, Copy)]
+pub struct ArticleRef<'a> {
+    table: &'a ArticleTable,
+    record: &'a ArticleRecord,
+}
+
+impl<'a> ArticleRef<'a> {
+    /// 文章唯一标识符
+    pub fn id(&self) -> &'a str {
+        self.table.slice(self.record.id)
+    }
+
+    /// 文章标题
+    pub fn title(&self) -> &'a str {
+        self.table.slice(self.record.title)
+    }
+
+    /// 文章摘要
+    pub fn summary(&self) -> &'a str {
+        self.table.slice(self.record.summary)
+    }
+
+    /// 发布日期
+    pub fn date(&self) -> DateTime<Utc> {
+        self.record.date
+    }
+
+    /// 文章标签列表，标签区间超出范围时为空
+    pub fn tags(&self) -> impl Iterator<Item = &'a str> + 'a {
+        let table = self.table;
+        range(self.record.tags, table.tags.len())
+            .and_then(|range| table.tags.get(range))
+            .unwrap_or_default()
+            .iter()
+            .map(move |span| table.slice(*span))
+    }
+
+    /// 文章URL路径
+    pub fn url(&self) -> &'a str {
+        self.table.slice(self.record.url)
+    }
+
+    /// 文章内容
+    pub fn content(&self) -> &'a str {
+        self.table.slice(self.record.content)
+    }
+
+    /// 页面类型
+    pub fn page_type(&self) -> &'a str {
+        self.table.slice(self.record.page_type)
+    }
+
+    /// 文章的主要语言
+    pub fn lang(&self) -> &'a str {
+        self.table.slice(self.record.lang)
+    }
+
+    /// 媒体说明文本
+    pub fn media_text(&self) -> &'a str {
+        self.table.slice(self.record.media_text)
+    }
+
+    /// 文章可见性
+    pub fn visibility(&self) -> Visibility {
+        self.record.visibility
+    }
+
+    /// 是否为置顶文章
+    pub fn featured(&self) -> bool {
+        self.record.featured
+    }
+
+    /// 文章所属的系列名称
+    pub fn series(&self) -> Option<&'a str> {
+        self.record.series.map(|span| self.table.slice(span))
+    }
+
+    /// 文章作者
+    pub fn author(&self) -> Option<&'a str> {
+        self.record.author.map(|span| self.table.slice(span))
+    }
+
+    /// 正文词数
+    pub fn word_count(&self) -> usize {
+        self.record.word_count as usize
+    }
+
+    /// 文章中的标题结构，标题区间超出范围时为空
+    pub fn headings(&self) -> impl Iterator<Item = Heading> + 'a {
+        let table = self.table;
+        range(self.record.headings, table.headings.len())
+            .and_then(|range| table.headings.get(range))
+            .unwrap_or_default()
+            .iter()
+            .map(move |heading| Heading {
+                level: heading.level as usize,
+                text: table.slice(heading.text).to_string(),
+                position: heading.position as usize,
+                end_position: heading.end_position.map(|p| p as usize),
+                raw_text: heading.raw_text.map(|span| table.slice(span).to_string()),
+            })
+    }
+
+    /// 复制为独立的文章元数据
+    pub fn to_metadata(&self) -> ArticleMetadata {
+        ArticleMetadata {
+            id: self.id().to_string(),
+            title: self.title().to_string(),
+            summary: self.summary().to_string(),
+            date: self.date(),
+            tags: self.tags().map(|tag| tag.to_string()).collect(),
+            url: self.url().to_string(),
+            content: self.content().to_string(),
+            page_type: self.page_type().to_string(),
+            headings: self.headings().collect(),
+            lang: self.lang().to_string(),
+            media_text: self.media_text().to_string(),
+            visibility: self.visibility(),
+            featured: self.featured(),
+            series: self.series().map(|series| series.to_string()),
+            author: self.author().map(|author| author.to_string()),
+            word_count: self.word_count(),
+        }
+    }
+}
@@ -1,21 +1,82 @@
 use std::io::{self, Read};
 use flate2::{Compression, write::GzEncoder, read::GzDecoder};
+use serde::{Deserialize, Serialize};
+use crate::models::IndexMetadata;
 
 /// 魔数常量 - 用于标识文件格式
-pub const MAGIC_BYTES: &'static [u8] = b"NECMP"; // NewEchoes Compressed
+pub const MAGIC_BYTES: &[u8] = b"NECMP"; // NewEchoes Compressed
+
+/// 带头部格式的魔数 - 压缩数据前有未压缩的头部（如索引元数据）
+pub const HEADER_MAGIC_BYTES: &[u8] = b"NECMH"; // NewEchoes Compressed with Header
+
+/// 带格式字节的魔数 - 版本号之后有一个字节标识序列化格式，用于bincode以外的格式
+pub const FORMAT_MAGIC_BYTES: &[u8] = b"NECMF"; // NewEchoes Compressed with Format
+
+/// 压缩数据的序列化格式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SerializationFormat {
+    /// bincode - 体积小、解码快，但数据与Rust模型的字段顺序绑定（默认）
+    #[default]
+    #[serde(rename = "bincode")]
+    Bincode,
+    /// MessagePack - 自描述格式，字段按名称编码，可以直接查看内容，也可以用其他语言的
+    /// MessagePack库读取
+    #[serde(rename = "msgpack")]
+    MessagePack,
+}
+
+impl SerializationFormat {
+    /// 写入数据帧的格式字节
+    pub fn byte(self) -> u8 {
+        match self {
+            SerializationFormat::Bincode => 0,
+            SerializationFormat::MessagePack => 1,
+        }
+    }
+
+    /// 从格式字节解析格式
+    pub fn from_byte(byte: u8) -> Result<Self, io::Error> {
+        match byte {
+            0 => Ok(SerializationFormat::Bincode),
+            1 => Ok(SerializationFormat::MessagePack),
+            other => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("不支持的序列化格式: {}", other)
+            )),
+        }
+    }
+
+    /// 按该格式序列化对象
+    pub fn encode<T: serde::Serialize>(self, obj: &T) -> Result<Vec<u8>, io::Error> {
+        match self {
+            SerializationFormat::Bincode => to_binary(obj),
+            SerializationFormat::MessagePack => rmp_serde::to_vec_named(obj)
+                .map_err(|e| io::Error::other(format!("序列化失败: {}", e))),
+        }
+    }
+
+    /// 按该格式反序列化对象
+    pub fn decode<T: for<'a> serde::de::Deserialize<'a>>(self, data: &[u8]) -> Result<T, io::Error> {
+        match self {
+            SerializationFormat::Bincode => from_binary(data),
+            SerializationFormat::MessagePack => rmp_serde::from_slice(data)
+                .map_err(|e| io::Error::other(format!("反序列化失败: {}", e))),
+        }
+    }
+}
 
 /// 将对象序列化为二进制格式
 pub fn to_binary<T: serde::Serialize>(obj: &T) -> Result<Vec<u8>, io::Error> {
     // 直接使用bincode标准配置序列化原始对象
     bincode::serde::encode_to_vec(obj, bincode::config::standard())
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("序列化失败: {}", e)))
+        .map_err(|e| io::Error::other(format!("序列化失败: {}", e)))
 }
 
 /// 从二进制格式反序列化对象
 pub fn from_binary<T: for<'a> serde::de::Deserialize<'a>>(data: &[u8]) -> Result<T, io::Error> {
     // 使用bincode标准配置从二进制数据反序列化对象
     bincode::serde::decode_from_slice(data, bincode::config::standard())
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("反序列化失败: {}", e)))
+        .map_err(|e| io::Error::other(format!("反序列化失败: {}", e)))
         .map(|(value, _)| value)
 }
 
@@ -31,67 +92,50 @@ pub fn to_compressed<T: serde::Serialize>(obj: &T, version: [u8; 2]) -> Result<V
     // 写入版本号
     output.extend_from_slice(&version);
     
-    // 写入原始数据大小
-    let data_len = (binary.len() as u32).to_le_bytes();
-    output.extend_from_slice(&data_len);
-    
-    // 压缩数据
-    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
-    std::io::Write::write_all(&mut encoder, &binary)?;
-    let compressed_data = encoder.finish()?;
-    
-    // 添加压缩后的数据
-    output.extend_from_slice(&compressed_data);
+    // 写入原始数据大小和压缩数据
+    output.extend_from_slice(&compress_payload(&binary)?);
     
     Ok(output)
 }
 
+/// 压缩序列化后的数据，返回原始数据大小（u32）和压缩数据
+fn compress_payload(binary: &[u8]) -> Result<Vec<u8>, io::Error> {
+    let mut output = Vec::with_capacity(binary.len() / 2);
+    output.extend_from_slice(&(binary.len() as u32).to_le_bytes());
+    
+    let mut encoder = GzEncoder::new(output, Compression::best());
+    std::io::Write::write_all(&mut encoder, binary)?;
+    encoder.finish()
+}
+
 /// 从压缩的二进制格式反序列化对象，使用默认最大版本4
 pub fn from_compressed<T: for<'a> serde::de::Deserialize<'a>>(data: &[u8]) -> Result<T, io::Error> {
     from_compressed_with_max_version(data, 4)
 }
 
 /// 从压缩的二进制格式反序列化对象，允许指定支持的最大版本
+///
+/// 带头部和不带头部的格式都可以读取，头部会被跳过。
 pub fn from_compressed_with_max_version<T: for<'a> serde::de::Deserialize<'a>>(
     data: &[u8], 
     max_version: u8
 ) -> Result<T, io::Error> {
-    // 检查数据长度是否足够
-    if data.len() < MAGIC_BYTES.len() + 2 + 4 {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("数据太短，无法解析: {} 字节", data.len())
-        ));
-    }
-
-    // 验证魔数
-    if &data[0..MAGIC_BYTES.len()] != MAGIC_BYTES {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "无效的文件格式：魔数不匹配"
-        ));
-    }
+    let frame = parse_frame(data, max_version)?;
+    let payload = frame.payload;
 
-    // 读取版本号
-    let version_offset = MAGIC_BYTES.len();
-    let version = [data[version_offset], data[version_offset + 1]];
-    
-    // 验证版本兼容性
-    if version[0] > max_version {
+    // 读取原始数据大小
+    if payload.len() < 4 {
         return Err(io::Error::new(
             io::ErrorKind::InvalidData,
-            format!("不支持的版本: {}.{}", version[0], version[1])
+            format!("数据太短，无法解析: {} 字节", data.len())
         ));
     }
-
-    // 读取原始数据大小
-    let size_offset = version_offset + 2;
     let mut size_bytes = [0u8; 4];
-    size_bytes.copy_from_slice(&data[size_offset..size_offset + 4]);
+    size_bytes.copy_from_slice(&payload[..4]);
     let original_size = u32::from_le_bytes(size_bytes);
 
     // 提取压缩数据
-    let compressed_data = &data[size_offset + 4..];
+    let compressed_data = &payload[4..];
 
     // 解压数据
     let mut decoder = GzDecoder::new(compressed_data);
@@ -107,27 +151,117 @@ pub fn from_compressed_with_max_version<T: for<'a> serde::de::Deserialize<'a>>(
         ));
     }
 
-    // 反序列化数据
-    from_binary(&decompressed_data)
+    // 按数据帧记录的格式反序列化数据
+    frame.format.decode(&decompressed_data)
 }
 
-/// 验证压缩数据是否有效
-pub fn validate_compressed_data(data: &[u8]) -> Result<[u8; 2], io::Error> {
-    validate_compressed_data_with_max_version(data, 4)
+/// 从压缩的二进制格式反序列化对象，只接受 `min_version` 到 `max_version` 之间的主版本
+///
+/// 低于最小版本的索引结构已经改变，直接返回要求重新生成索引的错误，不尝试解码。
+pub fn from_compressed_with_version_range<T: for<'a> serde::de::Deserialize<'a>>(
+    data: &[u8],
+    min_version: u8,
+    max_version: u8,
+) -> Result<T, io::Error> {
+    validate_compressed_data_with_version_range(data, min_version, max_version)?;
+    from_compressed_with_max_version(data, max_version)
 }
 
-/// 验证压缩数据是否有效，允许指定支持的最大版本
-pub fn validate_compressed_data_with_max_version(data: &[u8], max_version: u8) -> Result<[u8; 2], io::Error> {
+/// 将对象序列化为压缩的二进制格式，并在压缩数据前写入未压缩的头部
+///
+/// 格式为：头部魔数、版本号、头部长度（u32）、头部（bincode）、原始数据大小、压缩数据。
+/// 头部通常是索引元数据，读取时无需解压整个文件。
+pub fn to_compressed_with_header<T: serde::Serialize, H: serde::Serialize>(
+    obj: &T,
+    version: [u8; 2],
+    header: &H,
+) -> Result<Vec<u8>, io::Error> {
+    let header = to_binary(header)?;
+    let body = to_compressed(obj, version)?;
+
+    let mut output = Vec::with_capacity(body.len() + header.len() + 4);
+    output.extend_from_slice(HEADER_MAGIC_BYTES);
+    output.extend_from_slice(&version);
+    output.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    output.extend_from_slice(&header);
+    // 跳过不带头部格式的魔数和版本号，保留原始数据大小和压缩数据
+    output.extend_from_slice(&body[MAGIC_BYTES.len() + 2..]);
+    
+    Ok(output)
+}
+
+/// 按指定格式将对象序列化为压缩的二进制格式，头部（如果有）使用相同的格式
+///
+/// bincode格式写入与 `to_compressed`、`to_compressed_with_header` 相同的数据，旧版本可以读取；
+/// 其他格式的格式为：格式魔数、版本号、格式字节、头部长度（u32，0表示没有头部）、头部、
+/// 原始数据大小、压缩数据。
+pub fn to_compressed_with_format<T: serde::Serialize, H: serde::Serialize>(
+    obj: &T,
+    version: [u8; 2],
+    header: Option<&H>,
+    format: SerializationFormat,
+) -> Result<Vec<u8>, io::Error> {
+    if format == SerializationFormat::Bincode {
+        return match header {
+            Some(header) => to_compressed_with_header(obj, version, header),
+            None => to_compressed(obj, version),
+        };
+    }
+
+    let header = header.map(|header| format.encode(header)).transpose()?.unwrap_or_default();
+    let payload = compress_payload(&format.encode(obj)?)?;
+
+    let mut output = Vec::with_capacity(FORMAT_MAGIC_BYTES.len() + 7 + header.len() + payload.len());
+    output.extend_from_slice(FORMAT_MAGIC_BYTES);
+    output.extend_from_slice(&version);
+    output.push(format.byte());
+    output.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    output.extend_from_slice(&header);
+    output.extend_from_slice(&payload);
+
+    Ok(output)
+}
+
+/// 只读取压缩数据的头部，不解压数据；没有头部的旧格式返回None
+pub fn read_header<H: for<'a> serde::de::Deserialize<'a>>(data: &[u8], max_version: u8) -> Result<Option<H>, io::Error> {
+    let frame = parse_frame(data, max_version)?;
+    frame.header.map(|header| frame.format.decode(header)).transpose()
+}
+
+/// 读取压缩数据的序列化格式，不解压数据
+pub fn read_format(data: &[u8], max_version: u8) -> Result<SerializationFormat, io::Error> {
+    parse_frame(data, max_version).map(|frame| frame.format)
+}
+
+/// 读取索引文件中的索引元数据，不解压索引数据；没有头部的旧格式返回None
+pub fn read_index_metadata(data: &[u8], max_version: u8) -> Result<Option<IndexMetadata>, io::Error> {
+    read_header(data, max_version)
+}
+
+/// 解析后的数据帧
+struct Frame<'a> {
+    version: [u8; 2],
+    format: SerializationFormat,
+    header: Option<&'a [u8]>,
+    /// 原始数据大小和压缩数据
+    payload: &'a [u8],
+}
+
+/// 验证魔数和版本号，拆分出头部和压缩数据
+fn parse_frame(data: &[u8], max_version: u8) -> Result<Frame<'_>, io::Error> {
     // 检查数据长度是否足够
     if data.len() < MAGIC_BYTES.len() + 2 + 4 {
         return Err(io::Error::new(
             io::ErrorKind::InvalidData,
-            format!("数据太短，无法验证: {} 字节", data.len())
+            format!("数据太短，无法解析: {} 字节", data.len())
         ));
     }
 
     // 验证魔数
-    if &data[0..MAGIC_BYTES.len()] != MAGIC_BYTES {
+    let magic = &data[0..MAGIC_BYTES.len()];
+    let has_header = magic == HEADER_MAGIC_BYTES;
+    let has_format = magic == FORMAT_MAGIC_BYTES;
+    if magic != MAGIC_BYTES && !has_header && !has_format {
         return Err(io::Error::new(
             io::ErrorKind::InvalidData,
             "无效的文件格式：魔数不匹配"
@@ -145,6 +279,54 @@ pub fn validate_compressed_data_with_max_version(data: &[u8], max_version: u8) -
             format!("不支持的版本: {}.{}", version[0], version[1])
         ));
     }
+
+    let mut rest = &data[version_offset + 2..];
+    let format = if has_format {
+        let format = SerializationFormat::from_byte(rest[0])?;
+        rest = &rest[1..];
+        format
+    } else {
+        SerializationFormat::Bincode
+    };
+    if !has_header && !has_format {
+        return Ok(Frame { version, format, header: None, payload: rest });
+    }
+
+    // 读取头部，带格式字节的数据帧中头部长度为0表示没有头部
+    let len_bytes: [u8; 4] = rest.get(..4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "头部长度不完整"))?;
+    let header_len = u32::from_le_bytes(len_bytes) as usize;
+    // 头部长度来自数据本身，32位目标上相加可能溢出
+    let header_end = 4usize.checked_add(header_len)
+        .filter(|&end| end <= rest.len())
+        .ok_or_else(|| io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("头部不完整: 期望 {} 字节", header_len)
+        ))?;
+    let header = (has_header || header_len > 0).then_some(&rest[4..header_end]);
     
+    Ok(Frame { version, format, header, payload: &rest[header_end..] })
+}
+
+/// 验证压缩数据是否有效
+pub fn validate_compressed_data(data: &[u8]) -> Result<[u8; 2], io::Error> {
+    validate_compressed_data_with_max_version(data, 4)
+}
+
+/// 验证压缩数据是否有效，允许指定支持的最大版本
+pub fn validate_compressed_data_with_max_version(data: &[u8], max_version: u8) -> Result<[u8; 2], io::Error> {
+    parse_frame(data, max_version).map(|frame| frame.version)
+}
+
+/// 验证压缩数据是否有效，且主版本不低于 `min_version`、不高于 `max_version`
+pub fn validate_compressed_data_with_version_range(data: &[u8], min_version: u8, max_version: u8) -> Result<[u8; 2], io::Error> {
+    let version = validate_compressed_data_with_max_version(data, max_version)?;
+    if version[0] < min_version {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("索引版本 {}.{} 过旧，最低支持 {}.0，请重新生成索引", version[0], version[1], min_version)
+        ));
+    }
     Ok(version)
-} 
\ No newline at end of file
+}
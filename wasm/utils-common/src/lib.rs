@@ -1,6 +1,9 @@
+pub mod arena;
 pub mod compression;
 pub mod models;
+pub mod text;
 
 // 重新导出常用模块和函数，方便直接使用
-pub use compression::{to_compressed, from_compressed, to_binary, from_binary, validate_compressed_data};
-pub use models::{ArticleMetadata, Heading, IndexType, IndexMetadata}; 
\ No newline at end of file
+pub use compression::{to_compressed, from_compressed, to_binary, from_binary, validate_compressed_data, read_index_metadata, SerializationFormat};
+pub use models::{ArticleMetadata, Heading, IndexType, IndexMetadata, Visibility};
+pub use arena::{ArticleRef, ArticleTable};
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// 标题结构 - 存储文章中的标题及其层级
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Heading {
     /// 标题级别（1表示h1，2表示h2，依此类推）
     pub level: usize,
@@ -12,10 +12,13 @@ pub struct Heading {
     pub position: usize,
     /// 标题内容结束位置（下一个标题开始前或文章结束）
     pub end_position: Option<usize>,
+    /// 规范化前的原始标题文本（仅在规范化改变了文本时保存，用于原样展示）
+    #[serde(default)]
+    pub raw_text: Option<String>,
 }
 
 /// 文章元数据 - 存储索引所需的文章基本信息
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct ArticleMetadata {
     /// 文章唯一标识符
     pub id: String,
@@ -30,7 +33,7 @@ pub struct ArticleMetadata {
     /// 文章URL路径
     pub url: String,
     /// 文章内容，用于全文搜索
-    #[serde(skip_serializing_if = "String::is_empty", default)]
+    #[serde(default)]
     pub content: String,
     /// 页面类型：article（文章）、page（普通页面）
     #[serde(default = "default_page_type")]
@@ -38,6 +41,27 @@ pub struct ArticleMetadata {
     /// 文章中的标题结构
     #[serde(default)]
     pub headings: Vec<Heading>,
+    /// 文章的主要语言（如 "zh"、"en"），为空时由搜索索引构建器检测
+    #[serde(default)]
+    pub lang: String,
+    /// 媒体说明文本 - 图片的alt/title属性和figcaption中的文字
+    #[serde(default)]
+    pub media_text: String,
+    /// 文章可见性
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// 是否为置顶（推荐）文章 - 在文章列表中排在其他文章前面
+    #[serde(default)]
+    pub featured: bool,
+    /// 文章所属的系列名称 - 分为多篇发布的文章属于同一系列，不属于任何系列时为空
+    #[serde(default)]
+    pub series: Option<String>,
+    /// 文章作者，未注明作者时为空
+    #[serde(default)]
+    pub author: Option<String>,
+    /// 正文词数（按 `text::count_words` 统计），由索引器计算，为0时由筛选索引构建器根据内容计算
+    #[serde(default)]
+    pub word_count: usize,
 }
 
 /// 默认页面类型为article
@@ -45,6 +69,54 @@ fn default_page_type() -> String {
     "article".to_string()
 }
 
+/// 文章可见性 - 控制文章是否出现在归档列表和搜索结果中
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// 公开
+    #[default]
+    Public,
+    /// 不公开列出 - 仍可通过URL访问，但不出现在归档列表和搜索结果中
+    Unlisted,
+    /// 仅会员可见
+    Members,
+}
+
+impl Visibility {
+    /// 在给定的最高可见级别下是否列出该文章
+    ///
+    /// 可见级别从低到高为 public、members，unlisted 文章在任何级别下都不列出。
+    pub fn is_listed(self, max_visibility: Visibility) -> bool {
+        match self {
+            Visibility::Public => true,
+            Visibility::Unlisted => false,
+            Visibility::Members => max_visibility == Visibility::Members,
+        }
+    }
+
+    /// 可见性名称，与序列化格式一致
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Unlisted => "unlisted",
+            Visibility::Members => "members",
+        }
+    }
+}
+
+impl std::str::FromStr for Visibility {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "public" => Ok(Visibility::Public),
+            "unlisted" => Ok(Visibility::Unlisted),
+            "members" => Ok(Visibility::Members),
+            other => Err(format!("无效的可见性: {}", other)),
+        }
+    }
+}
+
 /// 索引类型 - 用于区分不同的索引
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IndexType {
@@ -56,10 +128,12 @@ pub enum IndexType {
     Search,
     /// 完整索引，包含所有内容
     Full,
+    /// 足迹地图的地理索引，包含区域表和多边形，文章和标签数量为0
+    Geo,
 }
 
-/// 索引元数据 - 存储索引的基本信息
-#[derive(Serialize, Deserialize, Debug)]
+/// 索引元数据 - 存储索引的基本信息，写入索引文件的头部
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct IndexMetadata {
     /// 索引包含的文章数量
     pub article_count: usize,
@@ -67,11 +141,11 @@ pub struct IndexMetadata {
     pub tag_count: usize,
     /// 索引创建时间
     pub created_at: DateTime<Utc>,
-    /// 索引版本
+    /// 索引格式版本（如 "7.0"）
     pub version: String,
     /// 索引类型
     pub index_type: IndexType,
-    /// 索引中的词元总数
+    /// 索引中的词元总数（各词典的词汇数之和），筛选索引为0
     pub token_count: usize,
 }
 
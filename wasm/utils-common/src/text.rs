@@ -0,0 +1,131 @@
+/// 标题锚点符号 - 主题常在标题末尾追加这些符号作为永久链接
+const ANCHOR_SYMBOLS: &[char] = &['¶', '§', '🔗', '⚓', '⛓'];
+
+/// 判断字符是否为标题末尾的装饰字符（锚点符号、emoji及其修饰符）
+fn is_trailing_decoration(c: char) -> bool {
+    ANCHOR_SYMBOLS.contains(&c)
+        || matches!(c as u32,
+            0x1F300..=0x1FAFF   // 符号和象形文字、表情
+            | 0x2600..=0x27BF   // 杂项符号、装饰符号
+            | 0xFE0E..=0xFE0F   // 变体选择符
+            | 0x200D            // 零宽连接符
+            | 0x20E3            // 组合用键帽符号
+        )
+}
+
+/// 规范化标题文本
+///
+/// 去除末尾的锚点符号（如 "Install #"、"安装 ¶"、"🔗"）并合并连续空白，
+/// 使相同标题在不同主题下得到一致的文本。索引器和搜索索引构建器都使用此函数。
+/// 紧跟在单词后的 `#` 视为正文（如 "C#"），不会被去除。
+pub fn normalize_heading_text(raw: &str) -> String {
+    let collapsed = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut text = collapsed.as_str();
+
+    loop {
+        let trimmed = text.trim_end();
+        let Some(last) = trimmed.chars().next_back() else {
+            break;
+        };
+        let rest = &trimmed[..trimmed.len() - last.len_utf8()];
+
+        let is_anchor = if last == '#' {
+            // 独立的 "#" 才是锚点
+            rest.is_empty() || rest.ends_with(char::is_whitespace)
+        } else {
+            is_trailing_decoration(last)
+        };
+
+        if !is_anchor {
+            text = trimmed;
+            break;
+        }
+        text = rest;
+    }
+
+    // 整个标题都是装饰字符时保留原文，避免产生空标题
+    if text.is_empty() {
+        return collapsed;
+    }
+
+    text.to_string()
+}
+
+/// 是否为按字计词的CJK字符（汉字、假名、谚文）
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x4E00..=0x9FFF | 0x3400..=0x4DBF | 0xF900..=0xFAFF
+        | 0x3040..=0x30FF
+        | 0xAC00..=0xD7AF | 0x1100..=0x11FF
+    )
+}
+
+/// 统计文本的词数
+///
+/// 每个汉字/假名/谚文计为一个词，其他文字按连续的字母数字计为一个词，标点和空白不计。
+pub fn count_words(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk(c) {
+            count += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                count += 1;
+            }
+            in_word = true;
+        } else {
+            in_word = false;
+        }
+    }
+    count
+}
+
+/// 估算阅读时间使用的阅读速度（词/分钟）
+///
+/// 汉字按字计词，中文约每分钟300至500字，英文约每分钟200至250词，取两者之间的保守值。
+pub const READING_WORDS_PER_MINUTE: usize = 300;
+
+/// 按词数估算阅读时间（分钟），向上取整；词数为0时为0
+pub fn reading_minutes(word_count: usize) -> u32 {
+    word_count.div_ceil(READING_WORDS_PER_MINUTE) as u32
+}
+
+/// 检测文本的主要语言，返回语言代码（"zh"、"ja"、"ko"、"en"），无法判断时返回 "und"
+///
+/// 按文字系统统计字符：一个汉字/假名/谚文大致相当于一个词，而拉丁字母平均约4个组成一个词，
+/// 以此比较两类文字的分量。
+pub fn detect_language(text: &str) -> &'static str {
+    let mut han = 0usize;
+    let mut kana = 0usize;
+    let mut hangul = 0usize;
+    let mut latin = 0usize;
+
+    for c in text.chars() {
+        match c as u32 {
+            0x4E00..=0x9FFF | 0x3400..=0x4DBF | 0xF900..=0xFAFF => han += 1,
+            0x3040..=0x30FF => kana += 1,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => hangul += 1,
+            _ if c.is_ascii_alphabetic() => latin += 1,
+            _ => {}
+        }
+    }
+
+    let cjk = han + kana + hangul;
+    if cjk == 0 && latin == 0 {
+        return "und";
+    }
+
+    if cjk * 4 >= latin {
+        if kana * 10 >= cjk {
+            "ja"
+        } else if hangul * 2 >= cjk {
+            "ko"
+        } else {
+            "zh"
+        }
+    } else {
+        "en"
+    }
+}
@@ -0,0 +1,153 @@
+//! 紧凑文章表测试 - 文章表与文章元数据相互转换不丢失字段，重复的短文本只保存一份；
+//! 区间越界或不在字符边界上的文章表无法通过检查，访问器读取为空而不会崩溃。
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use utils_common::compression::{from_binary, to_binary};
+use utils_common::models::{ArticleMetadata, Heading, Visibility};
+use utils_common::ArticleTable;
+
+fn article(id: &str, tags: &[&str]) -> ArticleMetadata {
+    ArticleMetadata {
+        id: id.to_string(),
+        title: format!("标题 {}", id),
+        summary: "摘要".to_string(),
+        date: Utc.with_ymd_and_hms(2024, 1, 10, 8, 0, 0).unwrap(),
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        url: format!("/{}", id),
+        content: "正文内容".to_string(),
+        page_type: "article".to_string(),
+        headings: vec![Heading {
+            level: 2,
+            text: "安装".to_string(),
+            position: 0,
+            end_position: Some(4),
+            raw_text: Some("安装 Rust".to_string()),
+        }],
+        lang: "zh".to_string(),
+        media_text: "图片说明".to_string(),
+        visibility: Visibility::Unlisted,
+        featured: true,
+        series: Some("入门".to_string()),
+        author: None,
+        word_count: 120,
+    }
+}
+
+#[test]
+fn round_trips_articles() {
+    let articles = vec![article("notes/rust", &["rust", "教程"]), article("notes/wasm", &["rust", "wasm"])];
+    let table = ArticleTable::from_articles(&articles);
+    table.validate().unwrap();
+
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.to_articles(), articles);
+    let decoded: ArticleTable = from_binary(&to_binary(&table).unwrap()).unwrap();
+    decoded.validate().unwrap();
+    assert_eq!(decoded.to_articles(), articles);
+
+    let wasm = decoded.get(1).unwrap();
+    assert_eq!(wasm.id(), "notes/wasm");
+    assert_eq!(wasm.tags().collect::<Vec<_>>(), ["rust", "wasm"]);
+    assert_eq!(wasm.series(), Some("入门"));
+    assert!(decoded.get(2).is_none());
+}
+
+#[test]
+fn interns_repeated_text() {
+    let shared = ArticleTable::from_articles(&[article("a", &["rust"]), article("b", &["rust"])]);
+    let distinct = ArticleTable::from_articles(&[article("a", &["rust"]), article("b", &["wasm"])]);
+
+    // 相同的标签只写入一次文本块，长度相同的不同标签需要再写入一次
+    let size = |table: &ArticleTable| to_binary(table).unwrap().len();
+    assert!(size(&shared) < size(&distinct));
+    assert_eq!(shared.get(1).unwrap().tags().collect::<Vec<_>>(), ["rust"]);
+}
+
+/// 与文章表相同的序列化布局，用于构造损坏的文章表
+#[derive(Serialize)]
+struct RawTable {
+    text: String,
+    records: Vec<RawRecord>,
+    tags: Vec<(u32, u32)>,
+    headings: Vec<RawHeading>,
+}
+
+/// 标题记录：级别、文本、位置、结束位置和原始文本
+type RawHeading = (u32, (u32, u32), u32, Option<u32>, Option<(u32, u32)>);
+
+#[derive(Serialize)]
+struct RawRecord {
+    id: (u32, u32),
+    title: (u32, u32),
+    summary: (u32, u32),
+    date: DateTime<Utc>,
+    tags: (u32, u32),
+    url: (u32, u32),
+    content: (u32, u32),
+    page_type: (u32, u32),
+    headings: (u32, u32),
+    lang: (u32, u32),
+    media_text: (u32, u32),
+    visibility: Visibility,
+    featured: bool,
+    series: Option<(u32, u32)>,
+    author: Option<(u32, u32)>,
+    word_count: u32,
+}
+
+fn raw_record() -> RawRecord {
+    RawRecord {
+        id: (0, 1),
+        title: (0, 0),
+        summary: (0, 0),
+        date: Utc.with_ymd_and_hms(2024, 1, 10, 8, 0, 0).unwrap(),
+        tags: (0, 1),
+        url: (0, 0),
+        content: (0, 0),
+        page_type: (0, 0),
+        headings: (0, 0),
+        lang: (0, 0),
+        media_text: (0, 0),
+        visibility: Visibility::Public,
+        featured: false,
+        series: None,
+        author: None,
+        word_count: 0,
+    }
+}
+
+fn decode(raw: &RawTable) -> ArticleTable {
+    from_binary(&to_binary(raw).unwrap()).unwrap()
+}
+
+#[test]
+fn rejects_out_of_range_spans() {
+    let valid = decode(&RawTable { text: "a标签".to_string(), records: vec![raw_record()], tags: vec![(1, 6)], headings: Vec::new() });
+    valid.validate().unwrap();
+    assert_eq!(valid.get(0).unwrap().tags().collect::<Vec<_>>(), ["标签"]);
+
+    let corrupted = [
+        // 标签区间超出标签列表
+        RawTable { text: "a".to_string(), records: vec![RawRecord { tags: (0, 2), ..raw_record() }], tags: vec![(0, 1)], headings: Vec::new() },
+        // 起始位置加数量溢出
+        RawTable { text: "a".to_string(), records: vec![RawRecord { headings: (u32::MAX, 2), ..raw_record() }], tags: vec![(0, 1)], headings: Vec::new() },
+        // 文本区间超出文本块
+        RawTable { text: "a".to_string(), records: vec![RawRecord { summary: (0, 10), ..raw_record() }], tags: vec![(0, 1)], headings: Vec::new() },
+        // 文本区间不在字符边界上
+        RawTable { text: "a标签".to_string(), records: vec![raw_record()], tags: vec![(1, 2)], headings: Vec::new() },
+        // 标题文本超出文本块
+        RawTable { text: "a".to_string(), records: vec![RawRecord { headings: (0, 1), ..raw_record() }], tags: vec![(0, 1)], headings: vec![(2, (0, 5), 0, None, None)] },
+    ];
+    for raw in &corrupted {
+        let table = decode(raw);
+        assert!(table.validate().is_err());
+        // 未经检查的文章表也不会因越界而崩溃
+        let article = table.get(0).unwrap();
+        let _ = article.to_metadata();
+    }
+    let table = decode(&corrupted[0]);
+    assert_eq!(table.get(0).unwrap().tags().count(), 0);
+    let table = decode(&corrupted[2]);
+    assert_eq!(table.get(0).unwrap().summary(), "");
+}
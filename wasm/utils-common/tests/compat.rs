@@ -0,0 +1,140 @@
+//! 格式兼容性测试 - 解码检入的样本文件，确保对模型和压缩格式的修改不会让已部署的
+//! 索引文件无法读取。
+//!
+//! 样本文件位于 `tests/fixtures`，筛选和搜索模块的兼容性测试共用同一目录和同一份
+//! 样本文章（`articles.json`）。修改索引结构时需要提升写入的格式版本，并设置环境变量
+//! `UPDATE_GOLDEN=1` 运行测试重新生成样本文件，再检入新的样本。
+
+use std::path::PathBuf;
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use utils_common::compression::{
+    from_compressed, from_compressed_with_max_version, from_compressed_with_version_range, read_format, read_index_metadata, to_compressed,
+    to_compressed_with_format, to_compressed_with_header, validate_compressed_data_with_max_version,
+    SerializationFormat, HEADER_MAGIC_BYTES,
+};
+use utils_common::models::{IndexMetadata, IndexType};
+
+/// 样本数据 - 只用于检查压缩格式，结构不随索引模型变化
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Sample {
+    name: String,
+    values: Vec<u32>,
+    tags: Vec<String>,
+}
+
+fn sample() -> Sample {
+    Sample {
+        name: "样本".to_string(),
+        values: vec![1, 2, 3, 65536],
+        tags: vec!["rust".to_string(), "标签".to_string()],
+    }
+}
+
+fn metadata() -> IndexMetadata {
+    IndexMetadata {
+        article_count: 3,
+        tag_count: 4,
+        created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        version: "8.0".to_string(),
+        index_type: IndexType::Search,
+        token_count: 42,
+    }
+}
+
+/// 读取样本文件，设置 `UPDATE_GOLDEN` 时先用 `generate` 重新生成
+fn fixture(name: &str, generate: impl FnOnce() -> Vec<u8>) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, generate()).unwrap();
+    }
+    std::fs::read(&path).unwrap_or_else(|e| panic!("无法读取样本文件 {}: {}", path.display(), e))
+}
+
+#[test]
+fn decodes_legacy_frame_without_header() {
+    let data = fixture("frame-v4.bin", || to_compressed(&sample(), [4, 0]).unwrap());
+
+    assert_eq!(validate_compressed_data_with_max_version(&data, 4).unwrap(), [4, 0]);
+    assert_eq!(from_compressed::<Sample>(&data).unwrap(), sample());
+    assert!(read_index_metadata(&data, 4).unwrap().is_none());
+}
+
+#[test]
+fn decodes_frame_with_metadata_header() {
+    let data = fixture("frame-header-v8.bin", || {
+        to_compressed_with_header(&sample(), [8, 0], &metadata()).unwrap()
+    });
+
+    assert_eq!(validate_compressed_data_with_max_version(&data, 9).unwrap(), [8, 0]);
+    assert_eq!(from_compressed_with_max_version::<Sample>(&data, 9).unwrap(), sample());
+
+    let header = read_index_metadata(&data, 9).unwrap().expect("缺少索引元数据头部");
+    let expected = metadata();
+    assert_eq!(header.article_count, expected.article_count);
+    assert_eq!(header.tag_count, expected.tag_count);
+    assert_eq!(header.created_at, expected.created_at);
+    assert_eq!(header.version, expected.version);
+    assert_eq!(header.index_type, expected.index_type);
+    assert_eq!(header.token_count, expected.token_count);
+}
+
+#[test]
+fn decodes_messagepack_frame() {
+    let data = fixture("frame-msgpack-v8.bin", || {
+        to_compressed_with_format(&sample(), [8, 0], Some(&metadata()), SerializationFormat::MessagePack).unwrap()
+    });
+
+    assert_eq!(validate_compressed_data_with_max_version(&data, 9).unwrap(), [8, 0]);
+    assert_eq!(read_format(&data, 9).unwrap(), SerializationFormat::MessagePack);
+    assert_eq!(from_compressed_with_max_version::<Sample>(&data, 9).unwrap(), sample());
+
+    let header = read_index_metadata(&data, 9).unwrap().expect("缺少索引元数据头部");
+    assert_eq!(header.article_count, metadata().article_count);
+    assert_eq!(header.created_at, metadata().created_at);
+    assert_eq!(header.index_type, metadata().index_type);
+}
+
+#[test]
+fn bincode_format_writes_legacy_frame() {
+    let data = to_compressed_with_format(&sample(), [8, 0], Some(&metadata()), SerializationFormat::Bincode).unwrap();
+
+    assert!(data.starts_with(HEADER_MAGIC_BYTES));
+    assert_eq!(read_format(&data, 9).unwrap(), SerializationFormat::Bincode);
+    assert_eq!(from_compressed_with_max_version::<Sample>(&data, 9).unwrap(), sample());
+
+    let data = to_compressed_with_format::<_, IndexMetadata>(&sample(), [4, 0], None, SerializationFormat::MessagePack).unwrap();
+    assert!(read_index_metadata(&data, 4).unwrap().is_none());
+    assert_eq!(from_compressed::<Sample>(&data).unwrap(), sample());
+}
+
+#[test]
+fn rejects_newer_versions() {
+    let data = to_compressed_with_header(&sample(), [8, 0], &metadata()).unwrap();
+
+    assert!(from_compressed_with_max_version::<Sample>(&data, 7).is_err());
+}
+
+#[test]
+fn rejects_versions_below_minimum() {
+    let data = to_compressed_with_header(&sample(), [7, 0], &metadata()).unwrap();
+
+    // 旧版本不尝试解码，直接提示重新生成索引
+    let error = from_compressed_with_version_range::<Sample>(&data, 8, 9).unwrap_err();
+    assert!(error.to_string().contains("请重新生成索引"), "{}", error);
+    assert_eq!(from_compressed_with_version_range::<Sample>(&data, 7, 9).unwrap(), sample());
+}
+
+#[test]
+fn rejects_truncated_headers() {
+    let mut data = to_compressed_with_header(&sample(), [8, 0], &metadata()).unwrap();
+    let len_offset = HEADER_MAGIC_BYTES.len() + 2;
+
+    // 头部长度超出数据长度时返回错误，不越界读取
+    data[len_offset..len_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let error = validate_compressed_data_with_max_version(&data, 8).unwrap_err();
+    assert!(error.to_string().contains("头部不完整"), "{}", error);
+
+    data.truncate(len_offset + 2);
+    assert!(validate_compressed_data_with_max_version(&data, 8).is_err());
+}